@@ -0,0 +1,61 @@
+//! NEP-297 events emitted by the token on top of the nep141 ones, logged as `EVENT_JSON:{...}`.
+use near_sdk::serde::Serialize;
+use near_sdk::{env, serde_json, AccountId};
+
+use crate::fungible_token::{Feature, Role};
+
+pub const EVENT_STANDARD: &str = "openedu_token";
+pub const EVENT_STANDARD_VERSION: &str = "1.0.0";
+
+#[derive(Serialize)]
+#[serde(crate = "near_sdk::serde")]
+struct EventLog<'a, T: Serialize> {
+    standard: &'static str,
+    version: &'static str,
+    event: &'a str,
+    data: &'a [T],
+}
+
+fn emit_event<T: Serialize>(event: &str, data: &[T]) {
+    let log = EventLog {
+        standard: EVENT_STANDARD,
+        version: EVENT_STANDARD_VERSION,
+        event,
+        data,
+    };
+    env::log_str(&format!("EVENT_JSON:{}", serde_json::to_string(&log).unwrap()));
+}
+
+#[derive(Serialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct RoleChanged<'a> {
+    pub role: &'a Role,
+    pub account_id: &'a AccountId,
+}
+
+impl RoleChanged<'_> {
+    pub fn emit_granted(self) {
+        emit_event("role_granted", &[self])
+    }
+
+    pub fn emit_revoked(self) {
+        emit_event("role_revoked", &[self])
+    }
+}
+
+#[derive(Serialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct PauseChanged<'a> {
+    pub features: &'a [Feature],
+    pub account_id: &'a AccountId,
+}
+
+impl PauseChanged<'_> {
+    pub fn emit_paused(self) {
+        emit_event("paused", &[self])
+    }
+
+    pub fn emit_unpaused(self) {
+        emit_event("unpaused", &[self])
+    }
+}
@@ -1,17 +1,61 @@
 use near_contract_standards::fungible_token::metadata::{
     FungibleTokenMetadata, FungibleTokenMetadataProvider, FT_METADATA_SPEC,
 };
+use near_contract_standards::fungible_token::core::FungibleTokenCore;
+use near_contract_standards::fungible_token::resolver::FungibleTokenResolver;
 use near_contract_standards::fungible_token::{events, FungibleToken};
 use near_sdk::borsh::{self, BorshDeserialize, BorshSerialize};
-use near_sdk::collections::LazyOption;
+use near_sdk::collections::{LazyOption, LookupMap, UnorderedSet};
 use near_sdk::json_types::U128;
+use near_sdk::serde::{Deserialize, Serialize};
 use near_sdk::{env, log, near_bindgen, AccountId, Balance, PanicOnDefault, PromiseOrValue};
 
+use crate::events::{PauseChanged, RoleChanged};
+
 #[near_bindgen]
 #[derive(BorshDeserialize, BorshSerialize, PanicOnDefault)]
 pub struct Contract {
     token: FungibleToken,
     metadata: LazyOption<FungibleTokenMetadata>,
+    owner_id: AccountId,
+    role_members: LookupMap<Role, UnorderedSet<AccountId>>,
+    paused_features: Vec<Feature>,
+}
+
+/// Layout deployed before the token could be paused.
+#[derive(BorshDeserialize)]
+struct ContractV0 {
+    token: FungibleToken,
+    metadata: LazyOption<FungibleTokenMetadata>,
+}
+
+/// Roles the owner can grant. The owner implicitly holds every role.
+#[derive(BorshDeserialize, BorshSerialize, Deserialize, Serialize, Clone, PartialEq, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub enum Role {
+    Pauser, // pauses the token in an emergency
+}
+
+impl Role {
+    pub const ALL: [Role; 1] = [Role::Pauser];
+
+    fn members_prefix(&self) -> Vec<u8> {
+        let mut prefix = b"role".to_vec();
+        prefix.extend_from_slice(&self.try_to_vec().unwrap());
+        prefix
+    }
+}
+
+/// Parts of the token a pauser can halt independently during an incident.
+#[derive(BorshDeserialize, BorshSerialize, Deserialize, Serialize, Clone, PartialEq, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub enum Feature {
+    Transfer, // ft_transfer and ft_transfer_call
+    Burn,     // ft_burn
+}
+
+impl Feature {
+    pub const ALL: [Feature; 2] = [Feature::Transfer, Feature::Burn];
 }
 
 const DATA_IMAGE_SVG_NEAR_ICON: &str = "data:image/svg+xml,%3Csvg xmlns='http://www.w3.org/2000/svg' viewBox='0 0 288 288'%3E%3Cg id='l' data-name='l'%3E%3Cpath d='M187.58,79.81l-30.1,44.69a3.2,3.2,0,0,0,4.75,4.2L191.86,103a1.2,1.2,0,0,1,2,.91v80.46a1.2,1.2,0,0,1-2.12.77L102.18,77.93A15.35,15.35,0,0,0,90.47,72.5H87.34A15.34,15.34,0,0,0,72,87.84V201.16A15.34,15.34,0,0,0,87.34,216.5h0a15.35,15.35,0,0,0,13.08-7.31l30.1-44.69a3.2,3.2,0,0,0-4.75-4.2L96.14,186a1.2,1.2,0,0,1-2-.91V104.61a1.2,1.2,0,0,1,2.12-.77l89.55,107.23a15.35,15.35,0,0,0,11.71,5.43h3.13A15.34,15.34,0,0,0,216,201.16V87.84A15.34,15.34,0,0,0,200.66,72.5h0A15.35,15.35,0,0,0,187.58,79.81Z'/%3E%3C/g%3E%3C/svg%3E";
@@ -46,6 +90,9 @@ impl Contract {
         let mut this = Self {
             token: FungibleToken::new(b"a".to_vec()),
             metadata: LazyOption::new(b"m".to_vec(), Some(&metadata)),
+            owner_id: owner_id.clone(),
+            role_members: LookupMap::new(b"r".to_vec()),
+            paused_features: Vec::new(),
         };
         this.token.internal_register_account(&owner_id);
         this.token.internal_deposit(&owner_id, total_supply.into());
@@ -58,7 +105,25 @@ impl Contract {
         this
     }
 
+    /// Adds the pause flags to a token deployed before they existed, `owner_id` grants the roles.
+    /// Called by the token account itself in the same batch as the redeployment.
+    #[private]
+    #[init(ignore_state)]
+    pub fn migrate(owner_id: AccountId) -> Self {
+        let old: ContractV0 = env::state_read().expect("Failed to read old state");
+
+        Self {
+            token: old.token,
+            metadata: old.metadata,
+            owner_id,
+            role_members: LookupMap::new(b"r".to_vec()),
+            paused_features: Vec::new(),
+        }
+    }
+
     pub fn ft_burn(&mut self, account_id: AccountId, amount: U128) {
+        self.assert_not_paused(Feature::Burn);
+
         if !self.token.accounts.contains_key(&account_id) {
             self.token.internal_register_account(&account_id);
         }
@@ -66,13 +131,121 @@ impl Contract {
         self.token.internal_withdraw(&account_id, amount.into());
                     
         events::FtBurn {
-            amount: &amount.into(),
+            amount: &amount,
             owner_id: &account_id,
             memo: None,
         }
         .emit()
     }
 
+    /// Grants `role` to `account_id`. Only the owner can call it.
+    pub fn grant_role(&mut self, role: Role, account_id: AccountId) {
+        self.assert_owner();
+
+        let mut members = self.role_members.get(&role).unwrap_or_else(|| UnorderedSet::new(role.members_prefix()));
+        assert!(members.insert(&account_id), "Account already has this role");
+        self.role_members.insert(&role, &members);
+
+        RoleChanged {
+            role: &role,
+            account_id: &account_id,
+        }
+        .emit_granted();
+    }
+
+    /// Revokes `role` from `account_id`. Only the owner can call it.
+    pub fn revoke_role(&mut self, role: Role, account_id: AccountId) {
+        self.assert_owner();
+
+        let mut members = self.role_members.get(&role).expect("Account does not have this role");
+        assert!(members.remove(&account_id), "Account does not have this role");
+        self.role_members.insert(&role, &members);
+
+        RoleChanged {
+            role: &role,
+            account_id: &account_id,
+        }
+        .emit_revoked();
+    }
+
+    /// Halts the given features, every feature when none are given.
+    pub fn pause(&mut self, features: Option<Vec<Feature>>) {
+        self.assert_role(Role::Pauser);
+
+        let features = features.unwrap_or_else(|| Feature::ALL.to_vec());
+        for feature in features.iter() {
+            if !self.paused_features.contains(feature) {
+                self.paused_features.push(feature.clone());
+            }
+        }
+
+        PauseChanged {
+            features: &features,
+            account_id: &env::predecessor_account_id(),
+        }
+        .emit_paused();
+    }
+
+    /// Resumes the given features, every feature when none are given.
+    pub fn unpause(&mut self, features: Option<Vec<Feature>>) {
+        self.assert_role(Role::Pauser);
+
+        let features = features.unwrap_or_else(|| Feature::ALL.to_vec());
+        self.paused_features.retain(|feature| !features.contains(feature));
+
+        PauseChanged {
+            features: &features,
+            account_id: &env::predecessor_account_id(),
+        }
+        .emit_unpaused();
+    }
+
+    pub fn is_paused(&self, feature: Feature) -> bool {
+        self.paused_features.contains(&feature)
+    }
+
+    pub fn get_paused_features(&self) -> Vec<Feature> {
+        self.paused_features.clone()
+    }
+
+    pub fn has_role(&self, role: Role, account_id: AccountId) -> bool {
+        self.internal_has_role(&role, &account_id)
+    }
+
+    pub fn get_role_members(&self, role: Role) -> Vec<AccountId> {
+        self.role_members.get(&role).map_or_else(Vec::new, |members| members.to_vec())
+    }
+
+    // roles granted to the account, the owner holds all of them implicitly
+    pub fn get_roles(&self, account_id: AccountId) -> Vec<Role> {
+        Role::ALL
+            .into_iter()
+            .filter(|role| self.internal_has_role(role, &account_id))
+            .collect()
+    }
+
+    fn assert_owner(&self) {
+        assert_eq!(env::predecessor_account_id(), self.owner_id, "Only the owner can call this method");
+    }
+
+    fn internal_has_role(&self, role: &Role, account_id: &AccountId) -> bool {
+        account_id == &self.owner_id
+            || self.role_members.get(role).is_some_and(|members| members.contains(account_id))
+    }
+
+    // the owner holds every role
+    fn assert_role(&self, role: Role) {
+        assert!(
+            self.internal_has_role(&role, &env::predecessor_account_id()),
+            "Caller does not have the {:?} role",
+            role
+        );
+    }
+
+    fn assert_not_paused(&self, feature: Feature) {
+        assert!(!self.paused_features.contains(&feature), "{:?} is paused", feature);
+    }
+
     fn on_account_closed(&mut self, account_id: AccountId, balance: Balance) {
         log!("Closed @{} with {}", account_id, balance);
     }
@@ -82,7 +255,50 @@ impl Contract {
     }
 }
 
-near_contract_standards::impl_fungible_token_core!(Contract, token, on_tokens_burned);
+// the core methods of `impl_fungible_token_core!`, with transfers gated by the pause flag
+#[near_bindgen]
+impl FungibleTokenCore for Contract {
+    #[payable]
+    fn ft_transfer(&mut self, receiver_id: AccountId, amount: U128, memo: Option<String>) {
+        self.assert_not_paused(Feature::Transfer);
+        self.token.ft_transfer(receiver_id, amount, memo)
+    }
+
+    #[payable]
+    fn ft_transfer_call(
+        &mut self,
+        receiver_id: AccountId,
+        amount: U128,
+        memo: Option<String>,
+        msg: String,
+    ) -> PromiseOrValue<U128> {
+        self.assert_not_paused(Feature::Transfer);
+        self.token.ft_transfer_call(receiver_id, amount, memo, msg)
+    }
+
+    fn ft_total_supply(&self) -> U128 {
+        self.token.ft_total_supply()
+    }
+
+    fn ft_balance_of(&self, account_id: AccountId) -> U128 {
+        self.token.ft_balance_of(account_id)
+    }
+}
+
+// not gated, so an ft_transfer_call started before a pause still returns the unused tokens
+#[near_bindgen]
+impl FungibleTokenResolver for Contract {
+    #[private]
+    fn ft_resolve_transfer(&mut self, sender_id: AccountId, receiver_id: AccountId, amount: U128) -> U128 {
+        let (used_amount, burned_amount) =
+            self.token.internal_ft_resolve_transfer(&sender_id, receiver_id, amount);
+        if burned_amount > 0 {
+            self.on_tokens_burned(sender_id, burned_amount);
+        }
+        used_amount.into()
+    }
+}
+
 near_contract_standards::impl_fungible_token_storage!(Contract, token, on_account_closed);
 
 #[near_bindgen]
@@ -0,0 +1,210 @@
+use near_gas::NearGas;
+use near_sdk::json_types::{Base64VecU8, U128, U64};
+use near_token::NearToken;
+use near_units::parse_near;
+use serde_json::{json, Value};
+#[allow(dead_code)]
+mod helpers;
+use near_workspaces::{Account, Contract};
+
+use helpers::{PoolMetadata, Status};
+
+const LAUNCHPAD_WASM_FILEPATH: &str = "../res/launchpad.wasm";
+const FT_WASM_FILEPATH: &str = "../res/ft_token.wasm";
+const DAO_WASM_FILEPATH: &str = "../res/mock_dao.wasm";
+
+const INITIAL_NEAR: NearToken = NearToken::from_near(30);
+const INIT_POOL: NearToken = NearToken::from_near(1);
+const DAO_GAS: NearGas = NearGas::from_tgas(300);
+
+#[tokio::main]
+async fn main() -> anyhow::Result<()> {
+    let worker = near_workspaces::sandbox().await?;
+
+    let ft_wasm = std::fs::read(FT_WASM_FILEPATH)?;
+    let ft_contract = worker.dev_deploy(&ft_wasm).await?;
+
+    let launchpad_wasm = std::fs::read(LAUNCHPAD_WASM_FILEPATH)?;
+    let launchpad_contract = worker.dev_deploy(&launchpad_wasm).await?;
+
+    let dao_wasm = std::fs::read(DAO_WASM_FILEPATH)?;
+    let dao_contract = worker.dev_deploy(&dao_wasm).await?;
+
+    let owner = worker.root_account().unwrap();
+
+    let owner_launchpad = owner
+        .create_subaccount("launchpad")
+        .initial_balance(INITIAL_NEAR)
+        .transact()
+        .await?
+        .into_result()?;
+
+    let council1 = owner
+        .create_subaccount("council1")
+        .initial_balance(INITIAL_NEAR)
+        .transact()
+        .await?
+        .into_result()?;
+
+    let council2 = owner
+        .create_subaccount("council2")
+        .initial_balance(INITIAL_NEAR)
+        .transact()
+        .await?
+        .into_result()?;
+
+    let creator = owner
+        .create_subaccount("creator")
+        .initial_balance(INITIAL_NEAR)
+        .transact()
+        .await?
+        .into_result()?;
+
+    ft_contract
+        .call("new_default_meta")
+        .args_json(json!({
+            "owner_id": owner.id(),
+            "total_supply": U128::from(parse_near!("1,000,000,000 N")),
+        }))
+        .transact()
+        .await?
+        .into_result()?;
+
+    owner_launchpad
+        .call(launchpad_contract.id(), "init")
+        .args_json(json!({}))
+        .transact()
+        .await?
+        .into_result()?;
+
+    dao_contract
+        .call("new")
+        .args_json(json!({"council": [council1.id(), council2.id()]}))
+        .transact()
+        .await?
+        .into_result()?;
+
+    // begin tests
+    test_dao_becomes_admin(&launchpad_contract, &dao_contract, &owner_launchpad, &council1, &council2).await?;
+    test_dao_administers_launchpad(&launchpad_contract, &dao_contract, &ft_contract, &creator, &council1, &council2).await?;
+    Ok(())
+}
+
+// council1 proposes a call from the DAO account and council2's approval executes it
+async fn dao_act(
+    dao_contract: &Contract,
+    council1: &Account,
+    council2: &Account,
+    receiver: &Contract,
+    method_name: &str,
+    args: Value
+) -> anyhow::Result<()> {
+    let proposal_id: u64 = council1
+        .call(dao_contract.id(), "add_proposal")
+        .args_json(json!({
+            "receiver_id": receiver.id(),
+            "method_name": method_name,
+            "args": Base64VecU8::from(serde_json::to_vec(&args)?),
+            "deposit": U128(0),
+            "gas": U64(NearGas::from_tgas(100).as_gas()),
+        }))
+        .gas(DAO_GAS)
+        .transact()
+        .await?
+        .json()?;
+
+    council2
+        .call(dao_contract.id(), "approve")
+        .args_json(json!({"proposal_id": proposal_id}))
+        .gas(DAO_GAS)
+        .transact()
+        .await?
+        .into_result()?;
+
+    Ok(())
+}
+
+pub async fn test_dao_becomes_admin(
+    launchpad_contract: &Contract,
+    dao_contract: &Contract,
+    owner_launchpad: &Account,
+    council1: &Account,
+    council2: &Account
+) -> anyhow::Result<()> {
+
+    owner_launchpad
+        .call(launchpad_contract.id(), "change_admin")
+        .args_json(json!({"new_admin": dao_contract.id()}))
+        .transact()
+        .await?
+        .into_result()?;
+
+    // the DAO contract accepts the ownership, no key of the DAO account signs anything
+    dao_act(dao_contract, council1, council2, launchpad_contract, "accept_admin", json!({})).await?;
+
+    let owner: String = launchpad_contract.call("get_owner").view().await?.json()?;
+    assert_eq!(owner, dao_contract.id().to_string(), "DAO should own the launchpad.");
+
+    println!("      Passed ✅ test_dao_becomes_admin");
+    Ok(())
+}
+
+pub async fn test_dao_administers_launchpad(
+    launchpad_contract: &Contract,
+    dao_contract: &Contract,
+    ft_contract: &Contract,
+    creator: &Account,
+    council1: &Account,
+    council2: &Account
+) -> anyhow::Result<()> {
+
+    // council members sign the transactions but are not admins themselves
+    let outcome = council1
+        .call(launchpad_contract.id(), "set_refund_reject_pool")
+        .args_json(json!({"percent": 20}))
+        .transact()
+        .await?;
+    assert!(outcome.is_failure(), "Council member should not act as admin directly.");
+
+    dao_act(dao_contract, council1, council2, launchpad_contract, "set_refund_reject_pool", json!({"percent": 20})).await?;
+
+    let refund_percent: u8 = launchpad_contract.call("get_refund_reject_pool").view().await?.json()?;
+    assert_eq!(refund_percent, 20, "DAO should set the refund percentage.");
+
+    dao_act(dao_contract, council1, council2, launchpad_contract, "add_token", json!({"token_id": ft_contract.id()})).await?;
+
+    let token_valid: bool = launchpad_contract
+        .call("is_token_supported")
+        .args_json(json!({"token_id": ft_contract.id()}))
+        .view()
+        .await?
+        .json()?;
+    assert!(token_valid, "DAO should add the token.");
+
+    creator
+        .call(launchpad_contract.id(), "init_pool")
+        .args_json(json!({
+            "campaign_id": "dao-campaign",
+            "token_id": ft_contract.id(),
+            "min_multiple_pledge": 10000,
+            "target_funding": "10000000"
+        }))
+        .deposit(INIT_POOL)
+        .transact()
+        .await?
+        .into_result()?;
+
+    dao_act(dao_contract, council1, council2, launchpad_contract, "admin_set_status_pool_pre_funding", json!({"pool_id": 1, "approve": true})).await?;
+
+    let pool: PoolMetadata = launchpad_contract
+        .call("get_detail_pool")
+        .args_json(json!({"pool_id": 1}))
+        .view()
+        .await?
+        .json::<Option<PoolMetadata>>()?
+        .unwrap();
+    assert_eq!(pool.status, Status::APPROVED, "DAO should approve the pool.");
+
+    println!("      Passed ✅ test_dao_administers_launchpad");
+    Ok(())
+}
@@ -1,11 +1,8 @@
 
-use near_sdk::json_types::Base64VecU8;
 use near_sdk::AccountId;
-use std::collections::HashMap;
 use near_token::NearToken;
 use near_workspaces::{Account, Contract};
 use serde::{Deserialize, Serialize};
-use serde_json::json;
 
 pub type PoolId = u64;
 
@@ -21,9 +18,101 @@ pub struct PoolMetadata {
     pub token_id: AccountId,
     pub total_balance: u128,
     pub target_funding: u128,
+    pub time_init: u64,
     pub time_start_pledge: u64,
     pub time_end_pledge: u64,
+    pub funding_duration_days: u64,
     pub min_multiple_pledge: u128,
+    pub total_voting_power: u128,
+    pub min_pledge: u128,
+    pub max_pledge: u128,
+    pub hard_cap: u128,
+    pub withdraw_penalty_percent: u8,
+    pub tokens: Vec<PoolToken>,
+}
+
+
+#[derive(Deserialize, Serialize, Clone, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct PoolToken {
+    pub token_id: AccountId,
+    pub balance: u128,
+    pub pledged: u128,
+}
+
+
+#[derive(Deserialize, Serialize, Clone, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct ReferralStats {
+    pub referrer_id: AccountId,
+    pub backers: u32,
+    pub pledged: u128,
+    pub tokens: Vec<TokenPledge>,
+    pub rewarded_tokens: Vec<AccountId>,
+}
+
+#[derive(Deserialize, Serialize, Clone, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct ReferralReserve {
+    pub referred: u128,
+    pub reserved: u128,
+    pub paid: u128,
+}
+
+
+#[derive(Deserialize, Serialize, Clone, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct RewardTier {
+    pub min_pledge: u128,
+    pub quantity: u32,
+    pub allocated: u32,
+    pub description: String,
+}
+
+
+#[derive(Deserialize, Serialize, Clone, Debug, PartialEq)]
+#[serde(crate = "near_sdk::serde")]
+pub struct FundingTerms {
+    pub waiting_threshold_percent: u8,
+    pub waiting_extension: u64,
+    pub init_expiry: u64,
+}
+
+
+#[derive(Deserialize, Serialize, Clone, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct StretchGoal {
+    pub amount: u128,
+    pub description: String,
+    pub unlocked: bool,
+}
+
+
+#[derive(Deserialize, Serialize, Clone, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct BackerTier {
+    pub tier_index: u32,
+    pub tier: RewardTier,
+}
+
+
+#[derive(Deserialize, Serialize, Clone, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct TokenPledge {
+    pub token_id: AccountId,
+    pub amount: u128,
+    pub value: u128,
+}
+
+
+#[derive(Deserialize, Serialize, Clone, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct TreasuryLedger {
+    pub balance: u128,
+    pub retained_stakes: u128,
+    pub slashed_stakes: u128,
+    pub keeper_rewards_paid: u128,
+    pub withdrawn: u128,
 }
 
 
@@ -37,8 +126,10 @@ pub struct Assets {
 
 #[derive(Deserialize, Serialize, Clone, Debug, PartialEq)]
 #[serde(crate = "near_sdk::serde")]
+#[allow(clippy::upper_case_acronyms)]
 pub enum Status {
     INIT,
+    APPROVED,
     FUNDING,
     REJECTED,
     CANCELED,
@@ -52,11 +143,58 @@ pub enum Status {
 
 
 
+#[derive(Deserialize, Serialize, Clone, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct Milestone {
+    pub percent: u8,
+    pub deadline: u64,
+    pub status: MilestoneStatus,
+    pub amount_released: u128,
+}
+
+
+#[derive(Deserialize, Serialize, Clone, Debug, PartialEq)]
+#[serde(crate = "near_sdk::serde")]
+#[allow(clippy::upper_case_acronyms)]
+pub enum MilestoneStatus {
+    PENDING,
+    APPROVED,
+    RELEASED,
+    FAILED
+}
+
+
+#[derive(Deserialize, Serialize, Clone, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct Proposal {
+    pub pool_id: PoolId,
+    pub milestone_index: u32,
+    pub description: String,
+    pub time_start_voting: u64,
+    pub time_end_voting: u64,
+    pub approve_power: u128,
+    pub reject_power: u128,
+    pub status: ProposalStatus,
+}
+
+
+#[derive(Deserialize, Serialize, Clone, Debug, PartialEq)]
+#[serde(crate = "near_sdk::serde")]
+#[allow(clippy::upper_case_acronyms)]
+pub enum ProposalStatus {
+    ACTIVE,
+    APPROVED,
+    REJECTED,
+    EXPIRED
+}
+
+
+
 #[derive(Deserialize, Serialize, Clone, Debug)]
 #[serde(crate = "near_sdk::serde")]
 pub struct UserTokenDepositRecord {
     pub amount: u128, // pledge amount if backer deposited +amount
-    pub voting_power: f64, // 0
+    pub voting_power: u128, // share of the pool, equal to amount once funding ends
 }
 
 #[derive(Deserialize, Serialize, Clone, Debug)]
@@ -66,6 +204,13 @@ pub struct UserRecordDetail {
     pub record: UserTokenDepositRecord,
 }
 
+#[derive(Deserialize, Serialize, Clone, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct BackerPoolDetail {
+    pub pool: PoolMetadata,
+    pub record: Option<UserTokenDepositRecord>,
+}
+
 pub async fn storage_deposit(
     owner: &Account,
     ft_contract: &Contract,
@@ -1,26 +1,32 @@
+use ed25519_dalek::{Keypair, PublicKey, SecretKey, Signer};
 use near_gas::NearGas;
 use near_token::NearToken;
 use near_units::parse_near;
 use serde_json::json;
 mod helpers;
+use near_sdk::base64::encode;
 use near_sdk::json_types::U128;
-use near_workspaces::{Account, Contract};
+use near_workspaces::{network::Sandbox, Account, Contract, Worker};
 use tokio::time::{sleep, Duration};
 
 use helpers::{
-    storage_deposit, Status, PoolMetadata, UserRecordDetail
+    storage_deposit, Assets, BackerTier, FundingTerms, Milestone, MilestoneStatus, ReferralReserve, ReferralStats, RewardTier, Status, StretchGoal, PoolMetadata, Proposal, ProposalStatus, TokenPledge, TreasuryLedger, UserRecordDetail, BackerPoolDetail
 };
 
-use crate::helpers::{};
-
 const LAUNCHPAD_WASM_FILEPATH: &str = "../res/launchpad.wasm";
 const FT_WASM_FILEPATH: &str = "../res/ft_token.wasm";
+const PAYMENT_WASM_FILEPATH: &str = "../res/payment.wasm";
+const NFT_WASM_FILEPATH: &str = "../res/nft_25519.wasm";
 
 const INITIAL_NEAR: NearToken = NearToken::from_near(30);
 
 const DEFAULT_DEPOSIT: NearToken = NearToken::from_yoctonear(1);
 const DEFAULT_GAS: NearGas = NearGas::from_tgas(200);
 const INIT_POOL: NearToken = NearToken::from_near(1);
+// secret key of the platform key signing pledges to signature-gated pools
+const PLEDGE_SIGNER_SECRET: [u8; 32] = [7; 32];
+// blocks produced to measure how far one sandbox block moves the clock
+const FAST_FORWARD_PROBE_BLOCKS: u64 = 100;
 
 
 #[tokio::main]
@@ -99,10 +105,30 @@ async fn main() -> anyhow::Result<()> {
     test_add_token(&launchpad_contract, &owner_launchpad, &ft_contract).await?;
     test_init_pools(&launchpad_contract, &owner_launchpad, &creator, &ft_contract).await?;
     test_admin_set_status_pool_pre_funding(&launchpad_contract, &owner_launchpad).await?;
-    
-    sleep(Duration::from_secs(3)).await;
+    test_set_funding_pool_by_creator(&launchpad_contract, &creator).await?;
     
     test_backers_deposit_token_to_pools(&ft_contract, &launchpad_contract, &backer1, &backer2).await?;
+    test_backers_deposit_uneven_amounts(&ft_contract, &launchpad_contract, &backer1, &backer2).await?;
+    test_paginated_views(&launchpad_contract, &creator, &backer1).await?;
+    test_pause_launchpad(&ft_contract, &launchpad_contract, &owner_launchpad, &backer1).await?;
+    test_pause_token(&ft_contract, &owner_ft, &backer1, &backer2).await?;
+    test_pledge_limits(&ft_contract, &launchpad_contract, &owner_launchpad, &creator, &backer1, &backer2).await?;
+    test_withdraw_pledge(&ft_contract, &launchpad_contract, &owner_launchpad, &creator, &backer1).await?;
+    test_treasury(&ft_contract, &launchpad_contract, &owner_launchpad, &creator, &backer1).await?;
+    test_platform_fee(&ft_contract, &launchpad_contract, &owner_launchpad, &backer1).await?;
+    test_native_near_pledge(&launchpad_contract, &owner_launchpad, &creator, &backer1).await?;
+    test_multi_token_pledges(&ft_contract, &launchpad_contract, &owner_launchpad, &creator, &backer1, &backer2).await?;
+    test_reward_tiers(&ft_contract, &launchpad_contract, &owner_launchpad, &creator, &backer1, &backer2).await?;
+    test_transfer_messages(&ft_contract, &launchpad_contract, &owner_launchpad, &creator, &backer1).await?;
+    test_referrals(&ft_contract, &launchpad_contract, &owner_launchpad, &creator, &backer1, &backer2).await?;
+    test_restricted_pools(&ft_contract, &launchpad_contract, &owner_launchpad, &creator, &backer1, &backer2).await?;
+    test_stretch_goals(&ft_contract, &launchpad_contract, &owner_launchpad, &creator, &backer1, &backer2).await?;
+    test_funding_terms(&ft_contract, &launchpad_contract, &owner_launchpad, &creator).await?;
+    test_reschedule_funding(&ft_contract, &launchpad_contract, &owner_launchpad, &creator, &backer1).await?;
+    test_fund_voting_pools(&ft_contract, &launchpad_contract, &owner_launchpad, &creator, &backer1, &backer2).await?;
+    test_fund_milestone_pool(&ft_contract, &launchpad_contract, &owner_launchpad, &creator, &backer1, &backer2).await?;
+    test_fund_rejecting_receiver(&ft_contract, &launchpad_contract, &owner_launchpad, &creator, &backer1).await?;
+    test_event_payloads(&worker, &ft_contract, &launchpad_contract, &owner_launchpad, &creator, &backer1, &backer2).await?;
     
     let balance_backer1: U128 = ft_contract
         .call("ft_balance_of")
@@ -113,11 +139,21 @@ async fn main() -> anyhow::Result<()> {
     
     println!("Balance Backer1: {}", balance_backer1.0);
 
-    // delay waited time ending funding
-    sleep(Duration::from_secs(32)).await;
+    // fast forward past the one day pledge window of the pools funded above
+    let time_now: u64 = launchpad_contract.call("get_current_timestamp").view().await?.json()?;
+    fast_forward_past(&worker, &launchpad_contract, time_now + 24 * 60 * 60 * 1_000_000_000).await?;
     
     test_check_funding_result(&launchpad_contract, &owner_launchpad).await?;
-    test_claim_refund_for_backers(&launchpad_contract, &ft_contract, &backer1, &backer2).await?;
+    test_claim_refund_for_backers(&launchpad_contract, &backer1, &backer2).await?;
+    test_refunds_sum_to_pool_balance(&launchpad_contract, &ft_contract, &backer1, &backer2).await?;
+    test_advance_pool(&launchpad_contract, &owner_launchpad, &backer2).await?;
+    test_multi_token_refunds(&ft_contract, &launchpad_contract, &owner_launchpad, &backer1, &backer2).await?;
+    test_overfunding_settlement(&ft_contract, &launchpad_contract, &owner_launchpad, &backer1, &backer2).await?;
+    test_vote_and_tally(&launchpad_contract, &owner_launchpad, &creator, &backer1, &backer2).await?;
+    test_milestone_tranches(&ft_contract, &launchpad_contract, &owner_launchpad, &creator, &backer1, &backer2).await?;
+    test_refund_to_rejecting_receiver(&worker, &ft_contract, &launchpad_contract, &owner_launchpad, &backer1).await?;
+    test_roles_and_admin_transfer(&launchpad_contract, &owner_launchpad, &backer1, &backer2).await?;
+    test_rescheduled_funding_end(&worker, &launchpad_contract, &backer2).await?;
 
     sleep(Duration::from_secs(3)).await;
     
@@ -208,7 +244,7 @@ pub async fn test_add_token(
         .transact()
         .await?
         .json()?;
-    assert_eq!(token_valid, true);
+    assert!(token_valid);
     println!("      Passed ✅ test_add_token");
     Ok(())
 
@@ -221,15 +257,6 @@ pub async fn test_init_pools(
     ft_contract: &Contract
 ) -> anyhow::Result<()> {
     
-    let time_now: u64 = launchpad_contract
-        .call("get_current_timestamp")
-        .view()
-        .await?
-        .json()?;
-    
-    let time_start_pledge = time_now + 1_000_000_000; // 1 s in nanoseconds
-    let time_end_pledge = time_now + 30_000_000_000; // 20 minute
-
     // init pool 1
     creator
         .call(launchpad_contract.id(), "init_pool")
@@ -237,8 +264,6 @@ pub async fn test_init_pools(
             "campaign_id": "test1",
             "token_id": ft_contract.id(),
             "min_multiple_pledge": 10000,
-            "time_start_pledge": time_start_pledge,
-            "time_end_pledge": time_end_pledge,
             "target_funding": "10000000"
         }))
         .deposit(INIT_POOL)
@@ -253,8 +278,6 @@ pub async fn test_init_pools(
             "campaign_id": "test2",
             "token_id": ft_contract.id(),
             "min_multiple_pledge": 10000,
-            "time_start_pledge": time_start_pledge + 1_000_000_000,
-            "time_end_pledge": time_end_pledge,
             "target_funding": "10000000"
         }))
         .deposit(INIT_POOL)
@@ -270,8 +293,6 @@ pub async fn test_init_pools(
             "campaign_id": "test3",
             "token_id": ft_contract.id(),
             "min_multiple_pledge": 10000,
-            "time_start_pledge": time_start_pledge + 3_000_000_000,
-            "time_end_pledge": time_end_pledge,
             "target_funding": "100000000000000"
         }))
         .deposit(INIT_POOL)
@@ -279,6 +300,35 @@ pub async fn test_init_pools(
         .await?
         .into_result()?;
 
+    // init pool 4, never reaches its target so every pledge is refunded
+    creator
+        .call(launchpad_contract.id(), "init_pool")
+        .args_json(json!({
+            "campaign_id": "test4",
+            "token_id": ft_contract.id(),
+            "min_multiple_pledge": 1,
+            "target_funding": "1000000000000000000000000000"
+        }))
+        .deposit(INIT_POOL)
+        .transact()
+        .await?
+        .into_result()?;
+
+    // a pool without a target would be successful before its first pledge
+    let outcome = creator
+        .call(launchpad_contract.id(), "init_pool")
+        .args_json(json!({
+            "campaign_id": "test-zero-target",
+            "token_id": ft_contract.id(),
+            "min_multiple_pledge": 1,
+            "target_funding": "0"
+        }))
+        .deposit(INIT_POOL)
+        .transact()
+        .await?;
+    let error = format!("{:?}", outcome.into_result().unwrap_err());
+    assert!(error.contains("Target funding must be greater than zero"), "Pools without a target should be rejected.");
+
     let pool1: Option<PoolMetadata> = owner_launchpad
         .call(launchpad_contract.id(), "get_detail_pool")
         .args_json(json!({
@@ -293,9 +343,8 @@ pub async fn test_init_pools(
     assert_eq!(pool_metadata.campaign_id, "test1", "Campaign ID should match.");
     assert_eq!(pool_metadata.token_id.to_string(), ft_contract.id().to_string(), "Token ID should match.");
     assert_eq!(pool_metadata.min_multiple_pledge, 10000, "Min multiple pledge should match.");
-    assert_eq!(pool_metadata.time_start_pledge, time_start_pledge, "Start time should match.");
-    assert_eq!(pool_metadata.time_end_pledge, time_end_pledge, "End time should match.");
     assert_eq!(pool_metadata.target_funding, 10000000, "Target funding should match.");
+    assert_eq!(pool_metadata.status, Status::INIT, "Pool status should be INIT.");
     println!("      Passed ✅ test_init_pools");
     Ok(())
 }
@@ -305,40 +354,91 @@ pub async fn test_admin_set_status_pool_pre_funding(
     owner_launchpad: &Account
 ) -> anyhow::Result<()> {   
 
-    // approve pool1 by admin
-    owner_launchpad
-        .call(launchpad_contract.id(), "admin_set_status_pool_pre_funding")
+    // approve all pools by admin
+    for pool_id in 1..=4 {
+        owner_launchpad
+            .call(launchpad_contract.id(), "admin_set_status_pool_pre_funding")
+            .args_json(json!({
+                "pool_id": pool_id, 
+                "approve": true
+            }))
+            .transact()
+            .await?
+            .into_result()?;
+    }
+
+    let pool1: Option<PoolMetadata> = owner_launchpad
+        .call(launchpad_contract.id(), "get_detail_pool")
         .args_json(json!({
-            "pool_id": 1, 
-            "approve": true
+            "pool_id": 1
         }))
         .transact()
         .await?
-        .into_result()?;
+        .json()?;
 
-    // approve pool2 by admin
-    owner_launchpad
-        .call(launchpad_contract.id(), "admin_set_status_pool_pre_funding")
-        .args_json(json!({
-            "pool_id": 2, 
-            "approve": true
-        }))
-        .transact()
+    assert!(pool1.is_some(), "Pool should be initialized and exist.");
+    let pool_metadata = pool1.unwrap();
+    
+    assert_eq!(pool_metadata.status, Status::APPROVED, "Pool status should be APPROVED.");
+
+    println!("      Passed ✅ test_admin_set_status_pool_pre_funding");
+    Ok(())
+}
+
+pub async fn test_set_funding_pool_by_creator(
+    launchpad_contract: &Contract,
+    creator: &Account
+) -> anyhow::Result<()> {
+
+    let time_now: u64 = launchpad_contract
+        .call("get_current_timestamp")
+        .view()
         .await?
-        .into_result()?;
+        .json()?;
 
-    // approve pool3 by admin
-    owner_launchpad
-        .call(launchpad_contract.id(), "admin_set_status_pool_pre_funding")
+    let time_start_pledge = time_now + 2_000_000_000; // 2 s in nanoseconds
+
+    for pool_id in 1..=3 {
+        creator
+            .call(launchpad_contract.id(), "set_funding_pool_by_creator")
+            .args_json(json!({
+                "pool_id": pool_id,
+                "time_start_pledge": time_start_pledge,
+                "funding_duration_days": 1
+            }))
+            .transact()
+            .await?
+            .into_result()?;
+    }
+
+    // a start that already passed opens pledging right away
+    let outcome = creator
+        .call(launchpad_contract.id(), "set_funding_pool_by_creator")
         .args_json(json!({
-            "pool_id": 3, 
-            "approve": true
+            "pool_id": 4,
+            "time_start_pledge": time_now,
+            "funding_duration_days": 1
         }))
         .transact()
         .await?
         .into_result()?;
+    let logged: Vec<String> = events(&outcome.logs(), "openedu_launchpad")
+        .iter()
+        .map(|event| event["event"].as_str().unwrap().to_string())
+        .collect();
+    assert_eq!(logged, vec!["funding_scheduled", "pool_status_changed"]);
+
+    let pool4: PoolMetadata = launchpad_contract
+        .call("get_detail_pool")
+        .args_json(json!({"pool_id": 4}))
+        .view()
+        .await?
+        .json::<Option<PoolMetadata>>()?
+        .unwrap();
+    assert_eq!(pool4.status, Status::FUNDING, "Pool whose start passed should open without advance_pool.");
+    assert!(pool4.time_start_pledge > time_now, "Start time should be moved to the current block.");
 
-    let pool1: Option<PoolMetadata> = owner_launchpad
+    let pool1: Option<PoolMetadata> = creator
         .call(launchpad_contract.id(), "get_detail_pool")
         .args_json(json!({
             "pool_id": 1
@@ -347,12 +447,34 @@ pub async fn test_admin_set_status_pool_pre_funding(
         .await?
         .json()?;
 
-    assert!(pool1.is_some(), "Pool should be initialized and exist.");
     let pool_metadata = pool1.unwrap();
-    
-    assert_eq!(pool_metadata.status, Status::FUNDING, "Pool status should be FUNDING.");
+    assert_eq!(pool_metadata.status, Status::APPROVED, "Pool should stay APPROVED until pledging opens.");
+    assert_eq!(pool_metadata.time_start_pledge, time_start_pledge, "Start time should match.");
+    assert_eq!(pool_metadata.time_end_pledge, time_start_pledge + 24 * 60 * 60 * 1_000_000_000, "Funding should last one day.");
 
-    println!("      Passed ✅ test_admin_set_status_pool_pre_funding");
+    let outcome = creator
+        .call(launchpad_contract.id(), "advance_pool")
+        .args_json(json!({"pool_id": 1}))
+        .gas(NearGas::from_tgas(100))
+        .transact()
+        .await?;
+    assert!(outcome.is_failure(), "Funding should not open before its start time.");
+
+    sleep(Duration::from_secs(3)).await;
+
+    // anyone opens funding once the start time passed
+    for pool_id in 1..=3 {
+        let pool: PoolMetadata = creator
+            .call(launchpad_contract.id(), "advance_pool")
+            .args_json(json!({"pool_id": pool_id}))
+            .gas(NearGas::from_tgas(100))
+            .transact()
+            .await?
+            .json()?;
+        assert_eq!(pool.status, Status::FUNDING, "Pool status should be FUNDING.");
+    }
+
+    println!("      Passed ✅ test_set_funding_pool_by_creator");
     Ok(())
 }
 
@@ -478,6 +600,17 @@ pub async fn test_check_funding_result(
         .await?
         .into_result()?;
 
+    // check funding result pool 4
+    owner_launchpad
+        .call(launchpad_contract.id(), "check_funding_result")
+        .args_json(json!({
+            "pool_id": 4, 
+            "is_waiting_funding": false
+        }))
+        .transact()
+        .await?
+        .into_result()?;
+
     let list_records: Option<Vec<UserRecordDetail>> = owner_launchpad
         .call(launchpad_contract.id(), "get_user_records_by_pool_id")
         .args_json(json!({
@@ -544,7 +677,6 @@ pub async fn test_check_funding_result(
 
 pub async fn test_claim_refund_for_backers(
     launchpad_contract: &Contract,
-    ft_contract: &Contract,
     backer1: &Account,
     backer2: &Account
 ) -> anyhow::Result<()> {
@@ -591,4 +723,2989 @@ pub async fn test_claim_refund_for_backers(
 
     Ok(())
 
-}
\ No newline at end of file
+}
+
+// pledge amounts that are not multiples of any round number, so refunds cannot divide evenly;
+// the share math itself is property tested in the launchpad crate
+const UNEVEN_AMOUNTS: [[u128; 3]; 2] = [
+    [123_456_789_012_345_678_901, 1, 987_654_321_098_765_432_109],
+    [333_333_333_333_333_333_333, 7, 555_555_555_555_555_555_557],
+];
+
+pub async fn test_backers_deposit_uneven_amounts(
+    ft_contract: &Contract,
+    launchpad_contract: &Contract,
+    backer1: &Account,
+    backer2: &Account
+) -> anyhow::Result<()> {
+
+    let backers = vec![
+        (backer1, UNEVEN_AMOUNTS[0]),
+        (backer2, UNEVEN_AMOUNTS[1]),
+    ];
+
+    // backers deposit token to pool4
+    for (backer, amounts) in backers {
+        for amount in amounts {
+            backer
+                .call(ft_contract.id(), "ft_transfer_call")
+                .args_json(json!({
+                    "receiver_id": launchpad_contract.id(), 
+                    "amount": U128(amount), 
+                    "msg": "4"
+                }))
+                .deposit(DEFAULT_DEPOSIT)
+                .gas(NearGas::from_tgas(300))
+                .transact()
+                .await?
+                .into_result()?;
+        }
+    }
+
+    let pool4: Option<PoolMetadata> = backer1
+        .call(launchpad_contract.id(), "get_detail_pool")
+        .args_json(json!({
+            "pool_id": 4
+        }))
+        .transact()
+        .await?
+        .json()?;
+
+    let expected_total: u128 = UNEVEN_AMOUNTS.iter().flatten().sum();
+    assert_eq!(pool4.unwrap().total_balance, expected_total, "Pool 4 balance should be the sum of all pledges.");
+
+    println!("      Passed ✅ test_backers_deposit_uneven_amounts");
+    Ok(())
+}
+
+pub async fn test_refunds_sum_to_pool_balance(
+    launchpad_contract: &Contract,
+    ft_contract: &Contract,
+    backer1: &Account,
+    backer2: &Account
+) -> anyhow::Result<()> {
+
+    let pool4: PoolMetadata = backer1
+        .call(launchpad_contract.id(), "get_detail_pool")
+        .args_json(json!({
+            "pool_id": 4
+        }))
+        .view()
+        .await?
+        .json::<Option<PoolMetadata>>()?
+        .unwrap();
+
+    assert_eq!(pool4.status, Status::REFUNDED, "Pool status should be REFUNDED.");
+    assert_eq!(pool4.total_voting_power, pool4.total_balance, "Voting power should cover the whole pool.");
+
+    let mut total_refunded: u128 = 0;
+    for backer in [backer1, backer2] {
+        let before: U128 = ft_contract
+            .call("ft_balance_of")
+            .args_json(json!({"account_id": backer.id()}))
+            .view()
+            .await?
+            .json()?;
+
+        backer
+            .call(launchpad_contract.id(), "claim_refund")
+            .args_json(json!({
+                "pool_id": 4, 
+            }))
+            .gas(NearGas::from_tgas(30))
+            .transact()
+            .await?
+            .into_result()?;
+
+        let after: U128 = ft_contract
+            .call("ft_balance_of")
+            .args_json(json!({"account_id": backer.id()}))
+            .view()
+            .await?
+            .json()?;
+
+        total_refunded += after.0 - before.0;
+    }
+
+    let pool4_after: PoolMetadata = backer1
+        .call(launchpad_contract.id(), "get_detail_pool")
+        .args_json(json!({
+            "pool_id": 4
+        }))
+        .view()
+        .await?
+        .json::<Option<PoolMetadata>>()?
+        .unwrap();
+
+    assert_eq!(total_refunded, pool4.total_balance, "Refunds should sum to exactly the pool balance.");
+    assert_eq!(pool4_after.total_balance, 0, "No dust should be left in the pool.");
+    assert_eq!(pool4_after.total_voting_power, 0, "All voting power should be redeemed.");
+
+    println!("      Passed ✅ test_refunds_sum_to_pool_balance");
+    Ok(())
+}
+
+pub async fn test_paginated_views(
+    launchpad_contract: &Contract,
+    creator: &Account,
+    backer1: &Account
+) -> anyhow::Result<()> {
+
+    let number_of_pools: u64 = launchpad_contract
+        .call("get_number_of_pools")
+        .view()
+        .await?
+        .json()?;
+
+    assert_eq!(number_of_pools, 4, "Launchpad should hold 4 pools.");
+
+    // walk all pools two at a time
+    let mut pool_ids = Vec::new();
+    for from_index in (0..number_of_pools).step_by(2) {
+        let page: Option<Vec<PoolMetadata>> = launchpad_contract
+            .call("get_all_pool")
+            .args_json(json!({
+                "from_index": from_index,
+                "limit": 2
+            }))
+            .view()
+            .await?
+            .json()?;
+
+        let page = page.unwrap();
+        assert!(page.len() <= 2, "Page should not exceed the limit.");
+        pool_ids.extend(page.iter().map(|pool| pool.pool_id));
+    }
+
+    assert_eq!(pool_ids, vec![1, 2, 3, 4], "Pages should cover every pool once in creation order.");
+
+    let funding_pools: Option<Vec<PoolMetadata>> = launchpad_contract
+        .call("get_pools")
+        .args_json(json!({
+            "filter": {
+                "status": "FUNDING",
+                "creator_id": creator.id()
+            }
+        }))
+        .view()
+        .await?
+        .json()?;
+
+    assert!(
+        funding_pools.unwrap().iter().all(|pool| pool.status == Status::FUNDING && pool.creator_id.as_str() == creator.id().as_str()),
+        "Filtered pools should match status and creator."
+    );
+
+    let backed_pools: Option<Vec<BackerPoolDetail>> = launchpad_contract
+        .call("get_pools_by_backer")
+        .args_json(json!({
+            "account_id": backer1.id()
+        }))
+        .view()
+        .await?
+        .json()?;
+
+    let mut backed_pool_ids: Vec<u64> = backed_pools
+        .unwrap()
+        .iter()
+        .map(|detail| {
+            assert!(detail.record.as_ref().unwrap().amount > 0, "Backer should have a pledge in every listed pool.");
+            detail.pool.pool_id
+        })
+        .collect();
+    backed_pool_ids.sort();
+
+    assert_eq!(backed_pool_ids, vec![1, 3, 4], "Backer1 should be indexed in pools 1, 3 and 4.");
+
+    println!("      Passed ✅ test_paginated_views");
+    Ok(())
+}
+
+pub async fn test_roles_and_admin_transfer(
+    launchpad_contract: &Contract,
+    owner_launchpad: &Account,
+    new_admin: &Account,
+    reviewer: &Account
+) -> anyhow::Result<()> {
+
+    // only the admin can grant roles
+    let outcome = reviewer
+        .call(launchpad_contract.id(), "grant_role")
+        .args_json(json!({
+            "role": "PoolReviewer",
+            "account_id": reviewer.id()
+        }))
+        .transact()
+        .await?;
+    assert!(outcome.is_failure(), "Non-admin should not grant roles.");
+
+    owner_launchpad
+        .call(launchpad_contract.id(), "grant_role")
+        .args_json(json!({
+            "role": "PoolReviewer",
+            "account_id": reviewer.id()
+        }))
+        .transact()
+        .await?
+        .into_result()?;
+
+    let roles: Vec<String> = launchpad_contract
+        .call("get_roles")
+        .args_json(json!({"account_id": reviewer.id()}))
+        .view()
+        .await?
+        .json()?;
+    assert_eq!(roles, vec!["PoolReviewer".to_string()], "Reviewer should hold only the PoolReviewer role.");
+
+    // a pool reviewer cannot touch the treasury
+    let outcome = reviewer
+        .call(launchpad_contract.id(), "add_token")
+        .args_json(json!({"token_id": "other-token.test.near"}))
+        .transact()
+        .await?;
+    assert!(outcome.is_failure(), "Pool reviewer should not manage tokens.");
+
+    owner_launchpad
+        .call(launchpad_contract.id(), "revoke_role")
+        .args_json(json!({
+            "role": "PoolReviewer",
+            "account_id": reviewer.id()
+        }))
+        .transact()
+        .await?
+        .into_result()?;
+
+    let has_role: bool = launchpad_contract
+        .call("has_role")
+        .args_json(json!({
+            "role": "PoolReviewer",
+            "account_id": reviewer.id()
+        }))
+        .view()
+        .await?
+        .json()?;
+    assert!(!has_role, "Revoked role should be gone.");
+
+    // ownership only moves once the proposed admin accepts it
+    owner_launchpad
+        .call(launchpad_contract.id(), "change_admin")
+        .args_json(json!({"new_admin": new_admin.id()}))
+        .transact()
+        .await?
+        .into_result()?;
+
+    let owner: String = launchpad_contract.call("get_owner").view().await?.json()?;
+    assert_eq!(owner, owner_launchpad.id().to_string(), "Admin should not change before acceptance.");
+
+    let outcome = reviewer
+        .call(launchpad_contract.id(), "accept_admin")
+        .transact()
+        .await?;
+    assert!(outcome.is_failure(), "Only the proposed admin should accept.");
+
+    new_admin
+        .call(launchpad_contract.id(), "accept_admin")
+        .transact()
+        .await?
+        .into_result()?;
+
+    let owner: String = launchpad_contract.call("get_owner").view().await?.json()?;
+    assert_eq!(owner, new_admin.id().to_string(), "Proposed admin should own the launchpad.");
+
+    let pending_owner: Option<String> = launchpad_contract.call("get_pending_owner").view().await?.json()?;
+    assert!(pending_owner.is_none(), "Pending admin should be cleared.");
+
+    println!("      Passed ✅ test_roles_and_admin_transfer");
+    Ok(())
+}
+
+pub async fn test_pause_launchpad(
+    ft_contract: &Contract,
+    launchpad_contract: &Contract,
+    owner_launchpad: &Account,
+    backer1: &Account
+) -> anyhow::Result<()> {
+
+    // only a pauser can halt the launchpad
+    let outcome = backer1
+        .call(launchpad_contract.id(), "pause")
+        .args_json(json!({}))
+        .transact()
+        .await?;
+    assert!(outcome.is_failure(), "Non-pauser should not pause the launchpad.");
+
+    owner_launchpad
+        .call(launchpad_contract.id(), "pause")
+        .args_json(json!({"features": ["Pledge", "Refund"]}))
+        .transact()
+        .await?
+        .into_result()?;
+
+    let paused: bool = launchpad_contract
+        .call("is_paused")
+        .args_json(json!({"feature": "Pledge"}))
+        .view()
+        .await?
+        .json()?;
+    assert!(paused, "Pledging should be paused.");
+
+    let balance_before: U128 = ft_contract
+        .call("ft_balance_of")
+        .args_json(json!({"account_id": backer1.id()}))
+        .view()
+        .await?
+        .json()?;
+    let pool_before: PoolMetadata = launchpad_contract
+        .call("get_detail_pool")
+        .args_json(json!({"pool_id": 1}))
+        .view()
+        .await?
+        .json::<Option<PoolMetadata>>()?
+        .unwrap();
+
+    // the pledge is not recorded and the tokens go back to the backer
+    backer1
+        .call(ft_contract.id(), "ft_transfer_call")
+        .args_json(json!({
+            "receiver_id": launchpad_contract.id(),
+            "amount": "1000000",
+            "msg": "1"
+        }))
+        .deposit(DEFAULT_DEPOSIT)
+        .gas(NearGas::from_tgas(300))
+        .transact()
+        .await?
+        .into_result()?;
+
+    let balance_after: U128 = ft_contract
+        .call("ft_balance_of")
+        .args_json(json!({"account_id": backer1.id()}))
+        .view()
+        .await?
+        .json()?;
+    let pool_after: PoolMetadata = launchpad_contract
+        .call("get_detail_pool")
+        .args_json(json!({"pool_id": 1}))
+        .view()
+        .await?
+        .json::<Option<PoolMetadata>>()?
+        .unwrap();
+    assert_eq!(balance_after.0, balance_before.0, "Paused pledge should be refunded.");
+    assert_eq!(pool_after.total_balance, pool_before.total_balance, "Paused pledge should not be recorded.");
+
+    let outcome = backer1
+        .call(launchpad_contract.id(), "claim_refund")
+        .args_json(json!({"pool_id": 1}))
+        .gas(NearGas::from_tgas(30))
+        .transact()
+        .await?;
+    let error = format!("{:?}", outcome.into_result().unwrap_err());
+    assert!(error.contains("Refund is paused"), "Refunds should be halted while paused.");
+
+    owner_launchpad
+        .call(launchpad_contract.id(), "unpause")
+        .args_json(json!({}))
+        .transact()
+        .await?
+        .into_result()?;
+
+    let paused_features: Vec<String> = launchpad_contract.call("get_paused_features").view().await?.json()?;
+    assert!(paused_features.is_empty(), "Every feature should be resumed.");
+
+    println!("      Passed ✅ test_pause_launchpad");
+    Ok(())
+}
+
+pub async fn test_pause_token(
+    ft_contract: &Contract,
+    owner_ft: &Account,
+    backer1: &Account,
+    backer2: &Account
+) -> anyhow::Result<()> {
+
+    let outcome = backer1
+        .call(ft_contract.id(), "grant_role")
+        .args_json(json!({"role": "Pauser", "account_id": backer1.id()}))
+        .transact()
+        .await?;
+    assert!(outcome.is_failure(), "Only the owner should grant roles.");
+
+    let outcome = owner_ft
+        .call(ft_contract.id(), "grant_role")
+        .args_json(json!({"role": "Pauser", "account_id": backer2.id()}))
+        .transact()
+        .await?;
+    assert_eq!(events(&outcome.logs(), "openedu_token"), vec![json!({
+        "standard": "openedu_token",
+        "version": "1.0.0",
+        "event": "role_granted",
+        "data": [{"role": "Pauser", "account_id": backer2.id()}]
+    })]);
+
+    let outcome = backer1
+        .call(ft_contract.id(), "pause")
+        .args_json(json!({"features": ["Transfer"]}))
+        .transact()
+        .await?;
+    assert!(outcome.is_failure(), "Non-pauser should not pause the token.");
+
+    let outcome = backer2
+        .call(ft_contract.id(), "pause")
+        .args_json(json!({"features": ["Transfer"]}))
+        .transact()
+        .await?;
+    assert_eq!(events(&outcome.logs(), "openedu_token"), vec![json!({
+        "standard": "openedu_token",
+        "version": "1.0.0",
+        "event": "paused",
+        "data": [{"features": ["Transfer"], "account_id": backer2.id()}]
+    })]);
+
+    let outcome = backer1
+        .call(ft_contract.id(), "ft_transfer")
+        .args_json(json!({"receiver_id": backer2.id(), "amount": "1"}))
+        .deposit(DEFAULT_DEPOSIT)
+        .transact()
+        .await?;
+    assert!(outcome.is_failure(), "Transfers should be halted while paused.");
+
+    let outcome = backer2
+        .call(ft_contract.id(), "unpause")
+        .args_json(json!({"features": ["Transfer"]}))
+        .transact()
+        .await?;
+    assert_eq!(events(&outcome.logs(), "openedu_token"), vec![json!({
+        "standard": "openedu_token",
+        "version": "1.0.0",
+        "event": "unpaused",
+        "data": [{"features": ["Transfer"], "account_id": backer2.id()}]
+    })]);
+
+    owner_ft
+        .call(ft_contract.id(), "revoke_role")
+        .args_json(json!({"role": "Pauser", "account_id": backer2.id()}))
+        .transact()
+        .await?
+        .into_result()?;
+
+    let roles: Vec<String> = ft_contract
+        .call("get_roles")
+        .args_json(json!({"account_id": backer2.id()}))
+        .view()
+        .await?
+        .json()?;
+    assert!(roles.is_empty(), "Revoked pauser should hold no role.");
+
+    backer1
+        .call(ft_contract.id(), "ft_transfer")
+        .args_json(json!({"receiver_id": backer2.id(), "amount": "1"}))
+        .deposit(DEFAULT_DEPOSIT)
+        .transact()
+        .await?
+        .into_result()?;
+
+    println!("      Passed ✅ test_pause_token");
+    Ok(())
+}
+
+async fn pledge(ft_contract: &Contract, launchpad_contract: &Contract, backer: &Account, pool_id: u64, amount: u128) -> anyhow::Result<()> {
+    pledge_with_msg(ft_contract, launchpad_contract, backer, &pool_id.to_string(), amount).await?;
+    Ok(())
+}
+
+fn pledge_signer() -> Keypair {
+    let secret = SecretKey::from_bytes(&PLEDGE_SIGNER_SECRET).unwrap();
+    let public = PublicKey::from(&secret);
+    Keypair { secret, public }
+}
+
+// platform signature letting the account pledge to a signature-gated pool
+fn pledge_signature(pool_id: u64, account: &Account) -> String {
+    encode(pledge_signer().sign(format!("{}:{}", pool_id, account.id()).as_bytes()).to_bytes())
+}
+
+// logs of the transfer, a rejected pledge is refunded and reported with `pledge_rejected`
+async fn pledge_with_msg(ft_contract: &Contract, launchpad_contract: &Contract, backer: &Account, msg: &str, amount: u128) -> anyhow::Result<Vec<String>> {
+    let outcome = backer
+        .call(ft_contract.id(), "ft_transfer_call")
+        .args_json(json!({
+            "receiver_id": launchpad_contract.id(),
+            "amount": U128(amount),
+            "msg": msg
+        }))
+        .deposit(DEFAULT_DEPOSIT)
+        .gas(NearGas::from_tgas(300))
+        .transact()
+        .await?
+        .into_result()?;
+    Ok(outcome.logs().iter().map(|log| log.to_string()).collect())
+}
+
+async fn ft_balance(ft_contract: &Contract, account: &Account) -> anyhow::Result<u128> {
+    let balance: U128 = ft_contract
+        .call("ft_balance_of")
+        .args_json(json!({"account_id": account.id()}))
+        .view()
+        .await?
+        .json()?;
+    Ok(balance.0)
+}
+
+pub async fn test_pledge_limits(
+    ft_contract: &Contract,
+    launchpad_contract: &Contract,
+    owner_launchpad: &Account,
+    creator: &Account,
+    backer1: &Account,
+    backer2: &Account
+) -> anyhow::Result<()> {
+
+    let outcome = creator
+        .call(launchpad_contract.id(), "init_pool")
+        .args_json(json!({
+            "campaign_id": "test-limits",
+            "token_id": ft_contract.id(),
+            "min_multiple_pledge": 100,
+            "target_funding": "5000",
+            "pledge_limits": {"min_pledge": "200", "max_pledge": "100", "hard_cap": "6000"}
+        }))
+        .deposit(INIT_POOL)
+        .transact()
+        .await?;
+    assert!(outcome.is_failure(), "Maximum pledge below the minimum should be rejected.");
+
+    let pool: PoolMetadata = creator
+        .call(launchpad_contract.id(), "init_pool")
+        .args_json(json!({
+            "campaign_id": "test-limits",
+            "token_id": ft_contract.id(),
+            "min_multiple_pledge": 100,
+            "target_funding": "5000",
+            "pledge_limits": {"min_pledge": "200", "max_pledge": "5000", "hard_cap": "6000"}
+        }))
+        .deposit(INIT_POOL)
+        .transact()
+        .await?
+        .json()?;
+    let pool_id = pool.pool_id;
+
+    owner_launchpad
+        .call(launchpad_contract.id(), "admin_set_status_pool_pre_funding")
+        .args_json(json!({"pool_id": pool_id, "approve": true}))
+        .transact()
+        .await?
+        .into_result()?;
+
+    let time_now: u64 = launchpad_contract.call("get_current_timestamp").view().await?.json()?;
+    creator
+        .call(launchpad_contract.id(), "set_funding_pool_by_creator")
+        .args_json(json!({
+            "pool_id": pool_id,
+            "time_start_pledge": time_now + 2_000_000_000,
+            "funding_duration_days": 1
+        }))
+        .transact()
+        .await?
+        .into_result()?;
+
+    sleep(Duration::from_secs(3)).await;
+    open_funding(launchpad_contract, creator, pool_id).await?;
+
+    let balance1_before = ft_balance(ft_contract, backer1).await?;
+    let balance2_before = ft_balance(ft_contract, backer2).await?;
+
+    // below the minimum pledge, fully refunded
+    pledge(ft_contract, launchpad_contract, backer1, pool_id, 150).await?;
+    // rounded down to the pledge multiple, 50 refunded
+    pledge(ft_contract, launchpad_contract, backer1, pool_id, 4_050).await?;
+    // only 1000 left under the backer's maximum, 2000 refunded
+    pledge(ft_contract, launchpad_contract, backer1, pool_id, 3_000).await?;
+    // only 1000 left under the hard cap, 1000 refunded
+    pledge(ft_contract, launchpad_contract, backer2, pool_id, 2_000).await?;
+    // hard cap reached, fully refunded
+    pledge(ft_contract, launchpad_contract, backer2, pool_id, 500).await?;
+
+    assert_eq!(balance1_before - ft_balance(ft_contract, backer1).await?, 5_000, "Backer1 should be capped at the maximum pledge.");
+    assert_eq!(balance2_before - ft_balance(ft_contract, backer2).await?, 1_000, "Backer2 should be capped by the hard cap.");
+
+    let pool: PoolMetadata = launchpad_contract
+        .call("get_detail_pool")
+        .args_json(json!({"pool_id": pool_id}))
+        .view()
+        .await?
+        .json::<Option<PoolMetadata>>()?
+        .unwrap();
+    assert_eq!(pool.total_balance, 6_000, "Pool should stop at its hard cap.");
+
+    println!("      Passed ✅ test_pledge_limits");
+    Ok(())
+}
+
+async fn withdraw_pledge(launchpad_contract: &Contract, backer: &Account, pool_id: u64, amount: Option<u128>) -> anyhow::Result<bool> {
+    let outcome = backer
+        .call(launchpad_contract.id(), "withdraw_pledge")
+        .args_json(json!({"pool_id": pool_id, "amount": amount.map(U128)}))
+        .gas(NearGas::from_tgas(100))
+        .transact()
+        .await?;
+    Ok(outcome.is_success())
+}
+
+pub async fn test_withdraw_pledge(
+    ft_contract: &Contract,
+    launchpad_contract: &Contract,
+    owner_launchpad: &Account,
+    creator: &Account,
+    backer1: &Account
+) -> anyhow::Result<()> {
+
+    let pool: PoolMetadata = creator
+        .call(launchpad_contract.id(), "init_pool")
+        .args_json(json!({
+            "campaign_id": "test-withdraw",
+            "token_id": ft_contract.id(),
+            "min_multiple_pledge": 100,
+            "target_funding": "5000"
+        }))
+        .deposit(INIT_POOL)
+        .transact()
+        .await?
+        .json()?;
+    let pool_id = pool.pool_id;
+
+    owner_launchpad
+        .call(launchpad_contract.id(), "admin_set_status_pool_pre_funding")
+        .args_json(json!({"pool_id": pool_id, "approve": true}))
+        .transact()
+        .await?
+        .into_result()?;
+
+    let time_now: u64 = launchpad_contract.call("get_current_timestamp").view().await?.json()?;
+    let outcome = creator
+        .call(launchpad_contract.id(), "set_funding_pool_by_creator")
+        .args_json(json!({
+            "pool_id": pool_id,
+            "time_start_pledge": time_now + 2_000_000_000,
+            "funding_duration_days": 1,
+            "withdraw_penalty_percent": 101
+        }))
+        .transact()
+        .await?;
+    assert!(outcome.is_failure(), "Withdraw penalty above 100% should be rejected.");
+
+    creator
+        .call(launchpad_contract.id(), "set_funding_pool_by_creator")
+        .args_json(json!({
+            "pool_id": pool_id,
+            "time_start_pledge": time_now + 2_000_000_000,
+            "funding_duration_days": 1,
+            "withdraw_penalty_percent": 10
+        }))
+        .transact()
+        .await?
+        .into_result()?;
+
+    sleep(Duration::from_secs(3)).await;
+    open_funding(launchpad_contract, creator, pool_id).await?;
+
+    let balance_before = ft_balance(ft_contract, backer1).await?;
+    pledge(ft_contract, launchpad_contract, backer1, pool_id, 1_000).await?;
+
+    // 10% of the 400 withdrawn stays in the pool
+    assert!(withdraw_pledge(launchpad_contract, backer1, pool_id, Some(400)).await?, "Partial withdraw should succeed.");
+    assert_eq!(balance_before - ft_balance(ft_contract, backer1).await?, 640, "Backer should get 360 back.");
+
+    assert!(!withdraw_pledge(launchpad_contract, backer1, pool_id, Some(250)).await?, "Withdraw off the pledge multiple should fail.");
+    assert!(!withdraw_pledge(launchpad_contract, backer1, pool_id, Some(700)).await?, "Withdraw above the pledge should fail.");
+
+    // the remaining 600 are withdrawn, 60 stay in the pool
+    assert!(withdraw_pledge(launchpad_contract, backer1, pool_id, None).await?, "Full withdraw should succeed.");
+    assert_eq!(balance_before - ft_balance(ft_contract, backer1).await?, 100, "Only the penalties should stay pledged.");
+
+    let pool: PoolMetadata = launchpad_contract
+        .call("get_detail_pool")
+        .args_json(json!({"pool_id": pool_id}))
+        .view()
+        .await?
+        .json::<Option<PoolMetadata>>()?
+        .unwrap();
+    assert_eq!(pool.total_balance, 100, "Pool should keep the withdraw penalties.");
+
+    assert!(!withdraw_pledge(launchpad_contract, backer1, pool_id, None).await?, "Nothing is left to withdraw.");
+
+    println!("      Passed ✅ test_withdraw_pledge");
+    Ok(())
+}
+
+pub async fn test_treasury(
+    ft_contract: &Contract,
+    launchpad_contract: &Contract,
+    owner_launchpad: &Account,
+    creator: &Account,
+    backer1: &Account
+) -> anyhow::Result<()> {
+
+    let refund_percent: u8 = launchpad_contract.call("get_refund_reject_pool").view().await?.json()?;
+    owner_launchpad
+        .call(launchpad_contract.id(), "set_refund_reject_pool")
+        .args_json(json!({"percent": 50}))
+        .transact()
+        .await?
+        .into_result()?;
+
+    let treasury_before: TreasuryLedger = launchpad_contract.call("get_treasury").view().await?.json()?;
+
+    let pool: PoolMetadata = creator
+        .call(launchpad_contract.id(), "init_pool")
+        .args_json(json!({
+            "campaign_id": "test-treasury",
+            "token_id": ft_contract.id(),
+            "min_multiple_pledge": 100,
+            "target_funding": "5000"
+        }))
+        .deposit(INIT_POOL)
+        .transact()
+        .await?
+        .json()?;
+
+    owner_launchpad
+        .call(launchpad_contract.id(), "admin_set_status_pool_pre_funding")
+        .args_json(json!({"pool_id": pool.pool_id, "approve": false}))
+        .transact()
+        .await?
+        .into_result()?;
+
+    // half of the rejected stake stays with the platform
+    let treasury: TreasuryLedger = launchpad_contract.call("get_treasury").view().await?.json()?;
+    let retained = INIT_POOL.as_yoctonear() / 2;
+    assert_eq!(treasury.balance - treasury_before.balance, retained, "Treasury should keep the retained stake.");
+    assert_eq!(treasury.retained_stakes - treasury_before.retained_stakes, retained, "Retained stake should be recorded.");
+
+    let withdrawn = parse_near!("0.1 N");
+    let outcome = backer1
+        .call(launchpad_contract.id(), "withdraw_treasury")
+        .args_json(json!({"amount": U128(withdrawn)}))
+        .transact()
+        .await?;
+    assert!(outcome.is_failure(), "Only the admin should withdraw the treasury.");
+
+    let outcome = owner_launchpad
+        .call(launchpad_contract.id(), "withdraw_treasury")
+        .args_json(json!({"amount": U128(treasury.balance + 1)}))
+        .transact()
+        .await?;
+    assert!(outcome.is_failure(), "Withdraw above the treasury balance should fail.");
+
+    owner_launchpad
+        .call(launchpad_contract.id(), "withdraw_treasury")
+        .args_json(json!({"amount": U128(withdrawn)}))
+        .gas(NearGas::from_tgas(50))
+        .transact()
+        .await?
+        .into_result()?;
+
+    let treasury_after: TreasuryLedger = launchpad_contract.call("get_treasury").view().await?.json()?;
+    assert_eq!(treasury.balance - treasury_after.balance, withdrawn, "Withdraw should leave the treasury.");
+    assert_eq!(treasury_after.withdrawn - treasury.withdrawn, withdrawn, "Withdraw should be recorded.");
+
+    owner_launchpad
+        .call(launchpad_contract.id(), "set_refund_reject_pool")
+        .args_json(json!({"percent": refund_percent}))
+        .transact()
+        .await?
+        .into_result()?;
+
+    println!("      Passed ✅ test_treasury");
+    Ok(())
+}
+
+pub async fn test_platform_fee(
+    ft_contract: &Contract,
+    launchpad_contract: &Contract,
+    owner_launchpad: &Account,
+    backer1: &Account
+) -> anyhow::Result<()> {
+
+    let outcome = backer1
+        .call(launchpad_contract.id(), "set_platform_fee")
+        .args_json(json!({"fee_bps": 250}))
+        .transact()
+        .await?;
+    assert!(outcome.is_failure(), "Only a treasury manager should set the platform fee.");
+
+    let outcome = owner_launchpad
+        .call(launchpad_contract.id(), "set_platform_fee")
+        .args_json(json!({"fee_bps": 5_001}))
+        .transact()
+        .await?;
+    assert!(outcome.is_failure(), "Platform fee above the maximum should be rejected.");
+
+    owner_launchpad
+        .call(launchpad_contract.id(), "set_platform_fee")
+        .args_json(json!({"fee_bps": 250}))
+        .transact()
+        .await?
+        .into_result()?;
+
+    owner_launchpad
+        .call(launchpad_contract.id(), "set_token_fee")
+        .args_json(json!({"token_id": ft_contract.id(), "fee_bps": 100}))
+        .transact()
+        .await?
+        .into_result()?;
+
+    let platform_fee: u16 = launchpad_contract.call("get_platform_fee").args_json(json!({})).view().await?.json()?;
+    assert_eq!(platform_fee, 250, "Platform fee should be set.");
+
+    let token_fee: u16 = launchpad_contract
+        .call("get_platform_fee")
+        .args_json(json!({"token_id": ft_contract.id()}))
+        .view()
+        .await?
+        .json()?;
+    assert_eq!(token_fee, 100, "Token fee should override the platform fee.");
+
+    owner_launchpad
+        .call(launchpad_contract.id(), "set_token_fee")
+        .args_json(json!({"token_id": ft_contract.id()}))
+        .transact()
+        .await?
+        .into_result()?;
+
+    let token_fee: u16 = launchpad_contract
+        .call("get_platform_fee")
+        .args_json(json!({"token_id": ft_contract.id()}))
+        .view()
+        .await?
+        .json()?;
+    assert_eq!(token_fee, 250, "Cleared token fee should fall back to the platform fee.");
+
+    // no tranche has been released yet so nothing is accrued
+    let assets: Vec<Assets> = launchpad_contract.call("get_assets").view().await?.json()?;
+    let asset = assets.iter().find(|asset| asset.token_id.as_str() == ft_contract.id().as_str()).unwrap();
+    assert_eq!(asset.balances, 0, "No fee should be accrued before a release.");
+
+    let outcome = owner_launchpad
+        .call(launchpad_contract.id(), "withdraw_fees")
+        .args_json(json!({"token_id": ft_contract.id()}))
+        .gas(NearGas::from_tgas(50))
+        .transact()
+        .await?;
+    assert!(outcome.is_failure(), "Withdrawing without accrued fees should fail.");
+
+    owner_launchpad
+        .call(launchpad_contract.id(), "set_platform_fee")
+        .args_json(json!({"fee_bps": 0}))
+        .transact()
+        .await?
+        .into_result()?;
+
+    println!("      Passed ✅ test_platform_fee");
+    Ok(())
+}
+
+async fn near_pledge(launchpad_contract: &Contract, backer: &Account, pool_id: u64, deposit: NearToken) -> anyhow::Result<Option<u128>> {
+    let outcome = backer
+        .call(launchpad_contract.id(), "pledge")
+        .args_json(json!({"pool_id": pool_id}))
+        .deposit(deposit)
+        .gas(NearGas::from_tgas(50))
+        .transact()
+        .await?;
+    if outcome.is_failure() {
+        return Ok(None);
+    }
+    Ok(Some(outcome.json::<U128>()?.0))
+}
+
+pub async fn test_native_near_pledge(
+    launchpad_contract: &Contract,
+    owner_launchpad: &Account,
+    creator: &Account,
+    backer1: &Account
+) -> anyhow::Result<()> {
+
+    owner_launchpad
+        .call(launchpad_contract.id(), "add_token")
+        .args_json(json!({"token_id": "near"}))
+        .transact()
+        .await?
+        .into_result()?;
+
+    let pool: PoolMetadata = creator
+        .call(launchpad_contract.id(), "init_pool")
+        .args_json(json!({
+            "campaign_id": "test-near",
+            "token_id": "near",
+            // 0.00001 NEAR, the multiple is a plain JSON number so it has to fit a u64
+            "min_multiple_pledge": 10_000_000_000_000_000_000u64,
+            "target_funding": U128(parse_near!("5 N"))
+        }))
+        .deposit(INIT_POOL)
+        .transact()
+        .await?
+        .json()?;
+    let pool_id = pool.pool_id;
+
+    owner_launchpad
+        .call(launchpad_contract.id(), "admin_set_status_pool_pre_funding")
+        .args_json(json!({"pool_id": pool_id, "approve": true}))
+        .transact()
+        .await?
+        .into_result()?;
+
+    let time_now: u64 = launchpad_contract.call("get_current_timestamp").view().await?.json()?;
+    creator
+        .call(launchpad_contract.id(), "set_funding_pool_by_creator")
+        .args_json(json!({
+            "pool_id": pool_id,
+            "time_start_pledge": time_now + 2_000_000_000,
+            "funding_duration_days": 1
+        }))
+        .transact()
+        .await?
+        .into_result()?;
+
+    let pledged = near_pledge(launchpad_contract, backer1, pool_id, NearToken::from_near(1)).await?;
+    assert!(pledged.is_none(), "Pledging before the pledge period should fail.");
+
+    sleep(Duration::from_secs(3)).await;
+    open_funding(launchpad_contract, creator, pool_id).await?;
+
+    // the 0.000005 NEAR off the pledge multiple goes back to the backer
+    let balance_before = backer1.view_account().await?.balance;
+    let deposit = NearToken::from_yoctonear(parse_near!("1.2 N") + 5_000_000_000_000_000_000);
+    let pledged = near_pledge(launchpad_contract, backer1, pool_id, deposit).await?;
+    assert_eq!(pledged, Some(parse_near!("1.2 N")), "Pledge should be accepted down to the pledge multiple.");
+    let spent = balance_before.as_yoctonear() - backer1.view_account().await?.balance.as_yoctonear();
+    assert!(spent >= parse_near!("1.2 N") && spent < deposit.as_yoctonear(), "Backer should only pay the accepted pledge and gas.");
+
+    let pool: PoolMetadata = launchpad_contract
+        .call("get_detail_pool")
+        .args_json(json!({"pool_id": pool_id}))
+        .view()
+        .await?
+        .json::<Option<PoolMetadata>>()?
+        .unwrap();
+    assert_eq!(pool.total_balance, parse_near!("1.2 N"), "Pool should hold the accepted NEAR.");
+
+    // the withdrawn pledge comes back as a plain NEAR transfer
+    let balance_before = backer1.view_account().await?.balance;
+    assert!(withdraw_pledge(launchpad_contract, backer1, pool_id, None).await?, "Withdrawing a NEAR pledge should succeed.");
+    let received = backer1.view_account().await?.balance.as_yoctonear() - balance_before.as_yoctonear();
+    assert!(received > parse_near!("1.19 N"), "Backer should get the NEAR pledge back.");
+
+    println!("      Passed ✅ test_native_near_pledge");
+    Ok(())
+}
+
+// produces sandbox blocks until the block timestamp is past `timestamp`
+async fn fast_forward_past(worker: &Worker<Sandbox>, launchpad_contract: &Contract, timestamp: u64) -> anyhow::Result<()> {
+    loop {
+        let before: u64 = launchpad_contract.call("get_current_timestamp").view().await?.json()?;
+        if before > timestamp {
+            return Ok(());
+        }
+
+        worker.fast_forward(FAST_FORWARD_PROBE_BLOCKS).await?;
+        let after: u64 = launchpad_contract.call("get_current_timestamp").view().await?.json()?;
+        let block_time = ((after - before) / FAST_FORWARD_PROBE_BLOCKS).max(1);
+
+        if after <= timestamp {
+            worker.fast_forward((timestamp - after) / block_time + 1).await?;
+        }
+    }
+}
+
+// advance a scheduled pool into FUNDING once its pledge window has opened
+async fn open_funding(launchpad_contract: &Contract, keeper: &Account, pool_id: u64) -> anyhow::Result<()> {
+    keeper
+        .call(launchpad_contract.id(), "advance_pool")
+        .args_json(json!({"pool_id": pool_id}))
+        .gas(NearGas::from_tgas(100))
+        .transact()
+        .await?
+        .into_result()?;
+    Ok(())
+}
+
+async fn pool_by_campaign(launchpad_contract: &Contract, status: &str, campaign_id: &str) -> anyhow::Result<PoolMetadata> {
+    let pools: Vec<PoolMetadata> = launchpad_contract
+        .call("get_pools_by_status")
+        .args_json(json!({"status_str": status}))
+        .view()
+        .await?
+        .json::<Option<Vec<PoolMetadata>>>()?
+        .unwrap_or_default();
+    Ok(pools.into_iter().find(|pool| pool.campaign_id == campaign_id).expect("Pool should exist"))
+}
+
+pub async fn test_multi_token_pledges(
+    ft_contract: &Contract,
+    launchpad_contract: &Contract,
+    owner_launchpad: &Account,
+    creator: &Account,
+    backer1: &Account,
+    backer2: &Account
+) -> anyhow::Result<()> {
+
+    // 1 NEAR is worth 10 reference units, the FT stays at 1:1
+    let outcome = backer1
+        .call(launchpad_contract.id(), "set_token_rate")
+        .args_json(json!({"token_id": "near", "rate": "10"}))
+        .transact()
+        .await?;
+    assert!(outcome.is_failure(), "Only a treasury manager should set conversion rates.");
+
+    let outcome = owner_launchpad
+        .call(launchpad_contract.id(), "set_token_rate")
+        .args_json(json!({"token_id": "near", "rate": "0"}))
+        .transact()
+        .await?;
+    assert!(outcome.is_failure(), "A zero conversion rate should be rejected.");
+
+    owner_launchpad
+        .call(launchpad_contract.id(), "set_token_rate")
+        .args_json(json!({"token_id": "near", "rate": "10"}))
+        .transact()
+        .await?
+        .into_result()?;
+
+    let pool: PoolMetadata = creator
+        .call(launchpad_contract.id(), "init_pool")
+        .args_json(json!({
+            "campaign_id": "test-multi",
+            "token_id": ft_contract.id(),
+            "min_multiple_pledge": 1,
+            "target_funding": "100"
+        }))
+        .deposit(INIT_POOL)
+        .transact()
+        .await?
+        .json()?;
+    let pool_id = pool.pool_id;
+
+    let outcome = backer1
+        .call(launchpad_contract.id(), "set_accepted_tokens")
+        .args_json(json!({"pool_id": pool_id, "accepted_tokens": ["near"]}))
+        .transact()
+        .await?;
+    assert!(outcome.is_failure(), "Only the creator should set the accepted tokens.");
+
+    creator
+        .call(launchpad_contract.id(), "set_accepted_tokens")
+        .args_json(json!({"pool_id": pool_id, "accepted_tokens": ["near", ft_contract.id()]}))
+        .transact()
+        .await?
+        .into_result()?;
+
+    owner_launchpad
+        .call(launchpad_contract.id(), "admin_set_status_pool_pre_funding")
+        .args_json(json!({"pool_id": pool_id, "approve": true}))
+        .transact()
+        .await?
+        .into_result()?;
+
+    let time_now: u64 = launchpad_contract.call("get_current_timestamp").view().await?.json()?;
+    creator
+        .call(launchpad_contract.id(), "set_funding_pool_by_creator")
+        .args_json(json!({
+            "pool_id": pool_id,
+            "time_start_pledge": time_now + 2_000_000_000,
+            "funding_duration_days": 1
+        }))
+        .transact()
+        .await?
+        .into_result()?;
+
+    sleep(Duration::from_secs(3)).await;
+    open_funding(launchpad_contract, creator, pool_id).await?;
+
+    pledge(ft_contract, launchpad_contract, backer1, pool_id, 40).await?;
+    let pledged = near_pledge(launchpad_contract, backer2, pool_id, NearToken::from_near(3)).await?;
+    assert_eq!(pledged, Some(parse_near!("3 N")), "NEAR pledge should be accepted in full.");
+
+    let pool: PoolMetadata = launchpad_contract
+        .call("get_detail_pool")
+        .args_json(json!({"pool_id": pool_id}))
+        .view()
+        .await?
+        .json::<Option<PoolMetadata>>()?
+        .unwrap();
+    let token_ids: Vec<&str> = pool.tokens.iter().map(|token| token.token_id.as_str()).collect();
+    assert_eq!(token_ids, vec![ft_contract.id().as_str(), "near"], "Pool token should come first without duplicates.");
+    assert_eq!(pool.total_balance, 70, "Pool should hold 40 units in FT and 30 in NEAR.");
+    assert_eq!(pool.tokens[0].balance, 40, "Pool should hold the FT pledge.");
+    assert_eq!(pool.tokens[1].balance, parse_near!("3 N"), "Pool should hold the NEAR pledge.");
+
+    let token_pledges: Vec<TokenPledge> = launchpad_contract
+        .call("get_token_pledges")
+        .args_json(json!({"pool_id": pool_id, "account_id": backer2.id()}))
+        .view()
+        .await?
+        .json()?;
+    assert_eq!(token_pledges.len(), 1, "Backer should have pledged one token.");
+    assert_eq!(token_pledges[0].token_id.as_str(), "near", "Backer should have pledged NEAR.");
+    assert_eq!((token_pledges[0].amount, token_pledges[0].value), (parse_near!("3 N"), 30), "Pledge should keep its worth.");
+
+    // a later rate does not change the worth of pledges already made
+    owner_launchpad
+        .call(launchpad_contract.id(), "set_token_rate")
+        .args_json(json!({"token_id": "near", "rate": null}))
+        .transact()
+        .await?
+        .into_result()?;
+
+    let rate: U128 = launchpad_contract
+        .call("get_token_rate")
+        .args_json(json!({"token_id": "near"}))
+        .view()
+        .await?
+        .json()?;
+    assert_eq!(rate.0, parse_near!("1 N"), "Unset rate should be 1:1.");
+
+    println!("      Passed ✅ test_multi_token_pledges");
+    Ok(())
+}
+
+pub async fn test_multi_token_refunds(
+    ft_contract: &Contract,
+    launchpad_contract: &Contract,
+    owner_launchpad: &Account,
+    backer1: &Account,
+    backer2: &Account
+) -> anyhow::Result<()> {
+
+    let pool = pool_by_campaign(launchpad_contract, "FUNDING", "test-multi").await?;
+    let pool_id = pool.pool_id;
+    assert_eq!(pool.total_balance, 70, "Later rates should not change the pool balance.");
+
+    owner_launchpad
+        .call(launchpad_contract.id(), "update_pool_status")
+        .args_json(json!({"pool_id": pool_id, "status": "REFUNDED"}))
+        .gas(NearGas::from_tgas(50))
+        .transact()
+        .await?
+        .into_result()?;
+
+    // every backer gets the token it pledged back
+    let ft_before = ft_balance(ft_contract, backer1).await?;
+    backer1
+        .call(launchpad_contract.id(), "claim_refund")
+        .args_json(json!({"pool_id": pool_id}))
+        .gas(DEFAULT_GAS)
+        .transact()
+        .await?
+        .into_result()?;
+    assert_eq!(ft_balance(ft_contract, backer1).await? - ft_before, 40, "FT backer should get the FT back.");
+
+    let near_before = backer2.view_account().await?.balance.as_yoctonear();
+    backer2
+        .call(launchpad_contract.id(), "claim_refund")
+        .args_json(json!({"pool_id": pool_id}))
+        .gas(DEFAULT_GAS)
+        .transact()
+        .await?
+        .into_result()?;
+    let received = backer2.view_account().await?.balance.as_yoctonear() - near_before;
+    assert!(received > parse_near!("2.99 N"), "NEAR backer should get the NEAR back.");
+
+    let pool: PoolMetadata = launchpad_contract
+        .call("get_detail_pool")
+        .args_json(json!({"pool_id": pool_id}))
+        .view()
+        .await?
+        .json::<Option<PoolMetadata>>()?
+        .unwrap();
+    assert!(pool.tokens.iter().all(|token| token.balance == 0 && token.pledged == 0), "Every token should be refunded.");
+    assert_eq!(pool.total_balance, 0, "Pool should be empty.");
+
+    println!("      Passed ✅ test_multi_token_refunds");
+    Ok(())
+}
+
+pub async fn test_reward_tiers(
+    ft_contract: &Contract,
+    launchpad_contract: &Contract,
+    owner_launchpad: &Account,
+    creator: &Account,
+    backer1: &Account,
+    backer2: &Account
+) -> anyhow::Result<()> {
+
+    let pool: PoolMetadata = creator
+        .call(launchpad_contract.id(), "init_pool")
+        .args_json(json!({
+            "campaign_id": "test-tiers",
+            "token_id": ft_contract.id(),
+            "min_multiple_pledge": 1,
+            "target_funding": "1000"
+        }))
+        .deposit(INIT_POOL)
+        .transact()
+        .await?
+        .json()?;
+    let pool_id = pool.pool_id;
+
+    let tiers = json!([
+        {"min_pledge": "100", "quantity": 1, "description": "Lifetime course access"},
+        {"min_pledge": "10", "description": "Certificate of support"}
+    ]);
+
+    let outcome = backer1
+        .call(launchpad_contract.id(), "set_reward_tiers")
+        .args_json(json!({"pool_id": pool_id, "tiers": tiers}))
+        .transact()
+        .await?;
+    assert!(outcome.is_failure(), "Only the creator should set reward tiers.");
+
+    let outcome = creator
+        .call(launchpad_contract.id(), "set_reward_tiers")
+        .args_json(json!({"pool_id": pool_id, "tiers": [{"min_pledge": "0", "description": "Free"}]}))
+        .transact()
+        .await?;
+    assert!(outcome.is_failure(), "A tier without minimum pledge should be rejected.");
+
+    creator
+        .call(launchpad_contract.id(), "set_reward_tiers")
+        .args_json(json!({"pool_id": pool_id, "tiers": tiers}))
+        .transact()
+        .await?
+        .into_result()?;
+
+    owner_launchpad
+        .call(launchpad_contract.id(), "admin_set_status_pool_pre_funding")
+        .args_json(json!({"pool_id": pool_id, "approve": true}))
+        .transact()
+        .await?
+        .into_result()?;
+
+    let time_now: u64 = launchpad_contract.call("get_current_timestamp").view().await?.json()?;
+    creator
+        .call(launchpad_contract.id(), "set_funding_pool_by_creator")
+        .args_json(json!({
+            "pool_id": pool_id,
+            "time_start_pledge": time_now + 2_000_000_000,
+            "funding_duration_days": 1
+        }))
+        .transact()
+        .await?
+        .into_result()?;
+
+    let outcome = creator
+        .call(launchpad_contract.id(), "set_reward_tiers")
+        .args_json(json!({"pool_id": pool_id, "tiers": tiers}))
+        .transact()
+        .await?;
+    assert!(outcome.is_failure(), "Reward tiers should be fixed once funding is scheduled.");
+
+    sleep(Duration::from_secs(3)).await;
+    open_funding(launchpad_contract, creator, pool_id).await?;
+
+    pledge_with_msg(ft_contract, launchpad_contract, backer1, &format!("{}:0", pool_id), 100).await?;
+
+    // a sold out, missing or unreached tier sends the whole pledge back
+    let balance_before = ft_balance(ft_contract, backer2).await?;
+    pledge_with_msg(ft_contract, launchpad_contract, backer2, &format!("{}:0", pool_id), 150).await?;
+    pledge_with_msg(ft_contract, launchpad_contract, backer2, &format!("{}:5", pool_id), 150).await?;
+    pledge_with_msg(ft_contract, launchpad_contract, backer2, &format!("{}:1", pool_id), 5).await?;
+    assert_eq!(ft_balance(ft_contract, backer2).await?, balance_before, "Rejected tier pledges should be refunded.");
+
+    pledge_with_msg(ft_contract, launchpad_contract, backer2, &format!("{}:1", pool_id), 20).await?;
+
+    let tiers: Vec<RewardTier> = launchpad_contract
+        .call("get_reward_tiers")
+        .args_json(json!({"pool_id": pool_id}))
+        .view()
+        .await?
+        .json()?;
+    assert_eq!((tiers[0].allocated, tiers[1].allocated), (1, 1), "Each tier should have one backer.");
+
+    let backer_tier: Option<BackerTier> = launchpad_contract
+        .call("get_backer_tier")
+        .args_json(json!({"pool_id": pool_id, "account_id": backer1.id()}))
+        .view()
+        .await?
+        .json()?;
+    let backer_tier = backer_tier.expect("Backer should hold a tier.");
+    assert_eq!(backer_tier.tier_index, 0, "Backer should hold the first tier.");
+    assert_eq!(backer_tier.tier.description, "Lifetime course access", "Tier should come with its perk.");
+
+    // falling below the tier minimum gives the tier back
+    assert!(withdraw_pledge(launchpad_contract, backer1, pool_id, Some(95)).await?, "Withdraw should succeed.");
+
+    let backer_tier: Option<BackerTier> = launchpad_contract
+        .call("get_backer_tier")
+        .args_json(json!({"pool_id": pool_id, "account_id": backer1.id()}))
+        .view()
+        .await?
+        .json()?;
+    assert!(backer_tier.is_none(), "Backer should have given the tier back.");
+
+    let tiers: Vec<RewardTier> = launchpad_contract
+        .call("get_reward_tiers")
+        .args_json(json!({"pool_id": pool_id}))
+        .view()
+        .await?
+        .json()?;
+    assert_eq!(tiers[0].allocated, 0, "The limited tier should be available again.");
+
+    println!("      Passed ✅ test_reward_tiers");
+    Ok(())
+}
+
+pub async fn test_transfer_messages(
+    ft_contract: &Contract,
+    launchpad_contract: &Contract,
+    owner_launchpad: &Account,
+    creator: &Account,
+    backer: &Account
+) -> anyhow::Result<()> {
+
+    let pool: PoolMetadata = creator
+        .call(launchpad_contract.id(), "init_pool")
+        .args_json(json!({
+            "campaign_id": "test-messages",
+            "token_id": ft_contract.id(),
+            "min_multiple_pledge": 1,
+            "target_funding": "1000"
+        }))
+        .deposit(INIT_POOL)
+        .transact()
+        .await?
+        .json()?;
+    let pool_id = pool.pool_id;
+
+    owner_launchpad
+        .call(launchpad_contract.id(), "admin_set_status_pool_pre_funding")
+        .args_json(json!({"pool_id": pool_id, "approve": true}))
+        .transact()
+        .await?
+        .into_result()?;
+
+    let time_now: u64 = launchpad_contract.call("get_current_timestamp").view().await?.json()?;
+    creator
+        .call(launchpad_contract.id(), "set_funding_pool_by_creator")
+        .args_json(json!({
+            "pool_id": pool_id,
+            "time_start_pledge": time_now + 2_000_000_000,
+            "funding_duration_days": 1
+        }))
+        .transact()
+        .await?
+        .into_result()?;
+
+    sleep(Duration::from_secs(3)).await;
+    open_funding(launchpad_contract, creator, pool_id).await?;
+
+    let balance_before = ft_balance(ft_contract, backer).await?;
+
+    let rejected = [
+        (json!({"version": 2, "action": "pledge", "pool_id": pool_id}).to_string(), "UnsupportedVersion"),
+        (json!({"action": "swap", "pool_id": pool_id}).to_string(), "InvalidMessage"),
+        (json!({"action": "pledge"}).to_string(), "InvalidMessage"),
+        ("not-a-pool".to_string(), "InvalidMessage"),
+        (json!({"action": "pledge", "pool_id": 9999}).to_string(), "PoolNotFound"),
+        (json!({"action": "pledge", "pool_id": pool_id, "tier": 0}).to_string(), "TierNotFound"),
+    ];
+    for (msg, reason) in rejected {
+        let logs = pledge_with_msg(ft_contract, launchpad_contract, backer, &msg, 10).await?;
+        assert!(
+            logs.iter().any(|log| log.contains("pledge_rejected") && log.contains(reason)),
+            "Message {} should be rejected with {}.", msg, reason
+        );
+    }
+    assert_eq!(ft_balance(ft_contract, backer).await?, balance_before, "Rejected messages should be refunded.");
+
+    // the JSON form and the bare pool ID of older callers both pledge
+    pledge_with_msg(ft_contract, launchpad_contract, backer, &json!({"version": 1, "action": "pledge", "pool_id": pool_id}).to_string(), 10).await?;
+    pledge_with_msg(ft_contract, launchpad_contract, backer, &json!({"action": "pledge", "pool_id": pool_id}).to_string(), 20).await?;
+    pledge(ft_contract, launchpad_contract, backer, pool_id, 30).await?;
+
+    let pool: PoolMetadata = launchpad_contract
+        .call("get_detail_pool")
+        .args_json(json!({"pool_id": pool_id}))
+        .view()
+        .await?
+        .json()?;
+    assert_eq!(pool.total_balance, 60, "Every message form should pledge.");
+
+    println!("      Passed ✅ test_transfer_messages");
+    Ok(())
+}
+
+pub async fn test_referrals(
+    ft_contract: &Contract,
+    launchpad_contract: &Contract,
+    owner_launchpad: &Account,
+    creator: &Account,
+    backer1: &Account,
+    referrer: &Account
+) -> anyhow::Result<()> {
+
+    let outcome = backer1
+        .call(launchpad_contract.id(), "set_referral_reward")
+        .args_json(json!({"reward_bps": 100}))
+        .transact()
+        .await?;
+    assert!(outcome.is_failure(), "Only a treasury manager should set the referral reward.");
+
+    let outcome = owner_launchpad
+        .call(launchpad_contract.id(), "set_referral_reward")
+        .args_json(json!({"reward_bps": 5_001}))
+        .transact()
+        .await?;
+    assert!(outcome.is_failure(), "Referral reward above the maximum should be rejected.");
+
+    owner_launchpad
+        .call(launchpad_contract.id(), "set_referral_reward")
+        .args_json(json!({"reward_bps": 100}))
+        .transact()
+        .await?
+        .into_result()?;
+
+    let reward_bps: u16 = launchpad_contract.call("get_referral_reward").view().await?.json()?;
+    assert_eq!(reward_bps, 100, "Referral reward should be set.");
+
+    let pool: PoolMetadata = creator
+        .call(launchpad_contract.id(), "init_pool")
+        .args_json(json!({
+            "campaign_id": "test-referrals",
+            "token_id": ft_contract.id(),
+            "min_multiple_pledge": 1,
+            "target_funding": "1000"
+        }))
+        .deposit(INIT_POOL)
+        .transact()
+        .await?
+        .json()?;
+    let pool_id = pool.pool_id;
+
+    owner_launchpad
+        .call(launchpad_contract.id(), "admin_set_status_pool_pre_funding")
+        .args_json(json!({"pool_id": pool_id, "approve": true}))
+        .transact()
+        .await?
+        .into_result()?;
+
+    let time_now: u64 = launchpad_contract.call("get_current_timestamp").view().await?.json()?;
+    creator
+        .call(launchpad_contract.id(), "set_funding_pool_by_creator")
+        .args_json(json!({
+            "pool_id": pool_id,
+            "time_start_pledge": time_now + 2_000_000_000,
+            "funding_duration_days": 1
+        }))
+        .transact()
+        .await?
+        .into_result()?;
+
+    sleep(Duration::from_secs(3)).await;
+    open_funding(launchpad_contract, creator, pool_id).await?;
+
+    let logs = pledge_with_msg(ft_contract, launchpad_contract, backer1, &json!({"action": "pledge", "pool_id": pool_id, "referrer": referrer.id()}).to_string(), 100).await?;
+    assert!(logs.iter().any(|log| log.contains("referral_recorded")), "First pledge should record the referrer.");
+
+    // the referrer of the first pledge stays, a backer cannot refer itself
+    pledge_with_msg(ft_contract, launchpad_contract, backer1, &json!({"action": "pledge", "pool_id": pool_id, "referrer": creator.id()}).to_string(), 50).await?;
+    pledge_with_msg(ft_contract, launchpad_contract, referrer, &json!({"action": "pledge", "pool_id": pool_id, "referrer": referrer.id()}).to_string(), 40).await?;
+
+    let backer_referrer: Option<String> = launchpad_contract
+        .call("get_referrer")
+        .args_json(json!({"pool_id": pool_id, "account_id": backer1.id()}))
+        .view()
+        .await?
+        .json()?;
+    assert_eq!(backer_referrer.as_deref(), Some(referrer.id().as_str()), "Backer should keep the first referrer.");
+
+    let self_referrer: Option<String> = launchpad_contract
+        .call("get_referrer")
+        .args_json(json!({"pool_id": pool_id, "account_id": referrer.id()}))
+        .view()
+        .await?
+        .json()?;
+    assert!(self_referrer.is_none(), "Self-referral should be ignored.");
+
+    assert!(withdraw_pledge(launchpad_contract, backer1, pool_id, Some(30)).await?, "Withdraw should succeed.");
+
+    let stats: Option<ReferralStats> = launchpad_contract
+        .call("get_referral_stats")
+        .args_json(json!({"pool_id": pool_id, "referrer_id": referrer.id()}))
+        .view()
+        .await?
+        .json()?;
+    let stats = stats.expect("Referrer should have stats in the pool.");
+    assert_eq!(stats.backers, 1, "Referrer should have brought one backer.");
+    assert_eq!(stats.pledged, 120, "Referred pledges should follow the withdrawal.");
+    assert_eq!(stats.tokens[0].amount, 120, "Referred token pledge should follow the withdrawal.");
+
+    let referrals: Vec<ReferralStats> = launchpad_contract
+        .call("get_pool_referrals")
+        .args_json(json!({"pool_id": pool_id}))
+        .view()
+        .await?
+        .json()?;
+    assert_eq!(referrals.len(), 1, "Pool should have one referrer.");
+
+    // nothing is set aside before a tranche of the pool pays its fee
+    let reserve: ReferralReserve = launchpad_contract
+        .call("get_referral_reserve")
+        .args_json(json!({"pool_id": pool_id, "token_id": ft_contract.id()}))
+        .view()
+        .await?
+        .json()?;
+    assert_eq!((reserve.referred, reserve.reserved, reserve.paid), (120, 0, 0), "Reserve should follow the referred pledges.");
+
+    let outcome = referrer
+        .call(launchpad_contract.id(), "claim_referral_reward")
+        .args_json(json!({"pool_id": pool_id}))
+        .transact()
+        .await?;
+    assert!(outcome.is_failure(), "Referral rewards should wait for the pool to succeed.");
+
+    println!("      Passed ✅ test_referrals");
+    Ok(())
+}
+
+pub async fn test_restricted_pools(
+    ft_contract: &Contract,
+    launchpad_contract: &Contract,
+    owner_launchpad: &Account,
+    creator: &Account,
+    backer1: &Account,
+    backer2: &Account
+) -> anyhow::Result<()> {
+
+    let outcome = owner_launchpad
+        .call(launchpad_contract.id(), "set_pledge_signer_key")
+        .args_json(json!({"public_key": "not-a-key"}))
+        .transact()
+        .await?;
+    assert!(outcome.is_failure(), "An invalid signer key should be rejected.");
+
+    owner_launchpad
+        .call(launchpad_contract.id(), "set_pledge_signer_key")
+        .args_json(json!({"public_key": encode(pledge_signer().public.to_bytes())}))
+        .transact()
+        .await?
+        .into_result()?;
+
+    let mut pool_ids = Vec::new();
+    for (campaign_id, access) in [("test-allowlist", "Allowlist"), ("test-signature", "Signature")] {
+        let pool: PoolMetadata = creator
+            .call(launchpad_contract.id(), "init_pool")
+            .args_json(json!({
+                "campaign_id": campaign_id,
+                "token_id": ft_contract.id(),
+                "min_multiple_pledge": 1,
+                "target_funding": "1000"
+            }))
+            .deposit(INIT_POOL)
+            .transact()
+            .await?
+            .json()?;
+
+        let outcome = backer1
+            .call(launchpad_contract.id(), "set_pool_access")
+            .args_json(json!({"pool_id": pool.pool_id, "access": access}))
+            .transact()
+            .await?;
+        assert!(outcome.is_failure(), "Only the creator should restrict a pool.");
+
+        creator
+            .call(launchpad_contract.id(), "set_pool_access")
+            .args_json(json!({"pool_id": pool.pool_id, "access": access}))
+            .transact()
+            .await?
+            .into_result()?;
+
+        owner_launchpad
+            .call(launchpad_contract.id(), "admin_set_status_pool_pre_funding")
+            .args_json(json!({"pool_id": pool.pool_id, "approve": true}))
+            .transact()
+            .await?
+            .into_result()?;
+
+        let time_now: u64 = launchpad_contract.call("get_current_timestamp").view().await?.json()?;
+        creator
+            .call(launchpad_contract.id(), "set_funding_pool_by_creator")
+            .args_json(json!({
+                "pool_id": pool.pool_id,
+                "time_start_pledge": time_now + 2_000_000_000,
+                "funding_duration_days": 1
+            }))
+            .transact()
+            .await?
+            .into_result()?;
+
+        pool_ids.push(pool.pool_id);
+    }
+    let (allowlist_pool_id, signature_pool_id) = (pool_ids[0], pool_ids[1]);
+
+    creator
+        .call(launchpad_contract.id(), "add_to_allowlist")
+        .args_json(json!({"pool_id": allowlist_pool_id, "account_ids": [backer1.id()]}))
+        .transact()
+        .await?
+        .into_result()?;
+
+    sleep(Duration::from_secs(3)).await;
+    for pool_id in pool_ids.iter() {
+        open_funding(launchpad_contract, creator, *pool_id).await?;
+    }
+
+    let balance_before = ft_balance(ft_contract, backer2).await?;
+    let rejected = [
+        (allowlist_pool_id.to_string(), "NotAllowlisted"),
+        (signature_pool_id.to_string(), "SignatureRequired"),
+        (json!({"action": "pledge", "pool_id": signature_pool_id, "signature": pledge_signature(signature_pool_id, backer1)}).to_string(), "InvalidSignature"),
+        (json!({"action": "pledge", "pool_id": signature_pool_id, "signature": pledge_signature(allowlist_pool_id, backer2)}).to_string(), "InvalidSignature"),
+    ];
+    for (msg, reason) in rejected {
+        let logs = pledge_with_msg(ft_contract, launchpad_contract, backer2, &msg, 10).await?;
+        assert!(
+            logs.iter().any(|log| log.contains("pledge_rejected") && log.contains(reason)),
+            "Message {} should be rejected with {}.", msg, reason
+        );
+    }
+    assert_eq!(ft_balance(ft_contract, backer2).await?, balance_before, "Non-eligible senders should be refunded.");
+
+    pledge(ft_contract, launchpad_contract, backer1, allowlist_pool_id, 10).await?;
+
+    // a valid signature puts the backer on the allowlist, later pledges need none
+    let signed_msg = json!({"action": "pledge", "pool_id": signature_pool_id, "signature": pledge_signature(signature_pool_id, backer2)});
+    pledge_with_msg(ft_contract, launchpad_contract, backer2, &signed_msg.to_string(), 10).await?;
+    pledge(ft_contract, launchpad_contract, backer2, signature_pool_id, 10).await?;
+
+    let allowlisted: bool = launchpad_contract
+        .call("is_allowlisted")
+        .args_json(json!({"pool_id": signature_pool_id, "account_id": backer2.id()}))
+        .view()
+        .await?
+        .json()?;
+    assert!(allowlisted, "Signed backer should be on the allowlist.");
+
+    for (pool_id, expected_balance) in [(allowlist_pool_id, 10), (signature_pool_id, 20)] {
+        let pool: PoolMetadata = launchpad_contract
+            .call("get_detail_pool")
+            .args_json(json!({"pool_id": pool_id}))
+            .view()
+            .await?
+            .json()?;
+        assert_eq!(pool.total_balance, expected_balance, "Eligible backers should pledge.");
+    }
+
+    creator
+        .call(launchpad_contract.id(), "remove_from_allowlist")
+        .args_json(json!({"pool_id": allowlist_pool_id, "account_ids": [backer1.id()]}))
+        .transact()
+        .await?
+        .into_result()?;
+
+    let allowlist: Vec<String> = launchpad_contract
+        .call("get_allowlist")
+        .args_json(json!({"pool_id": allowlist_pool_id}))
+        .view()
+        .await?
+        .json()?;
+    assert!(allowlist.is_empty(), "Allowlist should be empty again.");
+
+    println!("      Passed ✅ test_restricted_pools");
+    Ok(())
+}
+
+pub async fn test_stretch_goals(
+    ft_contract: &Contract,
+    launchpad_contract: &Contract,
+    owner_launchpad: &Account,
+    creator: &Account,
+    backer1: &Account,
+    backer2: &Account
+) -> anyhow::Result<()> {
+
+    let pool: PoolMetadata = creator
+        .call(launchpad_contract.id(), "init_pool")
+        .args_json(json!({
+            "campaign_id": "test-stretch",
+            "token_id": ft_contract.id(),
+            "min_multiple_pledge": 1,
+            "target_funding": "1000",
+            "pledge_limits": {"hard_cap": "3000"}
+        }))
+        .deposit(INIT_POOL)
+        .transact()
+        .await?
+        .json()?;
+    let pool_id = pool.pool_id;
+
+    let goals = json!([
+        {"amount": "1500", "description": "Subtitles in five languages"},
+        {"amount": "2500", "description": "Live mentoring sessions"}
+    ]);
+
+    let outcome = backer1
+        .call(launchpad_contract.id(), "set_stretch_goals")
+        .args_json(json!({"pool_id": pool_id, "goals": goals}))
+        .transact()
+        .await?;
+    assert!(outcome.is_failure(), "Only the creator should set stretch goals.");
+
+    let invalid_goals = [
+        json!([{"amount": "1000", "description": "At the target"}]),
+        json!([{"amount": "2000", "description": "Second"}, {"amount": "1500", "description": "First"}]),
+        json!([{"amount": "3500", "description": "Above the hard cap"}]),
+        json!([{"amount": "1500", "description": ""}]),
+    ];
+    for invalid in invalid_goals {
+        let outcome = creator
+            .call(launchpad_contract.id(), "set_stretch_goals")
+            .args_json(json!({"pool_id": pool_id, "goals": invalid}))
+            .transact()
+            .await?;
+        assert!(outcome.is_failure(), "Invalid stretch goals should be rejected: {}", invalid);
+    }
+
+    creator
+        .call(launchpad_contract.id(), "set_stretch_goals")
+        .args_json(json!({"pool_id": pool_id, "goals": goals}))
+        .transact()
+        .await?
+        .into_result()?;
+
+    let policy: String = launchpad_contract
+        .call("get_overfunding_policy")
+        .args_json(json!({"pool_id": pool_id}))
+        .view()
+        .await?
+        .json()?;
+    assert_eq!(policy, "AcceptAll", "Pools should keep their whole raise by default.");
+
+    creator
+        .call(launchpad_contract.id(), "set_overfunding_policy")
+        .args_json(json!({"pool_id": pool_id, "policy": "CapAndRefund"}))
+        .transact()
+        .await?
+        .into_result()?;
+
+    owner_launchpad
+        .call(launchpad_contract.id(), "admin_set_status_pool_pre_funding")
+        .args_json(json!({"pool_id": pool_id, "approve": true}))
+        .transact()
+        .await?
+        .into_result()?;
+
+    let time_now: u64 = launchpad_contract.call("get_current_timestamp").view().await?.json()?;
+    creator
+        .call(launchpad_contract.id(), "set_funding_pool_by_creator")
+        .args_json(json!({
+            "pool_id": pool_id,
+            "time_start_pledge": time_now + 2_000_000_000,
+            "funding_duration_days": 1
+        }))
+        .transact()
+        .await?
+        .into_result()?;
+
+    let outcome = creator
+        .call(launchpad_contract.id(), "set_overfunding_policy")
+        .args_json(json!({"pool_id": pool_id, "policy": "ProRata"}))
+        .transact()
+        .await?;
+    assert!(outcome.is_failure(), "Overfunding policy should be fixed once funding is scheduled.");
+
+    sleep(Duration::from_secs(3)).await;
+    open_funding(launchpad_contract, creator, pool_id).await?;
+
+    // 2000 raised: the first stretch goal is reached, the last 500 pledged are above it
+    pledge(ft_contract, launchpad_contract, backer1, pool_id, 1000).await?;
+    pledge(ft_contract, launchpad_contract, backer2, pool_id, 1000).await?;
+
+    let stretch_goals: Vec<StretchGoal> = launchpad_contract
+        .call("get_stretch_goals")
+        .args_json(json!({"pool_id": pool_id}))
+        .view()
+        .await?
+        .json()?;
+    assert_eq!(stretch_goals.len(), 2);
+    assert!(stretch_goals.iter().all(|goal| !goal.unlocked), "Goals should unlock when funding closes.");
+
+    println!("      Passed ✅ test_stretch_goals");
+    Ok(())
+}
+
+pub async fn test_funding_terms(
+    ft_contract: &Contract,
+    launchpad_contract: &Contract,
+    owner_launchpad: &Account,
+    creator: &Account
+) -> anyhow::Result<()> {
+    const DAY: u64 = 24 * 60 * 60 * 1_000_000_000;
+
+    let defaults: FundingTerms = launchpad_contract.call("get_funding_terms").view().await?.json()?;
+    assert_eq!(defaults, FundingTerms { waiting_threshold_percent: 80, waiting_extension: 3 * DAY, init_expiry: 15 * DAY });
+
+    let outcome = creator
+        .call(launchpad_contract.id(), "set_funding_terms")
+        .args_json(json!({"terms": {"waiting_threshold_percent": 70, "waiting_extension": 2 * DAY, "init_expiry": 10 * DAY}}))
+        .transact()
+        .await?;
+    assert!(outcome.is_failure(), "Only the admin should set the funding terms.");
+
+    let outcome = owner_launchpad
+        .call(launchpad_contract.id(), "set_funding_terms")
+        .args_json(json!({"terms": {"waiting_threshold_percent": 100, "waiting_extension": 2 * DAY, "init_expiry": 10 * DAY}}))
+        .transact()
+        .await?;
+    assert!(outcome.is_failure(), "A waiting threshold at the target should be rejected.");
+
+    owner_launchpad
+        .call(launchpad_contract.id(), "set_funding_terms")
+        .args_json(json!({"terms": {"waiting_threshold_percent": 70, "waiting_extension": 2 * DAY, "init_expiry": 10 * DAY}}))
+        .transact()
+        .await?
+        .into_result()?;
+
+    let outcome = creator
+        .call(launchpad_contract.id(), "init_pool")
+        .args_json(json!({
+            "campaign_id": "test-terms",
+            "token_id": ft_contract.id(),
+            "min_multiple_pledge": 1,
+            "target_funding": "1000",
+            "pledge_limits": {"init_expiry": 365 * DAY}
+        }))
+        .deposit(INIT_POOL)
+        .transact()
+        .await?;
+    assert!(outcome.is_failure(), "An init expiry out of range should be rejected.");
+
+    let pool: PoolMetadata = creator
+        .call(launchpad_contract.id(), "init_pool")
+        .args_json(json!({
+            "campaign_id": "test-terms",
+            "token_id": ft_contract.id(),
+            "min_multiple_pledge": 1,
+            "target_funding": "1000",
+            "pledge_limits": {"hard_cap": "2000", "waiting_threshold_percent": 90, "init_expiry": 2 * DAY}
+        }))
+        .deposit(INIT_POOL)
+        .transact()
+        .await?
+        .json()?;
+
+    // the terms a pool leaves out follow the contract's
+    let terms: Option<FundingTerms> = launchpad_contract
+        .call("get_pool_funding_terms")
+        .args_json(json!({"pool_id": pool.pool_id}))
+        .view()
+        .await?
+        .json()?;
+    assert_eq!(terms, Some(FundingTerms { waiting_threshold_percent: 90, waiting_extension: 2 * DAY, init_expiry: 2 * DAY }));
+
+    let terms: Option<FundingTerms> = launchpad_contract
+        .call("get_pool_funding_terms")
+        .args_json(json!({"pool_id": 1}))
+        .view()
+        .await?
+        .json()?;
+    assert_eq!(terms, Some(FundingTerms { waiting_threshold_percent: 70, waiting_extension: 2 * DAY, init_expiry: 10 * DAY }));
+
+    let terms: Option<FundingTerms> = launchpad_contract
+        .call("get_pool_funding_terms")
+        .args_json(json!({"pool_id": 10_000}))
+        .view()
+        .await?
+        .json()?;
+    assert!(terms.is_none(), "A missing pool has no funding terms.");
+
+    // the pools funded later in the run keep the default terms
+    owner_launchpad
+        .call(launchpad_contract.id(), "set_funding_terms")
+        .args_json(json!({"terms": defaults}))
+        .transact()
+        .await?
+        .into_result()?;
+
+    println!("      Passed ✅ test_funding_terms");
+    Ok(())
+}
+
+pub async fn test_reschedule_funding(
+    ft_contract: &Contract,
+    launchpad_contract: &Contract,
+    owner_launchpad: &Account,
+    creator: &Account,
+    backer1: &Account
+) -> anyhow::Result<()> {
+    const DAY: u64 = 24 * 60 * 60 * 1_000_000_000;
+    const HOUR: u64 = 60 * 60 * 1_000_000_000;
+
+    let pool: PoolMetadata = creator
+        .call(launchpad_contract.id(), "init_pool")
+        .args_json(json!({
+            "campaign_id": "test-reschedule",
+            "token_id": ft_contract.id(),
+            "min_multiple_pledge": 1,
+            "target_funding": "1000"
+        }))
+        .deposit(INIT_POOL)
+        .transact()
+        .await?
+        .json()?;
+    let pool_id = pool.pool_id;
+
+    owner_launchpad
+        .call(launchpad_contract.id(), "admin_set_status_pool_pre_funding")
+        .args_json(json!({"pool_id": pool_id, "approve": true}))
+        .transact()
+        .await?
+        .into_result()?;
+
+    let time_now: u64 = launchpad_contract.call("get_current_timestamp").view().await?.json()?;
+    let outcome = creator
+        .call(launchpad_contract.id(), "set_funding_pool_by_creator")
+        .args_json(json!({
+            "pool_id": pool_id,
+            "time_start_pledge": time_now + HOUR,
+            "funding_duration_days": 91
+        }))
+        .transact()
+        .await?;
+    assert!(outcome.is_failure(), "Funding longer than 90 days should be rejected.");
+
+    creator
+        .call(launchpad_contract.id(), "set_funding_pool_by_creator")
+        .args_json(json!({
+            "pool_id": pool_id,
+            "time_start_pledge": time_now + HOUR,
+            "funding_duration_days": 1
+        }))
+        .transact()
+        .await?
+        .into_result()?;
+
+    // the funding duration is counted in days, not minutes
+    let pool = pool_by_campaign(launchpad_contract, "APPROVED", "test-reschedule").await?;
+    assert_eq!(pool.time_end_pledge - pool.time_start_pledge, DAY, "Funding should last one day.");
+
+    let outcome = backer1
+        .call(launchpad_contract.id(), "change_pool_funding_time")
+        .args_json(json!({"pool_id": pool_id, "time_start_pledge": time_now + HOUR, "time_end_pledge": time_now + HOUR + 2 * DAY}))
+        .transact()
+        .await?;
+    assert!(outcome.is_failure(), "Only the creator or a pool reviewer should reschedule funding.");
+
+    let time_now: u64 = launchpad_contract.call("get_current_timestamp").view().await?.json()?;
+    let invalid_windows = [
+        (time_now - 1, time_now + DAY),
+        (time_now + HOUR, time_now + HOUR + DAY - 1),
+        (time_now + HOUR, time_now + HOUR + 91 * DAY),
+        (time_now + HOUR, time_now)
+    ];
+    for (time_start_pledge, time_end_pledge) in invalid_windows {
+        let outcome = creator
+            .call(launchpad_contract.id(), "change_pool_funding_time")
+            .args_json(json!({"pool_id": pool_id, "time_start_pledge": time_start_pledge, "time_end_pledge": time_end_pledge}))
+            .transact()
+            .await?;
+        assert!(outcome.is_failure(), "Funding window out of bounds should be rejected.");
+    }
+
+    // before pledging opens the creator can move the whole window
+    let time_start_pledge = time_now + 2_000_000_000;
+    let outcome = creator
+        .call(launchpad_contract.id(), "change_pool_funding_time")
+        .args_json(json!({"pool_id": pool_id, "time_start_pledge": time_start_pledge, "time_end_pledge": time_start_pledge + 2 * DAY}))
+        .transact()
+        .await?;
+    assert!(outcome.is_success(), "Creator should reschedule funding before it opens.");
+    assert!(
+        outcome.logs().iter().any(|log| log.contains("funding_rescheduled")),
+        "Rescheduling should be reported."
+    );
+
+    let pool = pool_by_campaign(launchpad_contract, "APPROVED", "test-reschedule").await?;
+    assert_eq!(pool.time_start_pledge, time_start_pledge);
+    assert_eq!(pool.time_end_pledge, time_start_pledge + 2 * DAY);
+    assert_eq!(pool.funding_duration_days, 2);
+
+    sleep(Duration::from_secs(3)).await;
+    open_funding(launchpad_contract, creator, pool_id).await?;
+
+    pledge(ft_contract, launchpad_contract, backer1, pool_id, 100).await?;
+
+    // once pledging opened the start is fixed and the end can only move later
+    let outcome = creator
+        .call(launchpad_contract.id(), "change_pool_funding_time")
+        .args_json(json!({"pool_id": pool_id, "time_start_pledge": time_start_pledge + HOUR, "time_end_pledge": time_start_pledge + 2 * DAY}))
+        .transact()
+        .await?;
+    assert!(outcome.is_failure(), "Start time should be fixed once pledging opened.");
+
+    let outcome = creator
+        .call(launchpad_contract.id(), "change_pool_funding_time")
+        .args_json(json!({"pool_id": pool_id, "time_start_pledge": time_start_pledge, "time_end_pledge": time_start_pledge + DAY}))
+        .transact()
+        .await?;
+    assert!(outcome.is_failure(), "Funding should not be shortened once pledging opened.");
+
+    creator
+        .call(launchpad_contract.id(), "change_pool_funding_time")
+        .args_json(json!({"pool_id": pool_id, "time_start_pledge": time_start_pledge, "time_end_pledge": time_start_pledge + 3 * DAY}))
+        .transact()
+        .await?
+        .into_result()?;
+
+    println!("      Passed ✅ test_reschedule_funding");
+    Ok(())
+}
+
+pub async fn test_overfunding_settlement(
+    ft_contract: &Contract,
+    launchpad_contract: &Contract,
+    owner_launchpad: &Account,
+    backer1: &Account,
+    backer2: &Account
+) -> anyhow::Result<()> {
+
+    let pool = pool_by_campaign(launchpad_contract, "FUNDING", "test-stretch").await?;
+    let pool_id = pool.pool_id;
+
+    owner_launchpad
+        .call(launchpad_contract.id(), "check_funding_result")
+        .args_json(json!({"pool_id": pool_id, "is_waiting_funding": false}))
+        .gas(NearGas::from_tgas(100))
+        .transact()
+        .await?
+        .into_result()?;
+
+    let pool: PoolMetadata = launchpad_contract
+        .call("get_detail_pool")
+        .args_json(json!({"pool_id": pool_id}))
+        .view()
+        .await?
+        .json::<Option<PoolMetadata>>()?
+        .unwrap();
+    assert_eq!(pool.status, Status::VOTING);
+    assert_eq!(pool.total_balance, 1500, "Pool should keep the first stretch goal.");
+
+    let stretch_goals: Vec<StretchGoal> = launchpad_contract
+        .call("get_stretch_goals")
+        .args_json(json!({"pool_id": pool_id}))
+        .view()
+        .await?
+        .json()?;
+    assert!(stretch_goals[0].unlocked, "Reached goal should be unlocked.");
+    assert!(!stretch_goals[1].unlocked, "Missed goal should stay locked.");
+
+    // the backer who pledged last gets the excess back
+    let excess: Vec<TokenPledge> = launchpad_contract
+        .call("get_excess")
+        .args_json(json!({"pool_id": pool_id, "account_id": backer1.id()}))
+        .view()
+        .await?
+        .json()?;
+    assert!(excess.is_empty(), "First backer should keep its whole pledge.");
+
+    let excess: Vec<TokenPledge> = launchpad_contract
+        .call("get_excess")
+        .args_json(json!({"pool_id": pool_id, "account_id": backer2.id()}))
+        .view()
+        .await?
+        .json()?;
+    assert_eq!(excess.len(), 1);
+    assert_eq!(excess[0].amount, 500);
+
+    let balance_before = ft_balance(ft_contract, backer2).await?;
+    backer2
+        .call(launchpad_contract.id(), "claim_excess")
+        .args_json(json!({"pool_id": pool_id}))
+        .gas(DEFAULT_GAS)
+        .transact()
+        .await?
+        .into_result()?;
+    assert_eq!(ft_balance(ft_contract, backer2).await? - balance_before, 500, "Backer should get the excess back.");
+
+    let outcome = backer2
+        .call(launchpad_contract.id(), "claim_excess")
+        .args_json(json!({"pool_id": pool_id}))
+        .gas(DEFAULT_GAS)
+        .transact()
+        .await?;
+    assert!(outcome.is_failure(), "Excess should only be claimed once.");
+
+    println!("      Passed ✅ test_overfunding_settlement");
+    Ok(())
+}
+
+pub async fn test_advance_pool(
+    launchpad_contract: &Contract,
+    owner_launchpad: &Account,
+    keeper: &Account
+) -> anyhow::Result<()> {
+
+    let outcome = keeper
+        .call(launchpad_contract.id(), "set_keeper_reward")
+        .args_json(json!({"amount": U128(parse_near!("0.05 N"))}))
+        .transact()
+        .await?;
+    assert!(outcome.is_failure(), "Only a treasury manager should set the keeper reward.");
+
+    let outcome = owner_launchpad
+        .call(launchpad_contract.id(), "set_keeper_reward")
+        .args_json(json!({"amount": U128(parse_near!("0.05 N"))}))
+        .transact()
+        .await?
+        .into_result()?;
+    assert_eq!(outcome.logs().len(), 1, "Setters should log their event only.");
+    assert_eq!(events(&outcome.logs(), "openedu_launchpad"), vec![json!({
+        "standard": "openedu_launchpad",
+        "version": "1.0.0",
+        "event": "config_changed",
+        "data": [{"setting": "keeper_reward", "value": parse_near!("0.05 N").to_string()}]
+    })]);
+
+    // the pledge window of the withdraw test pool has closed but nobody checked its result
+    let pool = pool_by_campaign(launchpad_contract, "FUNDING", "test-withdraw").await?;
+
+    let outcome = keeper
+        .call(launchpad_contract.id(), "advance_pool")
+        .args_json(json!({"pool_id": pool.pool_id}))
+        .gas(NearGas::from_tgas(100))
+        .transact()
+        .await?;
+    assert!(outcome.is_success(), "Anyone should advance a pool whose pledge window closed.");
+    assert!(
+        outcome.logs().iter().any(|log| log.contains("keeper_rewarded")),
+        "Keeper should be rewarded."
+    );
+
+    let pool: PoolMetadata = outcome.json()?;
+    assert_eq!(pool.status, Status::REFUNDED, "Pool far below its target should be refunded.");
+
+    let outcome = keeper
+        .call(launchpad_contract.id(), "advance_pool")
+        .args_json(json!({"pool_id": pool.pool_id}))
+        .gas(NearGas::from_tgas(100))
+        .transact()
+        .await?;
+    assert!(outcome.is_failure(), "Refunded pool should have no transition due.");
+
+    println!("      Passed ✅ test_advance_pool");
+    Ok(())
+}
+
+pub async fn test_rescheduled_funding_end(
+    worker: &Worker<Sandbox>,
+    launchpad_contract: &Contract,
+    keeper: &Account
+) -> anyhow::Result<()> {
+
+    // the pledge window was extended to three days, it outlives the pools funded for one day
+    let pool = pool_by_campaign(launchpad_contract, "FUNDING", "test-reschedule").await?;
+
+    let outcome = keeper
+        .call(launchpad_contract.id(), "advance_pool")
+        .args_json(json!({"pool_id": pool.pool_id}))
+        .gas(NearGas::from_tgas(100))
+        .transact()
+        .await?;
+    assert!(outcome.is_failure(), "Rescheduled pool should still be funding.");
+
+    fast_forward_past(worker, launchpad_contract, pool.time_end_pledge).await?;
+
+    let pool: PoolMetadata = keeper
+        .call(launchpad_contract.id(), "advance_pool")
+        .args_json(json!({"pool_id": pool.pool_id}))
+        .gas(NearGas::from_tgas(100))
+        .transact()
+        .await?
+        .json()?;
+    assert_eq!(pool.status, Status::REFUNDED, "Pool far below its target should be refunded once its new window closes.");
+
+    println!("      Passed ✅ test_rescheduled_funding_end");
+    Ok(())
+}
+
+// create a pool, approve it and wait until its one day funding window is open
+async fn open_funding_pool(
+    ft_contract: &Contract,
+    launchpad_contract: &Contract,
+    owner_launchpad: &Account,
+    creator: &Account,
+    campaign_id: &str,
+    milestones: Option<serde_json::Value>
+) -> anyhow::Result<u64> {
+    let pool: PoolMetadata = creator
+        .call(launchpad_contract.id(), "init_pool")
+        .args_json(json!({
+            "campaign_id": campaign_id,
+            "token_id": ft_contract.id(),
+            "min_multiple_pledge": 1,
+            "target_funding": "1000",
+            "milestones": milestones
+        }))
+        .deposit(INIT_POOL)
+        .transact()
+        .await?
+        .json()?;
+
+    owner_launchpad
+        .call(launchpad_contract.id(), "admin_set_status_pool_pre_funding")
+        .args_json(json!({"pool_id": pool.pool_id, "approve": true}))
+        .transact()
+        .await?
+        .into_result()?;
+
+    let time_now: u64 = launchpad_contract.call("get_current_timestamp").view().await?.json()?;
+    creator
+        .call(launchpad_contract.id(), "set_funding_pool_by_creator")
+        .args_json(json!({
+            "pool_id": pool.pool_id,
+            "time_start_pledge": time_now + 2_000_000_000,
+            "funding_duration_days": 1
+        }))
+        .transact()
+        .await?
+        .into_result()?;
+
+    sleep(Duration::from_secs(3)).await;
+    open_funding(launchpad_contract, creator, pool.pool_id).await?;
+    Ok(pool.pool_id)
+}
+
+async fn get_proposal(launchpad_contract: &Contract, pool_id: u64) -> anyhow::Result<Proposal> {
+    let proposal = launchpad_contract
+        .call("get_proposal")
+        .args_json(json!({"pool_id": pool_id}))
+        .view()
+        .await?
+        .json::<Option<Proposal>>()?
+        .unwrap();
+    Ok(proposal)
+}
+
+pub async fn test_fund_voting_pools(
+    ft_contract: &Contract,
+    launchpad_contract: &Contract,
+    owner_launchpad: &Account,
+    creator: &Account,
+    backer1: &Account,
+    backer2: &Account
+) -> anyhow::Result<()> {
+
+    // backer1 holds the majority of the voting power
+    let pool_id = open_funding_pool(ft_contract, launchpad_contract, owner_launchpad, creator, "test-voting-majority", None).await?;
+    pledge(ft_contract, launchpad_contract, backer1, pool_id, 700).await?;
+    pledge(ft_contract, launchpad_contract, backer2, pool_id, 300).await?;
+
+    // backer2 alone cannot reach the quorum
+    let pool_id = open_funding_pool(ft_contract, launchpad_contract, owner_launchpad, creator, "test-voting-quorum", None).await?;
+    pledge(ft_contract, launchpad_contract, backer1, pool_id, 900).await?;
+    pledge(ft_contract, launchpad_contract, backer2, pool_id, 100).await?;
+
+    println!("      Passed ✅ test_fund_voting_pools");
+    Ok(())
+}
+
+pub async fn test_vote_and_tally(
+    launchpad_contract: &Contract,
+    owner_launchpad: &Account,
+    creator: &Account,
+    backer1: &Account,
+    backer2: &Account
+) -> anyhow::Result<()> {
+
+    let majority_pool = pool_by_campaign(launchpad_contract, "FUNDING", "test-voting-majority").await?;
+    let quorum_pool = pool_by_campaign(launchpad_contract, "FUNDING", "test-voting-quorum").await?;
+
+    for pool_id in [majority_pool.pool_id, quorum_pool.pool_id] {
+        let pool: PoolMetadata = owner_launchpad
+            .call(launchpad_contract.id(), "check_funding_result")
+            .args_json(json!({"pool_id": pool_id, "is_waiting_funding": false}))
+            .gas(NearGas::from_tgas(100))
+            .transact()
+            .await?
+            .json()?;
+        assert_eq!(pool.status, Status::VOTING, "Funded pool should move to voting.");
+        assert_eq!(pool.total_voting_power, 1000, "Voting power should equal the pledges.");
+    }
+
+    // an outright majority decides the proposal before its deadline
+    let pool_id = majority_pool.pool_id;
+
+    let outcome = backer1
+        .call(launchpad_contract.id(), "create_proposal")
+        .args_json(json!({"pool_id": pool_id, "description": "Release the course"}))
+        .transact()
+        .await?;
+    assert!(outcome.is_failure(), "Only the creator should create a proposal.");
+
+    creator
+        .call(launchpad_contract.id(), "create_proposal")
+        .args_json(json!({"pool_id": pool_id, "description": "Release the course"}))
+        .transact()
+        .await?
+        .into_result()?;
+
+    let outcome = creator
+        .call(launchpad_contract.id(), "create_proposal")
+        .args_json(json!({"pool_id": pool_id, "description": "Release the course again"}))
+        .transact()
+        .await?;
+    assert!(outcome.is_failure(), "A pool should have one active proposal at a time.");
+
+    backer2
+        .call(launchpad_contract.id(), "vote")
+        .args_json(json!({"pool_id": pool_id, "approve": false}))
+        .transact()
+        .await?
+        .into_result()?;
+
+    let outcome = backer2
+        .call(launchpad_contract.id(), "vote")
+        .args_json(json!({"pool_id": pool_id, "approve": true}))
+        .transact()
+        .await?;
+    assert!(outcome.is_failure(), "A backer should vote only once.");
+
+    let outcome = creator
+        .call(launchpad_contract.id(), "vote")
+        .args_json(json!({"pool_id": pool_id, "approve": true}))
+        .transact()
+        .await?;
+    assert!(outcome.is_failure(), "An account without a pledge should not vote.");
+
+    let outcome = backer1
+        .call(launchpad_contract.id(), "tally_votes")
+        .args_json(json!({"pool_id": pool_id}))
+        .transact()
+        .await?;
+    assert!(outcome.is_failure(), "A minority should not decide the proposal before its deadline.");
+
+    backer1
+        .call(launchpad_contract.id(), "vote")
+        .args_json(json!({"pool_id": pool_id, "approve": true}))
+        .transact()
+        .await?
+        .into_result()?;
+
+    let proposal = get_proposal(launchpad_contract, pool_id).await?;
+    assert_eq!(proposal.approve_power, 700);
+    assert_eq!(proposal.reject_power, 300);
+
+    let pool: PoolMetadata = backer2
+        .call(launchpad_contract.id(), "tally_votes")
+        .args_json(json!({"pool_id": pool_id}))
+        .transact()
+        .await?
+        .json()?;
+    assert_eq!(pool.status, Status::SUCCESSFUL, "Approving the last milestone should settle the pool.");
+
+    let proposal = get_proposal(launchpad_contract, pool_id).await?;
+    assert_eq!(proposal.status, ProposalStatus::APPROVED);
+
+    // below the quorum the proposal expires at its deadline instead of being decided
+    let pool_id = quorum_pool.pool_id;
+    let quorum_percent: u8 = launchpad_contract.call("get_voting_quorum_percent").view().await?.json()?;
+    let voting_duration: u64 = launchpad_contract.call("get_voting_duration").view().await?.json()?;
+    assert_eq!(quorum_percent, 50, "Quorum should default to half of the voting power.");
+
+    owner_launchpad
+        .call(launchpad_contract.id(), "set_voting_config")
+        .args_json(json!({"quorum_percent": quorum_percent, "duration": 10_000_000_000u64}))
+        .transact()
+        .await?
+        .into_result()?;
+
+    creator
+        .call(launchpad_contract.id(), "create_proposal")
+        .args_json(json!({"pool_id": pool_id, "description": "Release the course"}))
+        .transact()
+        .await?
+        .into_result()?;
+
+    backer2
+        .call(launchpad_contract.id(), "vote")
+        .args_json(json!({"pool_id": pool_id, "approve": true}))
+        .transact()
+        .await?
+        .into_result()?;
+
+    let outcome = backer2
+        .call(launchpad_contract.id(), "tally_votes")
+        .args_json(json!({"pool_id": pool_id}))
+        .transact()
+        .await?;
+    assert!(outcome.is_failure(), "Proposal without a majority should wait for its deadline.");
+
+    sleep(Duration::from_secs(11)).await;
+
+    let outcome = backer1
+        .call(launchpad_contract.id(), "vote")
+        .args_json(json!({"pool_id": pool_id, "approve": false}))
+        .transact()
+        .await?;
+    assert!(outcome.is_failure(), "Votes after the deadline should be rejected.");
+
+    let pool: PoolMetadata = backer2
+        .call(launchpad_contract.id(), "tally_votes")
+        .args_json(json!({"pool_id": pool_id}))
+        .transact()
+        .await?
+        .json()?;
+    assert_eq!(pool.status, Status::VOTING, "Pool should keep voting when the quorum is missed.");
+
+    let proposal = get_proposal(launchpad_contract, pool_id).await?;
+    assert_eq!(proposal.status, ProposalStatus::EXPIRED, "Approval below the quorum should expire.");
+    assert_eq!(proposal.approve_power, 100);
+
+    let milestones = get_milestones(launchpad_contract, pool_id).await?;
+    assert_eq!(milestones[0].status, MilestoneStatus::PENDING, "Expired proposal should leave its milestone pending.");
+
+    // the funds are not stuck, the next proposal decides them
+    creator
+        .call(launchpad_contract.id(), "create_proposal")
+        .args_json(json!({"pool_id": pool_id, "description": "Release the course"}))
+        .transact()
+        .await?
+        .into_result()?;
+
+    backer1
+        .call(launchpad_contract.id(), "vote")
+        .args_json(json!({"pool_id": pool_id, "approve": false}))
+        .transact()
+        .await?
+        .into_result()?;
+
+    let pool: PoolMetadata = backer2
+        .call(launchpad_contract.id(), "tally_votes")
+        .args_json(json!({"pool_id": pool_id}))
+        .transact()
+        .await?
+        .json()?;
+    assert_eq!(pool.status, Status::REFUNDED, "Rejecting the new proposal should refund the backers.");
+
+    for backer in [backer1, backer2] {
+        backer
+            .call(launchpad_contract.id(), "claim_refund")
+            .args_json(json!({"pool_id": pool_id}))
+            .gas(NearGas::from_tgas(30))
+            .transact()
+            .await?
+            .into_result()?;
+    }
+
+    let pool: PoolMetadata = launchpad_contract
+        .call("get_detail_pool")
+        .args_json(json!({"pool_id": pool_id}))
+        .view()
+        .await?
+        .json::<Option<PoolMetadata>>()?
+        .unwrap();
+    assert_eq!(pool.total_balance, 0, "Backers should get the whole pool back.");
+
+    owner_launchpad
+        .call(launchpad_contract.id(), "set_voting_config")
+        .args_json(json!({"quorum_percent": quorum_percent, "duration": voting_duration}))
+        .transact()
+        .await?
+        .into_result()?;
+
+    println!("      Passed ✅ test_vote_and_tally");
+    Ok(())
+}
+
+async fn get_milestones(launchpad_contract: &Contract, pool_id: u64) -> anyhow::Result<Vec<Milestone>> {
+    let milestones = launchpad_contract
+        .call("get_milestones")
+        .args_json(json!({"pool_id": pool_id}))
+        .view()
+        .await?
+        .json::<Option<Vec<Milestone>>>()?
+        .unwrap();
+    Ok(milestones)
+}
+
+// creator proposes the current milestone and backer1, who holds the majority, decides it
+async fn decide_milestone(launchpad_contract: &Contract, creator: &Account, voter: &Account, pool_id: u64, approve: bool) -> anyhow::Result<PoolMetadata> {
+    creator
+        .call(launchpad_contract.id(), "create_proposal")
+        .args_json(json!({"pool_id": pool_id, "description": "Release the next milestone"}))
+        .transact()
+        .await?
+        .into_result()?;
+
+    voter
+        .call(launchpad_contract.id(), "vote")
+        .args_json(json!({"pool_id": pool_id, "approve": approve}))
+        .transact()
+        .await?
+        .into_result()?;
+
+    let pool: PoolMetadata = voter
+        .call(launchpad_contract.id(), "tally_votes")
+        .args_json(json!({"pool_id": pool_id}))
+        .transact()
+        .await?
+        .json()?;
+    Ok(pool)
+}
+
+pub async fn test_fund_milestone_pool(
+    ft_contract: &Contract,
+    launchpad_contract: &Contract,
+    owner_launchpad: &Account,
+    creator: &Account,
+    backer1: &Account,
+    backer2: &Account
+) -> anyhow::Result<()> {
+    const DAY: u64 = 24 * 60 * 60 * 1_000_000_000;
+
+    // milestone deadlines must fall after the funding window
+    let time_now: u64 = launchpad_contract.call("get_current_timestamp").view().await?.json()?;
+    let milestones = json!([
+        {"percent": 40, "deadline": time_now + 10 * DAY},
+        {"percent": 60, "deadline": time_now + 20 * DAY}
+    ]);
+
+    let pool_id = open_funding_pool(ft_contract, launchpad_contract, owner_launchpad, creator, "test-milestones", Some(milestones)).await?;
+    pledge(ft_contract, launchpad_contract, backer1, pool_id, 600).await?;
+    pledge(ft_contract, launchpad_contract, backer2, pool_id, 400).await?;
+
+    println!("      Passed ✅ test_fund_milestone_pool");
+    Ok(())
+}
+
+pub async fn test_milestone_tranches(
+    ft_contract: &Contract,
+    launchpad_contract: &Contract,
+    owner_launchpad: &Account,
+    creator: &Account,
+    backer1: &Account,
+    backer2: &Account
+) -> anyhow::Result<()> {
+
+    let pool = pool_by_campaign(launchpad_contract, "FUNDING", "test-milestones").await?;
+    let pool_id = pool.pool_id;
+
+    owner_launchpad
+        .call(launchpad_contract.id(), "check_funding_result")
+        .args_json(json!({"pool_id": pool_id, "is_waiting_funding": false}))
+        .gas(NearGas::from_tgas(100))
+        .transact()
+        .await?
+        .into_result()?;
+
+    let outcome = owner_launchpad
+        .call(launchpad_contract.id(), "withdraw_to_creator")
+        .args_json(json!({"pool_id": pool_id}))
+        .gas(DEFAULT_GAS)
+        .transact()
+        .await?;
+    assert!(outcome.is_failure(), "Nothing should be released before a milestone is approved.");
+
+    let pool = decide_milestone(launchpad_contract, creator, backer1, pool_id, true).await?;
+    assert_eq!(pool.status, Status::VOTING, "Pool should keep voting until its last milestone.");
+
+    let milestones = get_milestones(launchpad_contract, pool_id).await?;
+    assert_eq!(milestones[0].status, MilestoneStatus::APPROVED);
+    assert_eq!(milestones[1].status, MilestoneStatus::PENDING);
+
+    // the creator has no storage in the token yet, so the transfer is rejected and the tranche restored
+    owner_launchpad
+        .call(launchpad_contract.id(), "withdraw_to_creator")
+        .args_json(json!({"pool_id": pool_id}))
+        .gas(DEFAULT_GAS)
+        .transact()
+        .await?
+        .into_result()?;
+
+    let milestones = get_milestones(launchpad_contract, pool_id).await?;
+    assert_eq!(milestones[0].status, MilestoneStatus::APPROVED, "Rejected tranche should be released again.");
+    assert_eq!(milestones[0].amount_released, 0);
+
+    let pool: PoolMetadata = launchpad_contract
+        .call("get_detail_pool")
+        .args_json(json!({"pool_id": pool_id}))
+        .view()
+        .await?
+        .json::<Option<PoolMetadata>>()?
+        .unwrap();
+    assert_eq!(pool.total_balance, 1000, "Rejected tranche should go back to the pool.");
+    assert_eq!(pool.tokens[0].balance, 1000);
+
+    storage_deposit(creator, ft_contract, creator).await?;
+
+    // the first tranche is 40% of the raise
+    let creator_before = ft_balance(ft_contract, creator).await?;
+    owner_launchpad
+        .call(launchpad_contract.id(), "withdraw_to_creator")
+        .args_json(json!({"pool_id": pool_id}))
+        .gas(DEFAULT_GAS)
+        .transact()
+        .await?
+        .into_result()?;
+    assert_eq!(ft_balance(ft_contract, creator).await? - creator_before, 400, "Creator should receive the first tranche.");
+
+    let outcome = owner_launchpad
+        .call(launchpad_contract.id(), "withdraw_to_creator")
+        .args_json(json!({"pool_id": pool_id}))
+        .gas(DEFAULT_GAS)
+        .transact()
+        .await?;
+    assert!(outcome.is_failure(), "A released tranche should not be paid twice.");
+
+    let milestones = get_milestones(launchpad_contract, pool_id).await?;
+    assert_eq!(milestones[0].status, MilestoneStatus::RELEASED);
+    assert_eq!(milestones[0].amount_released, 400);
+
+    let pool: PoolMetadata = launchpad_contract
+        .call("get_detail_pool")
+        .args_json(json!({"pool_id": pool_id}))
+        .view()
+        .await?
+        .json::<Option<PoolMetadata>>()?
+        .unwrap();
+    assert_eq!(pool.total_balance, 600, "Unreleased tranches should stay in the pool.");
+
+    // rejecting the second milestone refunds what was not released
+    let pool = decide_milestone(launchpad_contract, creator, backer1, pool_id, false).await?;
+    assert_eq!(pool.status, Status::REFUNDED, "Failed milestone should refund the pool.");
+
+    let milestones = get_milestones(launchpad_contract, pool_id).await?;
+    assert_eq!(milestones[1].status, MilestoneStatus::FAILED);
+
+    let outcome = owner_launchpad
+        .call(launchpad_contract.id(), "withdraw_to_creator")
+        .args_json(json!({"pool_id": pool_id}))
+        .gas(DEFAULT_GAS)
+        .transact()
+        .await?;
+    assert!(outcome.is_failure(), "Failed milestone should not be released.");
+
+    for (backer, expected) in [(backer1, 360), (backer2, 240)] {
+        let balance_before = ft_balance(ft_contract, backer).await?;
+        backer
+            .call(launchpad_contract.id(), "claim_refund")
+            .args_json(json!({"pool_id": pool_id}))
+            .gas(DEFAULT_GAS)
+            .transact()
+            .await?
+            .into_result()?;
+        assert_eq!(ft_balance(ft_contract, backer).await? - balance_before, expected, "Backer should get its share of the remaining balance.");
+    }
+
+    let pool: PoolMetadata = launchpad_contract
+        .call("get_detail_pool")
+        .args_json(json!({"pool_id": pool_id}))
+        .view()
+        .await?
+        .json::<Option<PoolMetadata>>()?
+        .unwrap();
+    assert_eq!(pool.total_balance, 0, "Refunds should empty the pool.");
+
+    println!("      Passed ✅ test_milestone_tranches");
+    Ok(())
+}
+
+pub async fn test_fund_rejecting_receiver(
+    ft_contract: &Contract,
+    launchpad_contract: &Contract,
+    owner_launchpad: &Account,
+    creator: &Account,
+    backer1: &Account
+) -> anyhow::Result<()> {
+
+    // a backer holding nothing but its pledge, so it can leave the token before the refund;
+    // it signs with backer1's key so the refund test can act as it
+    let receiver = backer1
+        .create_subaccount("receiver")
+        .keys(backer1.secret_key().clone())
+        .initial_balance(NearToken::from_near(2))
+        .transact()
+        .await?
+        .into_result()?;
+    storage_deposit(backer1, ft_contract, &receiver).await?;
+
+    backer1
+        .call(ft_contract.id(), "ft_transfer")
+        .args_json(json!({"receiver_id": receiver.id(), "amount": U128(300)}))
+        .deposit(DEFAULT_DEPOSIT)
+        .transact()
+        .await?
+        .into_result()?;
+
+    let pool_id = open_funding_pool(ft_contract, launchpad_contract, owner_launchpad, creator, "test-rejecting-receiver", None).await?;
+    pledge(ft_contract, launchpad_contract, &receiver, pool_id, 300).await?;
+
+    println!("      Passed ✅ test_fund_rejecting_receiver");
+    Ok(())
+}
+
+pub async fn test_refund_to_rejecting_receiver(
+    worker: &Worker<Sandbox>,
+    ft_contract: &Contract,
+    launchpad_contract: &Contract,
+    owner_launchpad: &Account,
+    backer1: &Account
+) -> anyhow::Result<()> {
+
+    let pool = pool_by_campaign(launchpad_contract, "FUNDING", "test-rejecting-receiver").await?;
+    let pool_id = pool.pool_id;
+
+    let pool: PoolMetadata = owner_launchpad
+        .call(launchpad_contract.id(), "check_funding_result")
+        .args_json(json!({"pool_id": pool_id, "is_waiting_funding": false}))
+        .gas(NearGas::from_tgas(100))
+        .transact()
+        .await?
+        .json()?;
+    assert_eq!(pool.status, Status::REFUNDED, "Pool below its target should be refunded.");
+
+    let receiver_id: near_workspaces::AccountId = format!("receiver.{}", backer1.id()).parse()?;
+    let receiver = Account::from_secret_key(receiver_id, backer1.secret_key().clone(), worker);
+
+    // the receiver leaves the token, so the refund transfer is rejected
+    receiver
+        .call(ft_contract.id(), "storage_unregister")
+        .args_json(json!({}))
+        .deposit(DEFAULT_DEPOSIT)
+        .transact()
+        .await?
+        .into_result()?;
+
+    receiver
+        .call(launchpad_contract.id(), "claim_refund")
+        .args_json(json!({"pool_id": pool_id}))
+        .gas(DEFAULT_GAS)
+        .transact()
+        .await?
+        .into_result()?;
+
+    let pool_after: PoolMetadata = launchpad_contract
+        .call("get_detail_pool")
+        .args_json(json!({"pool_id": pool_id}))
+        .view()
+        .await?
+        .json::<Option<PoolMetadata>>()?
+        .unwrap();
+    assert_eq!(pool_after.total_balance, pool.total_balance, "Rejected refund should go back to the pool.");
+    assert_eq!(pool_after.total_voting_power, pool.total_voting_power, "Rejected refund should restore the voting power.");
+    assert_eq!(pool_after.tokens[0].balance, 300);
+    assert_eq!(pool_after.tokens[0].pledged, 300);
+
+    let records: Vec<UserRecordDetail> = launchpad_contract
+        .call("get_user_records_by_pool_id")
+        .args_json(json!({"pool_id": pool_id}))
+        .view()
+        .await?
+        .json::<Option<Vec<UserRecordDetail>>>()?
+        .unwrap();
+    let record = &records.iter().find(|detail| detail.user_id.as_str() == receiver.id().as_str()).unwrap().record;
+    assert_eq!(record.amount, 300, "Rejected refund should restore the pledge.");
+    assert_eq!(record.voting_power, 300, "Rejected refund should restore the voting power.");
+
+    // once the receiver accepts the token again the refund goes through
+    storage_deposit(backer1, ft_contract, &receiver).await?;
+    receiver
+        .call(launchpad_contract.id(), "claim_refund")
+        .args_json(json!({"pool_id": pool_id}))
+        .gas(DEFAULT_GAS)
+        .transact()
+        .await?
+        .into_result()?;
+    assert_eq!(ft_balance(ft_contract, &receiver).await?, 300, "Receiver should get its pledge back.");
+
+    println!("      Passed ✅ test_refund_to_rejecting_receiver");
+    Ok(())
+}
+
+// NEP-297 events of `standard` among the logs, parsed from after the `EVENT_JSON:` prefix
+fn events<S: AsRef<str>>(logs: &[S], standard: &str) -> Vec<serde_json::Value> {
+    logs.iter()
+        .filter_map(|log| log.as_ref().strip_prefix("EVENT_JSON:"))
+        .map(|event| serde_json::from_str::<serde_json::Value>(event).unwrap())
+        .filter(|event| event["standard"] == standard)
+        .collect()
+}
+
+pub async fn test_event_payloads(
+    worker: &Worker<Sandbox>,
+    ft_contract: &Contract,
+    launchpad_contract: &Contract,
+    owner_launchpad: &Account,
+    creator: &Account,
+    backer1: &Account,
+    backer2: &Account
+) -> anyhow::Result<()> {
+
+    // launchpad
+    let outcome = creator
+        .call(launchpad_contract.id(), "init_pool")
+        .args_json(json!({
+            "campaign_id": "test-events",
+            "token_id": ft_contract.id(),
+            "min_multiple_pledge": 1,
+            "target_funding": "1000"
+        }))
+        .deposit(INIT_POOL)
+        .transact()
+        .await?;
+    let logs = events(&outcome.logs(), "openedu_launchpad");
+    let pool: PoolMetadata = outcome.json()?;
+    assert_eq!(logs, vec![json!({
+        "standard": "openedu_launchpad",
+        "version": "1.0.0",
+        "event": "pool_created",
+        "data": [{
+            "pool_id": pool.pool_id,
+            "campaign_id": "test-events",
+            "creator_id": creator.id(),
+            "token_id": ft_contract.id(),
+            "target_funding": "1000",
+            "staking_amount": INIT_POOL.as_yoctonear().to_string()
+        }]
+    })]);
+
+    let outcome = owner_launchpad
+        .call(launchpad_contract.id(), "admin_set_status_pool_pre_funding")
+        .args_json(json!({"pool_id": pool.pool_id, "approve": true}))
+        .transact()
+        .await?;
+    assert_eq!(events(&outcome.logs(), "openedu_launchpad"), vec![json!({
+        "standard": "openedu_launchpad",
+        "version": "1.0.0",
+        "event": "pool_status_changed",
+        "data": [{"pool_id": pool.pool_id, "old_status": "INIT", "new_status": "APPROVED"}]
+    })]);
+
+    let time_now: u64 = launchpad_contract.call("get_current_timestamp").view().await?.json()?;
+    creator
+        .call(launchpad_contract.id(), "set_funding_pool_by_creator")
+        .args_json(json!({
+            "pool_id": pool.pool_id,
+            "time_start_pledge": time_now + 2_000_000_000,
+            "funding_duration_days": 1
+        }))
+        .transact()
+        .await?
+        .into_result()?;
+    sleep(Duration::from_secs(3)).await;
+
+    let outcome = creator
+        .call(launchpad_contract.id(), "advance_pool")
+        .args_json(json!({"pool_id": pool.pool_id}))
+        .gas(NearGas::from_tgas(100))
+        .transact()
+        .await?;
+    assert_eq!(events(&outcome.logs(), "openedu_launchpad")[0], json!({
+        "standard": "openedu_launchpad",
+        "version": "1.0.0",
+        "event": "pool_status_changed",
+        "data": [{"pool_id": pool.pool_id, "old_status": "APPROVED", "new_status": "FUNDING"}]
+    }));
+
+    let logs = pledge_with_msg(ft_contract, launchpad_contract, backer1, &pool.pool_id.to_string(), 25).await?;
+    assert_eq!(events(&logs, "openedu_launchpad"), vec![json!({
+        "standard": "openedu_launchpad",
+        "version": "1.0.0",
+        "event": "pledge",
+        "data": [{"pool_id": pool.pool_id, "account_id": backer1.id(), "token_id": ft_contract.id(), "amount": "25"}]
+    })]);
+
+    // payment
+    let payment_wasm = std::fs::read(PAYMENT_WASM_FILEPATH)?;
+    let payment_contract = worker.dev_deploy(&payment_wasm).await?;
+    payment_contract
+        .call("new")
+        .args_json(json!({"owner_id": creator.id()}))
+        .transact()
+        .await?
+        .into_result()?;
+    creator
+        .call(payment_contract.id(), "add_token")
+        .args_json(json!({"token_id": ft_contract.id()}))
+        .gas(DEFAULT_GAS)
+        .transact()
+        .await?
+        .into_result()?;
+
+    let outcome = backer1
+        .call(ft_contract.id(), "ft_transfer_call")
+        .args_json(json!({
+            "receiver_id": payment_contract.id(),
+            "amount": U128(40),
+            "msg": json!([{"user_id": backer2.id(), "amount": 40}]).to_string()
+        }))
+        .deposit(DEFAULT_DEPOSIT)
+        .gas(DEFAULT_GAS)
+        .transact()
+        .await?
+        .into_result()?;
+    assert_eq!(events(&outcome.logs(), "openedu_payment"), vec![json!({
+        "standard": "openedu_payment",
+        "version": "1.0.0",
+        "event": "payment_deposit",
+        "data": [{"sender_id": backer1.id(), "user_id": backer2.id(), "token_id": ft_contract.id(), "amount": "40"}]
+    })]);
+
+    let outcome = backer2
+        .call(payment_contract.id(), "claim")
+        .args_json(json!({"token_id": ft_contract.id()}))
+        .gas(DEFAULT_GAS)
+        .transact()
+        .await?
+        .into_result()?;
+    assert_eq!(events(&outcome.logs(), "openedu_payment"), vec![json!({
+        "standard": "openedu_payment",
+        "version": "1.0.0",
+        "event": "payment_claim",
+        "data": [{"user_id": backer2.id(), "token_id": ft_contract.id(), "amount": "40"}]
+    })]);
+
+    // NFT certificates
+    let nft_wasm = std::fs::read(NFT_WASM_FILEPATH)?;
+    let nft_contract = worker.dev_deploy(&nft_wasm).await?;
+    nft_contract
+        .call("new_default_meta")
+        .args_json(json!({"owner_id": creator.id(), "admin_pub_key": "admin-pub-key"}))
+        .transact()
+        .await?
+        .into_result()?;
+
+    let outcome = backer1
+        .call(nft_contract.id(), "deposit_sponsor")
+        .args_json(json!({"course_id": "course-events"}))
+        .deposit(NearToken::from_near(1))
+        .transact()
+        .await?
+        .into_result()?;
+    assert_eq!(events(&outcome.logs(), "openedu_certificate"), vec![json!({
+        "standard": "openedu_certificate",
+        "version": "1.0.0",
+        "event": "sponsor_deposit",
+        "data": [{"sponsor_id": backer1.id(), "course_id": "course-events", "amount": NearToken::from_near(1).as_yoctonear().to_string()}]
+    })]);
+
+    let outcome = creator
+        .call(nft_contract.id(), "nft_mint")
+        .args_json(json!({
+            "token_id": "certificate-events",
+            "receiver_id": backer2.id(),
+            "token_metadata": {"title": "Course certificate"}
+        }))
+        .deposit(NearToken::from_millinear(100))
+        .transact()
+        .await?
+        .into_result()?;
+    assert_eq!(events(&outcome.logs(), "openedu_certificate"), vec![json!({
+        "standard": "openedu_certificate",
+        "version": "1.0.0",
+        "event": "certificate_mint",
+        "data": [{"token_id": "certificate-events", "receiver_id": backer2.id()}]
+    })]);
+
+    println!("      Passed ✅ test_event_payloads");
+    Ok(())
+}
@@ -0,0 +1,682 @@
+use near_gas::NearGas;
+use near_sdk::borsh::{self, BorshSerialize};
+use near_token::NearToken;
+use serde_json::json;
+#[allow(dead_code)]
+mod helpers;
+use near_workspaces::{network::Sandbox, Account, Contract, Worker};
+
+use helpers::{BackerPoolDetail, PoolMetadata, Status, UserRecordDetail};
+
+const LAUNCHPAD_WASM_FILEPATH: &str = "../res/launchpad.wasm";
+const PAYMENT_WASM_FILEPATH: &str = "../res/payment.wasm";
+const NFT_WASM_FILEPATH: &str = "../res/nft_25519.wasm";
+
+const INITIAL_NEAR: NearToken = NearToken::from_near(30);
+const LAUNCHPAD_STATE_VERSION: u8 = 15;
+const PAYMENT_STATE_VERSION: u8 = 3;
+const NFT_STATE_VERSION: u8 = 3;
+
+/* //////////////////////////////////////////////////////////////
+                    RAW LAYOUT OF near_sdk COLLECTIONS
+////////////////////////////////////////////////////////////// */
+// mirrors of near_sdk::collections as they are stored, used to write old-layout state directly
+
+#[derive(BorshSerialize)]
+struct RawVector {
+    len: u64,
+    prefix: Vec<u8>,
+}
+
+#[derive(BorshSerialize)]
+struct RawLookupMap {
+    prefix: Vec<u8>,
+}
+
+#[derive(BorshSerialize)]
+struct RawUnorderedSet {
+    element_index_prefix: Vec<u8>,
+    elements: RawVector,
+}
+
+#[derive(BorshSerialize)]
+struct RawUnorderedMap {
+    key_index_prefix: Vec<u8>,
+    keys: RawVector,
+    values: RawVector,
+}
+
+// raw key and value pairs to write into the contract storage
+type StorageEntries = Vec<(Vec<u8>, Vec<u8>)>;
+
+fn with_suffix(prefix: &[u8], suffix: &[u8]) -> Vec<u8> {
+    [prefix, suffix].concat()
+}
+
+// storage entries of an UnorderedSet holding `elements`
+fn unordered_set_entries<T: BorshSerialize>(prefix: &[u8], elements: &[T]) -> (RawUnorderedSet, StorageEntries) {
+    let element_index_prefix = with_suffix(prefix, b"i");
+    let elements_prefix = with_suffix(prefix, b"e");
+
+    let mut entries = Vec::new();
+    for (index, element) in elements.iter().enumerate() {
+        let element = element.try_to_vec().unwrap();
+        let index = index as u64;
+        entries.push((with_suffix(&element_index_prefix, &element), index.try_to_vec().unwrap()));
+        entries.push((with_suffix(&elements_prefix, &index.to_le_bytes()), element));
+    }
+
+    let set = RawUnorderedSet {
+        element_index_prefix,
+        elements: RawVector { len: elements.len() as u64, prefix: elements_prefix },
+    };
+    (set, entries)
+}
+
+// storage entries of an UnorderedMap holding `items`
+fn unordered_map_entries<K: BorshSerialize, V: BorshSerialize>(prefix: &[u8], items: &[(K, V)]) -> (RawUnorderedMap, StorageEntries) {
+    let key_index_prefix = with_suffix(prefix, b"i");
+    let keys_prefix = with_suffix(prefix, b"k");
+    let values_prefix = with_suffix(prefix, b"v");
+
+    let mut entries = Vec::new();
+    for (index, (key, value)) in items.iter().enumerate() {
+        let key = key.try_to_vec().unwrap();
+        let index = index as u64;
+        entries.push((with_suffix(&key_index_prefix, &key), index.try_to_vec().unwrap()));
+        entries.push((with_suffix(&keys_prefix, &index.to_le_bytes()), key));
+        entries.push((with_suffix(&values_prefix, &index.to_le_bytes()), value.try_to_vec().unwrap()));
+    }
+
+    let map = RawUnorderedMap {
+        key_index_prefix,
+        keys: RawVector { len: items.len() as u64, prefix: keys_prefix },
+        values: RawVector { len: items.len() as u64, prefix: values_prefix },
+    };
+    (map, entries)
+}
+
+async fn patch_entries(worker: &Worker<Sandbox>, contract: &Contract, entries: StorageEntries) -> anyhow::Result<()> {
+    for (key, value) in entries {
+        worker.patch_state(contract.id(), &key, &value).await?;
+    }
+    Ok(())
+}
+
+/* //////////////////////////////////////////////////////////////
+                    LAUNCHPAD STATE VERSION 0
+////////////////////////////////////////////////////////////// */
+
+#[derive(BorshSerialize)]
+struct LaunchpadV0 {
+    owner_id: String,
+    all_pool_id: RawUnorderedSet,
+    list_assets: Vec<AssetsV0>,
+    pool_metadata_by_id: RawLookupMap,
+    min_staking_amount: u128,
+    refund_percent: u8,
+    user_records: RawLookupMap,
+}
+
+#[derive(BorshSerialize)]
+struct AssetsV0 {
+    token_id: String,
+    balances: u128,
+}
+
+#[derive(BorshSerialize)]
+struct PoolMetadataV0 {
+    pool_id: u64,
+    campaign_id: String,
+    creator_id: String,
+    staking_amount: u128,
+    status: u8, // index of the Status variant
+    token_id: String,
+    total_balance: u128,
+    target_funding: u128,
+    time_init: u64,
+    time_start_pledge: u64,
+    time_end_pledge: u64,
+    funding_duration_days: u64,
+    min_multiple_pledge: u128,
+}
+
+#[derive(BorshSerialize)]
+struct UserTokenDepositRecordV0 {
+    amount: u128,
+    voting_power: f64,
+}
+
+// record layout since shares became integer voting power
+#[derive(BorshSerialize)]
+struct UserTokenDepositRecordV1 {
+    amount: u128,
+    voting_power: u128,
+}
+
+fn user_records_prefix(pool_id: u64) -> Vec<u8> {
+    with_suffix(b"user", &pool_id.to_le_bytes())
+}
+
+#[tokio::main]
+async fn main() -> anyhow::Result<()> {
+    let worker = near_workspaces::sandbox().await?;
+    let owner = worker.root_account().unwrap();
+
+    let admin = owner
+        .create_subaccount("admin")
+        .initial_balance(INITIAL_NEAR)
+        .transact()
+        .await?
+        .into_result()?;
+
+    let backer1 = owner
+        .create_subaccount("backer1")
+        .initial_balance(INITIAL_NEAR)
+        .transact()
+        .await?
+        .into_result()?;
+
+    let backer2 = owner
+        .create_subaccount("backer2")
+        .initial_balance(INITIAL_NEAR)
+        .transact()
+        .await?
+        .into_result()?;
+
+    // begin tests
+    test_launchpad_migrate_from_v0(&worker, &admin, &backer1, &backer2).await?;
+    test_launchpad_upgrade_keeps_state(&worker, &admin, &backer1).await?;
+    test_launchpad_migrate_pools_from_v3(&worker, &admin).await?;
+    test_payment_migrate_from_v0(&worker, &admin, &backer1).await?;
+    test_nft_upgrade_keeps_state(&worker, &admin, &backer1).await?;
+    Ok(())
+}
+
+pub async fn test_launchpad_migrate_from_v0(
+    worker: &Worker<Sandbox>,
+    admin: &Account,
+    backer1: &Account,
+    backer2: &Account
+) -> anyhow::Result<()> {
+    let launchpad_wasm = std::fs::read(LAUNCHPAD_WASM_FILEPATH)?;
+    let launchpad_contract = worker.dev_deploy(&launchpad_wasm).await?;
+
+    // pool 1 ended funding with f64 shares, pool 2 is still funding so nobody has a share yet,
+    // pool 3 reached voting before its shares were computed so it has no voting power,
+    // pool 4 is funding and holds 30 more than its backer pledged, left behind by a withdrawn pledge
+    let pool = |pool_id: u64, status: u8, total_balance: u128| PoolMetadataV0 {
+        pool_id,
+        campaign_id: format!("campaign-{}", pool_id),
+        creator_id: admin.id().to_string(),
+        staking_amount: 1_000_000_000_000_000_000_000,
+        status,
+        token_id: "ft.test.near".to_string(),
+        total_balance,
+        target_funding: 400,
+        time_init: 1,
+        time_start_pledge: 2,
+        time_end_pledge: 3,
+        funding_duration_days: 1,
+        min_multiple_pledge: 1,
+    };
+    let pools = vec![pool(1, 8, 400), pool(2, 2, 50), pool(3, 8, 200), pool(4, 2, 130)];
+    let records = vec![
+        (1u64, vec![
+            (backer1.id().to_string(), UserTokenDepositRecordV0 { amount: 100, voting_power: 25.0 }),
+            (backer2.id().to_string(), UserTokenDepositRecordV0 { amount: 300, voting_power: 75.0 }),
+        ]),
+        (2u64, vec![
+            (backer1.id().to_string(), UserTokenDepositRecordV0 { amount: 50, voting_power: 0.0 }),
+        ]),
+        (3u64, vec![
+            (backer1.id().to_string(), UserTokenDepositRecordV0 { amount: 200, voting_power: 0.0 }),
+        ]),
+        (4u64, vec![
+            (backer2.id().to_string(), UserTokenDepositRecordV0 { amount: 100, voting_power: 0.0 }),
+        ]),
+    ];
+
+    let pool_ids: Vec<u64> = pools.iter().map(|pool| pool.pool_id).collect();
+    let (all_pool_id, mut entries) = unordered_set_entries(&[0], &pool_ids);
+
+    for pool in pools {
+        entries.push((with_suffix(&[1], &pool.pool_id.try_to_vec()?), pool.try_to_vec()?));
+    }
+
+    for (pool_id, items) in records {
+        let (user_records, user_entries) = unordered_map_entries(&user_records_prefix(pool_id), &items);
+        entries.push((with_suffix(&[2], &pool_id.try_to_vec()?), user_records.try_to_vec()?));
+        entries.extend(user_entries);
+    }
+
+    let state = LaunchpadV0 {
+        owner_id: admin.id().to_string(),
+        all_pool_id,
+        list_assets: vec![AssetsV0 { token_id: "ft.test.near".to_string(), balances: 0 }],
+        pool_metadata_by_id: RawLookupMap { prefix: vec![1] },
+        min_staking_amount: 1_000_000_000_000_000_000_000,
+        refund_percent: 10,
+        user_records: RawLookupMap { prefix: vec![2] },
+    };
+    entries.push((b"STATE".to_vec(), state.try_to_vec()?));
+    patch_entries(worker, &launchpad_contract, entries).await?;
+
+    // migrate is private, the contract calls it on itself
+    launchpad_contract
+        .call("migrate")
+        .args_json(json!({}))
+        .gas(NearGas::from_tgas(300))
+        .transact()
+        .await?
+        .into_result()?;
+
+    let version: u8 = launchpad_contract.call("get_state_version").view().await?.json()?;
+    assert_eq!(version, LAUNCHPAD_STATE_VERSION, "State should be tagged with the current version.");
+
+    let refund_percent: u8 = launchpad_contract.call("get_refund_reject_pool").view().await?.json()?;
+    assert_eq!(refund_percent, 10, "Config should survive the migration.");
+
+    let pool1: PoolMetadata = launchpad_contract
+        .call("get_detail_pool")
+        .args_json(json!({"pool_id": 1}))
+        .view()
+        .await?
+        .json::<Option<PoolMetadata>>()?
+        .unwrap();
+
+    assert_eq!(pool1.status, Status::VOTING, "Pool status should be kept.");
+    assert_eq!(pool1.total_balance, 400, "Pool balance should be kept.");
+    assert_eq!(pool1.total_voting_power, 400, "Shares should become integer voting power.");
+    assert_eq!(pool1.tokens[0].pledged, 400, "Outstanding pledges should be the unclaimed pledges.");
+
+    let records1: Vec<UserRecordDetail> = launchpad_contract
+        .call("get_user_records_by_pool_id")
+        .args_json(json!({"pool_id": 1}))
+        .view()
+        .await?
+        .json::<Option<Vec<UserRecordDetail>>>()?
+        .unwrap();
+
+    for detail in records1 {
+        assert_eq!(detail.record.voting_power, detail.record.amount, "Voting power should equal the pledge.");
+    }
+
+    let pool2: PoolMetadata = launchpad_contract
+        .call("get_detail_pool")
+        .args_json(json!({"pool_id": 2}))
+        .view()
+        .await?
+        .json::<Option<PoolMetadata>>()?
+        .unwrap();
+
+    assert_eq!(pool2.status, Status::FUNDING, "Pool status should be kept.");
+    assert_eq!(pool2.total_voting_power, 0, "Funding pool should have no voting power yet.");
+    assert_eq!(pool2.tokens[0].pledged, 50, "Outstanding pledges should be the backers' pledges.");
+
+    let pool3: PoolMetadata = launchpad_contract
+        .call("get_detail_pool")
+        .args_json(json!({"pool_id": 3}))
+        .view()
+        .await?
+        .json::<Option<PoolMetadata>>()?
+        .unwrap();
+
+    assert_eq!(pool3.status, Status::VOTING, "Pool status should be kept.");
+    assert_eq!(pool3.total_voting_power, 0, "Pool without shares should have no voting power.");
+
+    // nobody could vote, so a proposal would otherwise be rejected at tally and slash the creator
+    let outcome = admin
+        .call(launchpad_contract.id(), "create_proposal")
+        .args_json(json!({"pool_id": 3, "description": "Release the first milestone"}))
+        .transact()
+        .await?;
+    assert!(outcome.is_failure(), "Proposal on a pool without voting power should be refused.");
+
+    let pool4: PoolMetadata = launchpad_contract
+        .call("get_detail_pool")
+        .args_json(json!({"pool_id": 4}))
+        .view()
+        .await?
+        .json::<Option<PoolMetadata>>()?
+        .unwrap();
+
+    assert_eq!((pool4.total_balance, pool4.tokens[0].balance), (130, 130), "Pool balance should be kept.");
+    assert_eq!(pool4.tokens[0].pledged, 100, "What was never pledged should not count as outstanding pledges.");
+
+    let backed_pools: Vec<BackerPoolDetail> = launchpad_contract
+        .call("get_pools_by_backer")
+        .args_json(json!({"account_id": backer1.id()}))
+        .view()
+        .await?
+        .json::<Option<Vec<BackerPoolDetail>>>()?
+        .unwrap();
+
+    assert_eq!(backed_pools.len(), 3, "Backer index should be rebuilt from the old records.");
+
+    // running migrate again on the current version leaves the state as it is
+    launchpad_contract
+        .call("migrate")
+        .args_json(json!({}))
+        .gas(NearGas::from_tgas(300))
+        .transact()
+        .await?
+        .into_result()?;
+
+    let number_of_pools: u64 = launchpad_contract.call("get_number_of_pools").view().await?.json()?;
+    assert_eq!(number_of_pools, 4, "Re-running migrate should keep every pool.");
+
+    println!("      Passed ✅ test_launchpad_migrate_from_v0");
+    Ok(())
+}
+
+pub async fn test_launchpad_upgrade_keeps_state(
+    worker: &Worker<Sandbox>,
+    admin: &Account,
+    user: &Account
+) -> anyhow::Result<()> {
+    let launchpad_wasm = std::fs::read(LAUNCHPAD_WASM_FILEPATH)?;
+    let launchpad_contract = worker.dev_deploy(&launchpad_wasm).await?;
+
+    admin
+        .call(launchpad_contract.id(), "init")
+        .args_json(json!({}))
+        .transact()
+        .await?
+        .into_result()?;
+
+    admin
+        .call(launchpad_contract.id(), "add_token")
+        .args_json(json!({"token_id": "ft.test.near"}))
+        .transact()
+        .await?
+        .into_result()?;
+
+    let outcome = user
+        .call(launchpad_contract.id(), "upgrade")
+        .args(launchpad_wasm.clone())
+        .gas(NearGas::from_tgas(300))
+        .transact()
+        .await?;
+    assert!(outcome.is_failure(), "Only admin should be able to upgrade.");
+
+    admin
+        .call(launchpad_contract.id(), "upgrade")
+        .args(launchpad_wasm)
+        .gas(NearGas::from_tgas(300))
+        .transact()
+        .await?
+        .into_result()?;
+
+    let supported: bool = launchpad_contract
+        .call("is_token_supported")
+        .args_json(json!({"token_id": "ft.test.near"}))
+        .view()
+        .await?
+        .json()?;
+    assert!(supported, "Token list should survive the upgrade.");
+
+    let version: u8 = launchpad_contract.call("get_state_version").view().await?.json()?;
+    assert_eq!(version, LAUNCHPAD_STATE_VERSION, "State should be tagged with the current version.");
+
+    println!("      Passed ✅ test_launchpad_upgrade_keeps_state");
+    Ok(())
+}
+
+/* //////////////////////////////////////////////////////////////
+                    LAUNCHPAD STATE VERSION 3
+////////////////////////////////////////////////////////////// */
+
+// bytes the current launchpad root appends to the version 5 one: keeper_reward, treasury (five u128),
+// stake_slash_percent, platform_fee_bps, the fee_bps_by_token prefix, fee_collector_id
+// the token_rates, token_pledges, reward_tiers_by_pool_id and backer_tiers prefixes, referral_reward_bps,
+// the referrals_by_pool_id and referrer_by_backer prefixes, an unset pledge_signer_key
+// the access_by_pool_id and allowlist_by_pool_id prefixes
+// the stretch_goals_by_pool_id, overfunding_by_pool_id and excess_by_backer prefixes,
+// funding_terms (a u8 and two u64) and the funding_terms_by_pool_id prefix, the referral_reserves prefix
+fn launchpad_root_bytes_since_v5(fee_collector_id: &str) -> usize {
+    16 + 5 * 16 + 1 + 2 + (4 + 1) + (4 + fee_collector_id.len()) + 4 * (4 + 1) + 2 + 2 * (4 + 1) + 1 + 2 * (4 + 1) + 3 * (4 + 1)
+        + (1 + 8 + 8) + (4 + 1) + (4 + 1)
+}
+
+#[derive(BorshSerialize)]
+struct PoolMetadataV3 {
+    pool_id: u64,
+    campaign_id: String,
+    creator_id: String,
+    staking_amount: u128,
+    status: u8, // index of the Status variant
+    token_id: String,
+    total_balance: u128,
+    target_funding: u128,
+    time_init: u64,
+    time_start_pledge: u64,
+    time_end_pledge: u64,
+    funding_duration_days: u64,
+    min_multiple_pledge: u128,
+    total_voting_power: u128,
+}
+
+pub async fn test_launchpad_migrate_pools_from_v3(
+    worker: &Worker<Sandbox>,
+    admin: &Account
+) -> anyhow::Result<()> {
+    let launchpad_wasm = std::fs::read(LAUNCHPAD_WASM_FILEPATH)?;
+    let launchpad_contract = worker.dev_deploy(&launchpad_wasm).await?;
+
+    admin
+        .call(launchpad_contract.id(), "init")
+        .args_json(json!({}))
+        .transact()
+        .await?
+        .into_result()?;
+
+    admin
+        .call(launchpad_contract.id(), "add_token")
+        .args_json(json!({"token_id": "ft.test.near"}))
+        .transact()
+        .await?
+        .into_result()?;
+
+    admin
+        .call(launchpad_contract.id(), "init_pool")
+        .args_json(json!({
+            "campaign_id": "campaign-1",
+            "token_id": "ft.test.near",
+            "min_multiple_pledge": 1,
+            "target_funding": "500"
+        }))
+        .deposit(NearToken::from_near(1))
+        .transact()
+        .await?
+        .into_result()?;
+
+    // the root of version 3 is the current one without the fields appended since version 5,
+    // the stored pool is rewritten to the old layout
+    let state = launchpad_contract.view_state().await?;
+    let root = &state[b"STATE".as_slice()];
+    let old_root = root[..root.len() - launchpad_root_bytes_since_v5(admin.id().as_str())].to_vec();
+    let old_pool = PoolMetadataV3 {
+        pool_id: 1,
+        campaign_id: "campaign-1".to_string(),
+        creator_id: admin.id().to_string(),
+        staking_amount: 1_000_000_000_000_000_000_000_000,
+        status: 2,
+        token_id: "ft.test.near".to_string(),
+        total_balance: 120,
+        target_funding: 500,
+        time_init: 1,
+        time_start_pledge: 2,
+        time_end_pledge: 3,
+        funding_duration_days: 1,
+        min_multiple_pledge: 1,
+        total_voting_power: 0,
+    };
+    // 100 was pledged, the rest of the balance is no pledge
+    let records = vec![(admin.id().to_string(), UserTokenDepositRecordV1 { amount: 100, voting_power: 0 })];
+    let (user_records, mut entries) = unordered_map_entries(&user_records_prefix(1), &records);
+    entries.extend([
+        (with_suffix(&[1], &1u64.try_to_vec()?), old_pool.try_to_vec()?),
+        (with_suffix(&[2], &1u64.try_to_vec()?), user_records.try_to_vec()?),
+        (b"STATE".to_vec(), old_root),
+        (b"STATE_VERSION".to_vec(), vec![3]),
+    ]);
+    patch_entries(worker, &launchpad_contract, entries).await?;
+
+    launchpad_contract
+        .call("migrate")
+        .args_json(json!({}))
+        .gas(NearGas::from_tgas(300))
+        .transact()
+        .await?
+        .into_result()?;
+
+    let version: u8 = launchpad_contract.call("get_state_version").view().await?.json()?;
+    assert_eq!(version, LAUNCHPAD_STATE_VERSION, "State should be tagged with the current version.");
+
+    let pool: PoolMetadata = launchpad_contract
+        .call("get_detail_pool")
+        .args_json(json!({"pool_id": 1}))
+        .view()
+        .await?
+        .json::<Option<PoolMetadata>>()?
+        .unwrap();
+
+    assert_eq!(pool.status, Status::FUNDING, "Pool status should be kept.");
+    assert_eq!(pool.total_balance, 120, "Pool balance should be kept.");
+    assert_eq!((pool.min_pledge, pool.max_pledge, pool.hard_cap), (0, 0, 0), "Old pools should have no pledge limits.");
+    assert_eq!(pool.tokens.len(), 1, "Old pools should accept only their token.");
+    assert_eq!(pool.tokens[0].balance, 120, "Old pools should hold their balance in their token.");
+    assert_eq!(pool.tokens[0].pledged, 100, "Outstanding pledges should be summed from the backer records.");
+
+    println!("      Passed ✅ test_launchpad_migrate_pools_from_v3");
+    Ok(())
+}
+
+/* //////////////////////////////////////////////////////////////
+                    PAYMENT STATE VERSION 0
+////////////////////////////////////////////////////////////// */
+
+#[derive(BorshSerialize)]
+struct PaymentV0 {
+    owner_id: String,
+    list_assets: Vec<AssetsV0>,
+    records_user_by_id: RawLookupMap,
+    all_user_id: RawUnorderedSet,
+}
+
+pub async fn test_payment_migrate_from_v0(
+    worker: &Worker<Sandbox>,
+    admin: &Account,
+    user: &Account
+) -> anyhow::Result<()> {
+    let payment_wasm = std::fs::read(PAYMENT_WASM_FILEPATH)?;
+    let payment_contract = worker.dev_deploy(&payment_wasm).await?;
+
+    // state written before versioning has no version entry
+    let (all_user_id, mut entries) = unordered_set_entries::<String>(&[1], &[]);
+    let state = PaymentV0 {
+        owner_id: admin.id().to_string(),
+        list_assets: vec![AssetsV0 { token_id: "ft.test.near".to_string(), balances: 0 }],
+        records_user_by_id: RawLookupMap { prefix: vec![0] },
+        all_user_id,
+    };
+    entries.push((b"STATE".to_vec(), state.try_to_vec()?));
+    patch_entries(worker, &payment_contract, entries).await?;
+
+    let version: u8 = payment_contract.call("get_state_version").view().await?.json()?;
+    assert_eq!(version, 0, "Unversioned state should read as version 0.");
+
+    payment_contract
+        .call("migrate")
+        .args_json(json!({}))
+        .gas(NearGas::from_tgas(300))
+        .transact()
+        .await?
+        .into_result()?;
+
+    let version: u8 = payment_contract.call("get_state_version").view().await?.json()?;
+    assert_eq!(version, PAYMENT_STATE_VERSION, "State should be tagged with the current version.");
+
+    let outcome = user
+        .call(payment_contract.id(), "upgrade")
+        .args(payment_wasm.clone())
+        .gas(NearGas::from_tgas(300))
+        .transact()
+        .await?;
+    assert!(outcome.is_failure(), "Only admin should be able to upgrade.");
+
+    admin
+        .call(payment_contract.id(), "upgrade")
+        .args(payment_wasm)
+        .gas(NearGas::from_tgas(300))
+        .transact()
+        .await?
+        .into_result()?;
+
+    let token_ids: Option<Vec<String>> = payment_contract.call("get_all_token_id").view().await?.json()?;
+    assert_eq!(token_ids, Some(vec!["ft.test.near".to_string()]), "Token list should survive the upgrade.");
+
+    println!("      Passed ✅ test_payment_migrate_from_v0");
+    Ok(())
+}
+
+/* //////////////////////////////////////////////////////////////
+                            NFT UPGRADE
+////////////////////////////////////////////////////////////// */
+
+pub async fn test_nft_upgrade_keeps_state(
+    worker: &Worker<Sandbox>,
+    admin: &Account,
+    user: &Account
+) -> anyhow::Result<()> {
+    let nft_wasm = std::fs::read(NFT_WASM_FILEPATH)?;
+    let nft_contract = worker.dev_deploy(&nft_wasm).await?;
+
+    nft_contract
+        .call("new_default_meta")
+        .args_json(json!({
+            "owner_id": admin.id(),
+            "admin_pub_key": "admin-pub-key"
+        }))
+        .transact()
+        .await?
+        .into_result()?;
+
+    admin
+        .call(nft_contract.id(), "nft_mint")
+        .args_json(json!({
+            "token_id": "certificate-1",
+            "receiver_id": user.id(),
+            "token_metadata": {"title": "Certificate"}
+        }))
+        .deposit(NearToken::from_millinear(100))
+        .transact()
+        .await?
+        .into_result()?;
+
+    let outcome = user
+        .call(nft_contract.id(), "upgrade")
+        .args(nft_wasm.clone())
+        .gas(NearGas::from_tgas(300))
+        .transact()
+        .await?;
+    assert!(outcome.is_failure(), "Only the NFT owner should be able to upgrade.");
+
+    admin
+        .call(nft_contract.id(), "upgrade")
+        .args(nft_wasm)
+        .gas(NearGas::from_tgas(300))
+        .transact()
+        .await?
+        .into_result()?;
+
+    let supply: String = nft_contract.call("nft_total_supply").view().await?.json()?;
+    assert_eq!(supply, "1", "Minted certificates should survive the upgrade.");
+
+    let version: u8 = nft_contract.call("get_state_version").view().await?.json()?;
+    assert_eq!(version, NFT_STATE_VERSION, "State should be tagged with the current version.");
+
+    println!("      Passed ✅ test_nft_upgrade_keeps_state");
+    Ok(())
+}
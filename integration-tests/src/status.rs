@@ -0,0 +1,172 @@
+use near_gas::NearGas;
+use near_token::NearToken;
+use serde_json::json;
+#[allow(dead_code)]
+mod helpers;
+use near_workspaces::{network::Sandbox, Account, Contract, Worker};
+
+use helpers::{PoolMetadata, Status};
+
+const LAUNCHPAD_WASM_FILEPATH: &str = "../res/launchpad.wasm";
+
+const INITIAL_NEAR: NearToken = NearToken::from_near(30);
+const INIT_POOL: NearToken = NearToken::from_near(1);
+
+const STATUSES: [Status; 11] = [
+    Status::INIT, Status::APPROVED, Status::FUNDING, Status::REJECTED, Status::CANCELED, Status::FAILED,
+    Status::WAITING, Status::REFUNDED, Status::VOTING, Status::CLOSED, Status::SUCCESSFUL
+];
+
+// the transition graph the contract must enforce, kept apart from the contract's own copy
+fn next_statuses(status: &Status) -> Vec<Status> {
+    match status {
+        Status::INIT => vec![Status::APPROVED, Status::REJECTED, Status::CANCELED],
+        Status::APPROVED => vec![Status::FUNDING],
+        Status::FUNDING => vec![Status::FAILED, Status::WAITING, Status::REFUNDED, Status::VOTING],
+        Status::WAITING => vec![Status::REFUNDED, Status::VOTING],
+        Status::VOTING => vec![Status::REFUNDED, Status::CLOSED, Status::SUCCESSFUL],
+        _ => vec![],
+    }
+}
+
+// borsh stores a status as the index of its variant
+fn index_of(status: &Status) -> u8 {
+    STATUSES.iter().position(|candidate| candidate == status).unwrap() as u8
+}
+
+// edges the guards refuse for the test pool: never scheduled, nothing pledged and its milestone pending
+fn refused_by_guard(from: &Status, to: &Status) -> bool {
+    matches!(
+        (from, to),
+        (Status::APPROVED, Status::FUNDING)
+            | (Status::FUNDING, Status::WAITING)
+            | (Status::FUNDING, Status::VOTING)
+            | (Status::VOTING, Status::SUCCESSFUL)
+    )
+}
+
+#[tokio::main]
+async fn main() -> anyhow::Result<()> {
+    let worker = near_workspaces::sandbox().await?;
+    let owner = worker.root_account().unwrap();
+
+    let admin = owner
+        .create_subaccount("admin")
+        .initial_balance(INITIAL_NEAR)
+        .transact()
+        .await?
+        .into_result()?;
+
+    let launchpad_wasm = std::fs::read(LAUNCHPAD_WASM_FILEPATH)?;
+    let launchpad_contract = worker.dev_deploy(&launchpad_wasm).await?;
+
+    admin
+        .call(launchpad_contract.id(), "init")
+        .args_json(json!({}))
+        .transact()
+        .await?
+        .into_result()?;
+
+    // begin tests
+    test_update_pool_status_matrix(&worker, &launchpad_contract, &admin).await?;
+    Ok(())
+}
+
+pub async fn test_update_pool_status_matrix(
+    worker: &Worker<Sandbox>,
+    launchpad_contract: &Contract,
+    admin: &Account
+) -> anyhow::Result<()> {
+
+    admin
+        .call(launchpad_contract.id(), "add_token")
+        .args_json(json!({"token_id": "ft.test.near"}))
+        .transact()
+        .await?
+        .into_result()?;
+
+    let pool: PoolMetadata = admin
+        .call(launchpad_contract.id(), "init_pool")
+        .args_json(json!({
+            "campaign_id": "matrix",
+            "token_id": "ft.test.near",
+            "min_multiple_pledge": 1,
+            "target_funding": "500"
+        }))
+        .deposit(INIT_POOL)
+        .transact()
+        .await?
+        .json()?;
+
+    // every pair starts from the state right after init_pool with only the stored status changed
+    let snapshot = launchpad_contract.view_state().await?;
+    let pool_key = [&[1u8][..], &pool.pool_id.to_le_bytes()].concat();
+    let stored_pool = snapshot[&pool_key].clone();
+
+    // approving the pool changes nothing stored but its status, the one byte that differs is the status
+    admin
+        .call(launchpad_contract.id(), "admin_set_status_pool_pre_funding")
+        .args_json(json!({"pool_id": pool.pool_id, "approve": true}))
+        .transact()
+        .await?
+        .into_result()?;
+    let approved_pool = launchpad_contract.view_state().await?[&pool_key].clone();
+    assert_eq!(approved_pool.len(), stored_pool.len(), "Approval should not resize the stored pool.");
+    let changed: Vec<usize> = (0..stored_pool.len())
+        .filter(|&index| stored_pool[index] != approved_pool[index])
+        .collect();
+    assert_eq!(changed.len(), 1, "Approval should only change the status byte.");
+    let status_offset = changed[0];
+    assert_eq!(
+        (stored_pool[status_offset], approved_pool[status_offset]),
+        (index_of(&Status::INIT), index_of(&Status::APPROVED)),
+        "Status byte should hold the index of the status."
+    );
+
+    for from in STATUSES.iter() {
+        for to in STATUSES.iter() {
+            for (key, value) in snapshot.iter() {
+                worker.patch_state(launchpad_contract.id(), key, value).await?;
+            }
+            let mut patched_pool = stored_pool.clone();
+            patched_pool[status_offset] = index_of(from);
+            worker.patch_state(launchpad_contract.id(), &pool_key, &patched_pool).await?;
+
+            let outcome = admin
+                .call(launchpad_contract.id(), "update_pool_status")
+                .args_json(json!({"pool_id": pool.pool_id, "status": format!("{:?}", to)}))
+                .gas(NearGas::from_tgas(50))
+                .transact()
+                .await?;
+            let succeeded = outcome.is_success();
+            let error = outcome.into_result().err().map(|error| format!("{:?}", error)).unwrap_or_default();
+
+            let stored: PoolMetadata = launchpad_contract
+                .call("get_detail_pool")
+                .args_json(json!({"pool_id": pool.pool_id}))
+                .view()
+                .await?
+                .json::<Option<PoolMetadata>>()?
+                .unwrap();
+
+            if next_statuses(from).contains(to) {
+                assert!(
+                    !error.contains("Invalid status transition"),
+                    "{:?} -> {:?} should be permitted by the graph.", from, to
+                );
+                assert_eq!(succeeded, !refused_by_guard(from, to), "{:?} -> {:?} guard outcome is wrong.", from, to);
+                let expected = if succeeded { to } else { from };
+                assert_eq!(&stored.status, expected, "{:?} -> {:?} stored the wrong status.", from, to);
+            } else {
+                assert!(
+                    error.contains("Invalid status transition"),
+                    "{:?} -> {:?} should be refused by the graph.", from, to
+                );
+                assert_eq!(&stored.status, from, "{:?} -> {:?} should leave the status as it is.", from, to);
+            }
+        }
+    }
+
+    println!("      Passed ✅ test_update_pool_status_matrix");
+    Ok(())
+}
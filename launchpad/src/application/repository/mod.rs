@@ -1 +1,1390 @@
-// chưa dùng đến
\ No newline at end of file
+use near_sdk::{collections::{UnorderedMap, UnorderedSet}, env, json_types::U128, serde_json, AccountId, Promise, PromiseOrValue};
+use ed25519_dalek::{PublicKey, Signature, Verifier};
+use near_sdk::base64::decode;
+use uint::construct_uint;
+
+use crate::models::contract::{
+    Assets, Feature, FundingTerms, Launchpad, LaunchpadStorageKey, Role, Milestone, MilestoneInfo, MilestoneStatus, OverfundingPolicy, PoolFilter, PoolMetadata, PoolAccess, PoolToken, ProposalStatus, ReferralStats, RefundReason, RewardTier, RewardTierInfo, Status, StretchGoal, StretchGoalInfo, TokenPledge, TransferAction, TransferMsg, UserTokenDepositRecord,
+    BPS_DENOMINATOR, DEFAULT_PAGE_LIMIT, MAX_PAGE_LIMIT, MAX_GOAL_DESCRIPTION_LEN, MAX_FUNDING_DURATION, MAX_INIT_EXPIRY_DURATION, MAX_MILESTONES, MAX_REWARD_TIERS, MAX_STRETCH_GOALS, MAX_TIER_DESCRIPTION_LEN, NEAR_TOKEN_ID, RATE_DENOMINATOR, STATE_VERSION, STATE_VERSION_KEY, TRANSFER_MSG_VERSION,
+    MAX_WAITING_EXTENSION_DURATION, MAX_WAITING_THRESHOLD_PERCENT, MIN_FUNDING_DURATION, MIN_GRACE_PERIOD, MIN_WAITING_THRESHOLD_PERCENT
+};
+use crate::application::services::infrastructure::impl_launchpad::GAS_FOR_FT_TRANSFER_CALL;
+use crate::models::events::{KeeperRewarded, OverfundingSettled, Pledge, PledgeRejected, ReferralRecorded, RewardTierReleased, RewardTierSelected, StakeRefunded, StakeSlashed, StretchGoalUnlocked};
+use crate::models::ft_request::external::cross_edu;
+use crate::models::time::Timestamp;
+use crate::models::PoolId;
+
+// the macro expands to arithmetic clippy would write differently
+#[allow(clippy::assign_op_pattern, clippy::manual_div_ceil)]
+mod uint_types {
+    use super::construct_uint;
+
+    construct_uint! {
+        /// 256-bit unsigned integer so products of 24-decimal token amounts cannot overflow.
+        pub struct U256(4);
+    }
+}
+pub use uint_types::U256;
+
+// x * y / denominator rounded down, computed without intermediate overflow
+pub fn mul_div_down(x: u128, y: u128, denominator: u128) -> u128 {
+    if denominator == 0 {
+        env::panic_str("mulDivDown: Denominator is zero");
+    }
+
+    (U256::from(x) * U256::from(y) / U256::from(denominator)).as_u128()
+}
+
+// validate a milestone schedule declared by the creator and build its stored form
+pub fn build_milestones(milestones: Vec<MilestoneInfo>) -> Vec<Milestone> {
+    if milestones.is_empty() || milestones.len() > MAX_MILESTONES {
+        env::panic_str(&format!("Pool must have between 1 and {} milestones", MAX_MILESTONES));
+    }
+
+    if milestones.iter().any(|milestone| milestone.percent == 0) {
+        env::panic_str("Milestone percentage must be greater than 0");
+    }
+
+    let total_percent: u32 = milestones.iter().map(|milestone| milestone.percent as u32).sum();
+    if total_percent != 100 {
+        env::panic_str("Milestone percentages must add up to 100");
+    }
+
+    if milestones.windows(2).any(|pair| pair[1].deadline <= pair[0].deadline) {
+        env::panic_str("Milestone deadlines must be strictly increasing");
+    }
+
+    milestones
+        .into_iter()
+        .map(|milestone| Milestone {
+            percent: milestone.percent,
+            deadline: milestone.deadline,
+            status: MilestoneStatus::PENDING,
+            amount_released: 0,
+        })
+        .collect()
+}
+
+// validate the reward tiers declared by the creator and build their stored form
+pub fn build_reward_tiers(tiers: Vec<RewardTierInfo>) -> Vec<RewardTier> {
+    if tiers.len() > MAX_REWARD_TIERS {
+        env::panic_str(&format!("Pool cannot have more than {} reward tiers", MAX_REWARD_TIERS));
+    }
+
+    if tiers.iter().any(|tier| tier.min_pledge.0 == 0) {
+        env::panic_str("Reward tier minimum pledge must be greater than 0");
+    }
+
+    if tiers.iter().any(|tier| tier.description.is_empty() || tier.description.len() > MAX_TIER_DESCRIPTION_LEN) {
+        env::panic_str(&format!("Reward tier description must have between 1 and {} bytes", MAX_TIER_DESCRIPTION_LEN));
+    }
+
+    tiers
+        .into_iter()
+        .map(|tier| RewardTier {
+            min_pledge: tier.min_pledge.0,
+            quantity: tier.quantity.unwrap_or(0),
+            allocated: 0,
+            description: tier.description,
+        })
+        .collect()
+}
+
+// validate the stretch goals declared by the creator and build their stored form, every goal
+// lies between the funding target and the hard cap, above the goal before it
+pub fn build_stretch_goals(pool: &PoolMetadata, goals: Vec<StretchGoalInfo>) -> Vec<StretchGoal> {
+    if goals.len() > MAX_STRETCH_GOALS {
+        env::panic_str(&format!("Pool cannot have more than {} stretch goals", MAX_STRETCH_GOALS));
+    }
+
+    let mut previous = pool.target_funding;
+    for goal in goals.iter() {
+        if goal.amount.0 <= previous {
+            env::panic_str("Stretch goals must be above the funding target, each above the one before");
+        }
+        previous = goal.amount.0;
+    }
+
+    if previous > pool_hard_cap(pool) {
+        env::panic_str("Stretch goals cannot exceed the hard cap of the pool");
+    }
+
+    if goals.iter().any(|goal| goal.description.is_empty() || goal.description.len() > MAX_GOAL_DESCRIPTION_LEN) {
+        env::panic_str(&format!("Stretch goal description must have between 1 and {} bytes", MAX_GOAL_DESCRIPTION_LEN));
+    }
+
+    goals
+        .into_iter()
+        .map(|goal| StretchGoal {
+            amount: goal.amount.0,
+            description: goal.description,
+            unlocked: false,
+        })
+        .collect()
+}
+
+// action of an `ft_on_transfer` message, the JSON `TransferMsg` or the "<pool_id>" and
+// "<pool_id>:<tier_index>" pledges of older callers
+pub fn parse_transfer_msg(msg: &str) -> Result<TransferAction, RefundReason> {
+    if !msg.trim_start().starts_with('{') {
+        return parse_legacy_pledge_msg(msg).ok_or(RefundReason::InvalidMessage);
+    }
+
+    let transfer_msg: TransferMsg = serde_json::from_str(msg)
+        .map_err(|_| RefundReason::InvalidMessage)?;
+
+    if transfer_msg.version == 0 || transfer_msg.version > TRANSFER_MSG_VERSION {
+        return Err(RefundReason::UnsupportedVersion);
+    }
+
+    Ok(transfer_msg.action)
+}
+
+fn parse_legacy_pledge_msg(msg: &str) -> Option<TransferAction> {
+    let (pool_id, tier) = match msg.split_once(':') {
+        Some((pool_id, tier_index)) => (pool_id.parse().ok()?, Some(tier_index.parse().ok()?)),
+        None => (msg.parse().ok()?, None),
+    };
+
+    Some(TransferAction::Pledge { pool_id, tier, referrer: None, signature: None })
+}
+
+// whether `signature_base64` is the platform key's ed25519 signature of "<pool_id>:<account_id>"
+pub fn verify_pledge_signature(public_key_base64: &str, pool_id: PoolId, account_id: &AccountId, signature_base64: &str) -> bool {
+    let signature = match decode(signature_base64).ok().and_then(|bytes| Signature::from_bytes(&bytes).ok()) {
+        Some(signature) => signature,
+        None => return false,
+    };
+
+    let public_key = match decode(public_key_base64).ok().and_then(|bytes| PublicKey::from_bytes(&bytes).ok()) {
+        Some(public_key) => public_key,
+        None => return false,
+    };
+
+    let expected_message = format!("{}:{}", pool_id, account_id);
+    public_key.verify(expected_message.as_bytes(), &signature).is_ok()
+}
+
+// report why the tokens go back to the sender, returning the amount makes the token contract refund it
+pub fn reject_transfer(sender_id: &AccountId, token_id: &AccountId, amount: U128, reason: RefundReason) -> PromiseOrValue<U128> {
+    PledgeRejected {
+        account_id: sender_id,
+        token_id,
+        amount,
+        reason,
+    }
+    .emit();
+
+    PromiseOrValue::Value(amount)
+}
+
+// schedule used when the creator does not declare one: everything in a single tranche
+pub fn default_milestones() -> Vec<Milestone> {
+    vec![Milestone {
+        percent: 100,
+        deadline: Timestamp::default(),
+        status: MilestoneStatus::PENDING,
+        amount_released: 0,
+    }]
+}
+
+// first milestone that still waits for the backers' approval
+pub fn current_milestone_index(milestones: &[Milestone]) -> Option<usize> {
+    milestones.iter().position(|milestone| milestone.status == MilestoneStatus::PENDING)
+}
+
+pub fn is_milestone_expired(milestone: &Milestone, current_time: Timestamp) -> bool {
+    milestone.deadline.is_set() && current_time > milestone.deadline
+}
+
+// pool terms can change until the creator schedules funding
+pub fn is_before_funding(pool: &PoolMetadata) -> bool {
+    matches!(pool.status, Status::INIT | Status::APPROVED) && !pool.time_end_pledge.is_set()
+}
+
+// check a pledge window against the allowed funding durations
+pub fn validate_funding_window(time_start_pledge: Timestamp, time_end_pledge: Timestamp) {
+    if time_end_pledge <= time_start_pledge {
+        env::panic_str("Funding must end after it starts");
+    }
+
+    let duration = time_end_pledge - time_start_pledge;
+    if !(MIN_FUNDING_DURATION..=MAX_FUNDING_DURATION).contains(&duration) {
+        env::panic_str(&format!(
+            "Funding duration must be between {} and {} days",
+            MIN_FUNDING_DURATION.whole_days(),
+            MAX_FUNDING_DURATION.whole_days()
+        ));
+    }
+}
+
+pub fn is_native_near(token_id: &AccountId) -> bool {
+    token_id.as_str() == NEAR_TOKEN_ID
+}
+
+// send funds in the pool's token, native NEAR goes out as a plain transfer
+pub fn transfer_asset(token_id: &AccountId, receiver_id: AccountId, amount: u128) -> Promise {
+    if is_native_near(token_id) {
+        Promise::new(receiver_id)
+            .transfer(amount)
+    } else {
+        cross_edu::ext(token_id.clone())
+            .with_static_gas(GAS_FOR_FT_TRANSFER_CALL)
+            .with_attached_deposit(1)
+            .ft_transfer(receiver_id, U128(amount))
+    }
+}
+
+// status a pool reaches when its pledge window closes, a pool close to its target
+// only waits for its creator when the caller allows it
+pub fn funding_result(pool: &PoolMetadata, terms: &FundingTerms, is_waiting_funding: bool) -> Status {
+    let waiting_threshold = pool.target_funding * terms.waiting_threshold_percent as u128 / 100;
+
+    match pool.total_balance {
+        0 => Status::FAILED,
+        _ if pool.total_balance >= pool.target_funding => Status::VOTING,
+        _ if pool.total_balance >= waiting_threshold && is_waiting_funding => Status::WAITING,
+        _ => Status::REFUNDED,
+    }
+}
+
+// check funding terms set by the admin or merged from a pool's overrides against their allowed ranges
+pub fn validate_funding_terms(terms: &FundingTerms) {
+    if !(MIN_WAITING_THRESHOLD_PERCENT..=MAX_WAITING_THRESHOLD_PERCENT).contains(&terms.waiting_threshold_percent) {
+        env::panic_str(&format!(
+            "Waiting threshold must be between {}% and {}%",
+            MIN_WAITING_THRESHOLD_PERCENT,
+            MAX_WAITING_THRESHOLD_PERCENT
+        ));
+    }
+
+    if !(MIN_GRACE_PERIOD..=MAX_WAITING_EXTENSION_DURATION).contains(&terms.waiting_extension) {
+        env::panic_str(&format!(
+            "Waiting extension must be between {} and {}",
+            MIN_GRACE_PERIOD,
+            MAX_WAITING_EXTENSION_DURATION
+        ));
+    }
+
+    if !(MIN_GRACE_PERIOD..=MAX_INIT_EXPIRY_DURATION).contains(&terms.init_expiry) {
+        env::panic_str(&format!(
+            "Init expiry must be between {} and {}",
+            MIN_GRACE_PERIOD,
+            MAX_INIT_EXPIRY_DURATION
+        ));
+    }
+}
+
+// total the pool accepts pledges up to, the funding target unless the creator set a hard cap
+pub fn pool_hard_cap(pool: &PoolMetadata) -> u128 {
+    if pool.hard_cap == 0 {
+        pool.target_funding
+    } else {
+        pool.hard_cap
+    }
+}
+
+// part of a pledge the pool keeps once the hard cap, the backer's maximum and the pledge multiple
+// are applied, the rest goes back to the backer
+pub fn accepted_pledge_amount(pool: &PoolMetadata, pledged: u128, amount: u128) -> u128 {
+    let mut accepted = amount.min(pool_hard_cap(pool).saturating_sub(pool.total_balance));
+
+    if pool.max_pledge > 0 {
+        accepted = accepted.min(pool.max_pledge.saturating_sub(pledged));
+    }
+
+    round_to_pledge_multiple(pool, accepted)
+}
+
+// amount rounded down to a whole number of pledge multiples, unchanged when the pool has none
+pub fn round_to_pledge_multiple(pool: &PoolMetadata, amount: u128) -> u128 {
+    if pool.min_multiple_pledge > 0 {
+        amount - amount % pool.min_multiple_pledge
+    } else {
+        amount
+    }
+}
+
+pub fn pool_token_mut<'a>(pool: &'a mut PoolMetadata, token_id: &AccountId) -> &'a mut PoolToken {
+    pool.tokens.iter_mut()
+        .find(|token| &token.token_id == token_id)
+        .unwrap_or_else(|| env::panic_str("Token is not accepted by this pool"))
+}
+
+// add `amount` worth `value` to the backer's pledge in the token
+pub fn add_token_pledge(pledges: &mut Vec<TokenPledge>, token_id: &AccountId, amount: u128, value: u128) {
+    match pledges.iter_mut().find(|pledge| &pledge.token_id == token_id) {
+        Some(pledge) => {
+            pledge.amount += amount;
+            pledge.value += value;
+        },
+        None => pledges.push(TokenPledge { token_id: token_id.clone(), amount, value }),
+    }
+}
+
+// parts of each token making up `value` of the pledges, taken in proportion to their worth;
+// the rounding goes to the first parts with room left so the parts add up to `value`
+pub fn split_token_pledges(pledges: &[TokenPledge], value: u128) -> Vec<TokenPledge> {
+    let total_value: u128 = pledges.iter().map(|pledge| pledge.value).sum();
+    let mut values: Vec<u128> = pledges.iter()
+        .map(|pledge| mul_div_down(pledge.value, value, total_value))
+        .collect();
+
+    let mut leftover = value - values.iter().sum::<u128>();
+    for (part_value, pledge) in values.iter_mut().zip(pledges) {
+        let added = leftover.min(pledge.value - *part_value);
+        *part_value += added;
+        leftover -= added;
+    }
+
+    pledges.iter().zip(values)
+        .map(|(pledge, part_value)| {
+            let amount = if part_value == pledge.value {
+                pledge.amount
+            } else {
+                mul_div_down(pledge.amount, part_value, pledge.value)
+            };
+            TokenPledge { token_id: pledge.token_id.clone(), amount, value: part_value }
+        })
+        .collect()
+}
+
+// take a backer's refund out of the pool and return the amount and worth paid for each token pledge;
+// every token is shared by the pledges still outstanding in it and the worth by the voting power left,
+// so the last backer to claim receives the exact remainder of both
+pub fn take_refund(pool: &mut PoolMetadata, token_pledges: &[TokenPledge]) -> Vec<(u128, u128)> {
+    let pledged_value: u128 = token_pledges.iter().map(|token_pledge| token_pledge.value).sum();
+    let refund_value = mul_div_down(pledged_value, pool.total_balance, pool.total_voting_power);
+
+    let mut remaining_value = refund_value;
+    let mut refunds = Vec::with_capacity(token_pledges.len());
+    for (index, token_pledge) in token_pledges.iter().enumerate() {
+        let pool_token = pool_token_mut(pool, &token_pledge.token_id);
+        let amount = mul_div_down(token_pledge.amount, pool_token.balance, pool_token.pledged);
+        pool_token.balance -= amount;
+        pool_token.pledged -= token_pledge.amount;
+
+        // the worth is split by the pledges' worth, the last token takes the rounding
+        let value = if index == token_pledges.len() - 1 {
+            remaining_value
+        } else {
+            mul_div_down(refund_value, token_pledge.value, pledged_value)
+        };
+        remaining_value -= value;
+        refunds.push((amount, value));
+    }
+
+    pool.total_balance -= refund_value;
+    pool.total_voting_power -= pledged_value;
+
+    refunds
+}
+
+// outcome of a proposal given the voting power behind each side, None while it is still open;
+// a side holding the majority of all voting power decides at once, otherwise the proposal is
+// decided at its deadline if enough power voted and expires if not
+pub fn tally_outcome(total_power: u128, approve_power: u128, reject_power: u128, quorum_percent: u8, voting_ended: bool) -> Option<ProposalStatus> {
+    // a pool without voting power can only let its proposal expire
+    if total_power == 0 {
+        return if voting_ended { Some(ProposalStatus::EXPIRED) } else { None };
+    }
+
+    let quorum_power = mul_div_down(total_power, quorum_percent as u128, 100);
+    let quorum_reached = approve_power + reject_power >= quorum_power;
+
+    if approve_power > total_power - approve_power || (voting_ended && quorum_reached && approve_power > reject_power) {
+        Some(ProposalStatus::APPROVED)
+    } else if reject_power >= total_power - reject_power || (voting_ended && quorum_reached) {
+        Some(ProposalStatus::REJECTED)
+    } else if voting_ended {
+        Some(ProposalStatus::EXPIRED)
+    } else {
+        None
+    }
+}
+
+// layout version of the stored state, state written before versioning was introduced is version 0
+pub fn read_state_version() -> u8 {
+    env::storage_read(STATE_VERSION_KEY).map_or(0, |version| version[0])
+}
+
+pub fn write_state_version() {
+    env::storage_write(STATE_VERSION_KEY, &[STATE_VERSION]);
+}
+
+// clamp the requested page size of a view
+pub fn page_limit(limit: Option<u64>) -> u64 {
+    limit.unwrap_or(DEFAULT_PAGE_LIMIT).min(MAX_PAGE_LIMIT)
+}
+
+pub fn pool_matches_filter(pool: &PoolMetadata, filter: &PoolFilter) -> bool {
+    filter.status.iter().all(|status| &pool.status == status)
+        && filter.creator_id.iter().all(|creator_id| &pool.creator_id == creator_id)
+        && filter.token_id.iter().all(|token_id| pool.tokens.iter().any(|token| &token.token_id == token_id))
+        && filter.created_from.iter().all(|&from| pool.time_init >= from)
+        && filter.created_to.iter().all(|&to| pool.time_init <= to)
+}
+
+impl Launchpad {
+    pub(crate) fn internal_has_role(&self, role: &Role, account_id: &AccountId) -> bool {
+        account_id == &self.owner_id
+            || self.role_members.get(role).is_some_and(|members| members.contains(account_id))
+    }
+
+    // panic unless the caller is the admin
+    pub(crate) fn assert_owner(&self) {
+        if env::predecessor_account_id() != self.owner_id {
+            env::panic_str("Only the admin can call this method.");
+        }
+    }
+
+    // panic unless the caller is the admin or holds the role
+    pub(crate) fn assert_role(&self, role: Role) {
+        if !self.internal_has_role(&role, &env::predecessor_account_id()) {
+            env::panic_str(&format!("Caller does not have the {:?} role.", role));
+        }
+    }
+
+    // panic unless the caller created the pool, returns the pool
+    pub(crate) fn assert_pool_creator(&self, pool_id: PoolId) -> PoolMetadata {
+        let pool = self.pool_metadata_by_id.get(&pool_id)
+            .expect("Pool does not exist");
+
+        if env::predecessor_account_id() != pool.creator_id {
+            env::panic_str("Only the creator of the pool can call this method.");
+        }
+
+        pool
+    }
+
+    pub(crate) fn internal_is_paused(&self, feature: &Feature) -> bool {
+        self.paused_features.contains(feature)
+    }
+
+    // panic while a pauser has halted the feature
+    pub(crate) fn assert_not_paused(&self, feature: Feature) {
+        if self.internal_is_paused(&feature) {
+            env::panic_str(&format!("{:?} is paused.", feature));
+        }
+    }
+
+    // remember that the account backs the pool so it can be listed by get_pools_by_backer
+    pub(crate) fn internal_add_backer_pool(&mut self, account_id: &AccountId, pool_id: PoolId) {
+        let mut pool_ids = self.pools_by_backer.get(account_id).unwrap_or_else(|| {
+            UnorderedSet::new(LaunchpadStorageKey::backer_pools_prefix(account_id))
+        });
+
+        if pool_ids.insert(&pool_id) {
+            self.pools_by_backer.insert(account_id, &pool_ids);
+        }
+    }
+
+    // send the creator's stake back when the pool is rejected, the treasury keeps the rest
+    pub(crate) fn internal_refund_stake(&mut self, pool: &mut PoolMetadata) {
+        let refund_amount = if self.refund_percent == 0 {
+            1_000_000_000_000_000_000_000 // 1 NEAR
+        } else {
+            (pool.staking_amount * self.refund_percent as u128) / 100
+        }
+        .min(pool.staking_amount);
+
+        let retained = pool.staking_amount - refund_amount;
+        self.treasury.balance += retained;
+        self.treasury.retained_stakes += retained;
+
+        self.internal_return_stake(pool, refund_amount);
+    }
+
+    // move the slashed part of the creator's stake to the treasury after a failed milestone
+    // and return what is left of it
+    pub(crate) fn internal_slash_stake(&mut self, pool: &mut PoolMetadata) {
+        let slashed = mul_div_down(pool.staking_amount, self.stake_slash_percent as u128, 100);
+        let returned = pool.staking_amount - slashed;
+
+        self.treasury.balance += slashed;
+        self.treasury.slashed_stakes += slashed;
+
+        StakeSlashed {
+            pool_id: pool.pool_id,
+            creator_id: &pool.creator_id,
+            amount: U128(slashed),
+        }
+        .emit();
+
+        if returned > 0 {
+            self.internal_return_stake(pool, returned);
+        } else {
+            pool.staking_amount = 0;
+        }
+    }
+
+    // the whole stake goes back to the creator once the pool is SUCCESSFUL
+    pub(crate) fn internal_settle_successful_stake(&self, pool: &mut PoolMetadata) {
+        if pool.staking_amount > 0 {
+            let stake = pool.staking_amount;
+            self.internal_return_stake(pool, stake);
+        }
+    }
+
+    pub(crate) fn internal_return_stake(&self, pool: &mut PoolMetadata, amount: u128) {
+        Promise::new(pool.creator_id.clone())
+            .transfer(amount);
+
+        pool.staking_amount = 0;
+
+        StakeRefunded {
+            pool_id: pool.pool_id,
+            creator_id: &pool.creator_id,
+            amount: U128(amount),
+        }
+        .emit();
+    }
+
+    // move the pool out of FUNDING by its result
+    pub(crate) fn internal_finish_funding(&mut self, pool: &mut PoolMetadata, is_waiting_funding: bool) {
+        let terms = self.internal_funding_terms(pool.pool_id);
+
+        pool.status = funding_result(pool, &terms, is_waiting_funding);
+        if pool.status == Status::VOTING {
+            self.internal_settle_overfunding(pool);
+        }
+
+        self.internal_assign_voting_power(pool);
+        if pool.status == Status::WAITING {
+            pool.time_end_pledge += terms.waiting_extension;
+        }
+    }
+
+    // unlock the stretch goals the raise reached and set aside what the overfunding policy hands back,
+    // run as a pool enters VOTING from FUNDING and before voting power is assigned
+    pub(crate) fn internal_settle_overfunding(&mut self, pool: &mut PoolMetadata) {
+        let pool_id = pool.pool_id;
+        let raised = pool.total_balance;
+
+        // the pool keeps up to the highest goal reached, the funding target or an unlocked stretch goal
+        let mut goal = pool.target_funding;
+        if let Some(mut goals) = self.stretch_goals_by_pool_id.get(&pool_id) {
+            for (goal_index, stretch_goal) in goals.iter_mut().enumerate() {
+                if stretch_goal.amount > raised {
+                    break;
+                }
+                stretch_goal.unlocked = true;
+                goal = stretch_goal.amount;
+
+                StretchGoalUnlocked {
+                    pool_id,
+                    goal_index: goal_index as u32,
+                    amount: U128(stretch_goal.amount),
+                }
+                .emit();
+            }
+            self.stretch_goals_by_pool_id.insert(&pool_id, &goals);
+        }
+
+        let policy = self.overfunding_by_pool_id.get(&pool_id).unwrap_or(OverfundingPolicy::AcceptAll);
+        if policy == OverfundingPolicy::AcceptAll || raised <= goal {
+            return;
+        }
+
+        let mut user_records = match self.user_records.get(&pool_id) {
+            Some(user_records) => user_records,
+            None => return,
+        };
+
+        // withdraw penalties are in the raise but in no record, the pool keeps them
+        let records = user_records.to_vec();
+        let pledged: u128 = records.iter().map(|(_, record)| record.amount).sum();
+        let excess = (raised - goal).min(pledged);
+        if excess == 0 {
+            return;
+        }
+
+        // records are in the order of each backer's first pledge
+        let excess_values: Vec<(AccountId, u128)> = match policy {
+            OverfundingPolicy::CapAndRefund => {
+                let mut left = excess;
+                records.iter().rev()
+                    .map(|(account_id, record)| {
+                        let value = left.min(record.amount);
+                        left -= value;
+                        (account_id.clone(), value)
+                    })
+                    .collect()
+            },
+            OverfundingPolicy::ProRata => records.iter()
+                .map(|(account_id, record)| (account_id.clone(), mul_div_down(record.amount, excess, pledged)))
+                .collect(),
+            OverfundingPolicy::AcceptAll => Vec::new(),
+        };
+
+        let mut refunded = 0;
+        for (account_id, value) in excess_values {
+            if value == 0 {
+                continue;
+            }
+
+            let mut record = user_records.get(&account_id)
+                .expect("User has no record in this pool");
+            let mut token_pledges = self.internal_token_pledges(pool, &account_id, &record);
+            let mut parts = split_token_pledges(&token_pledges, value);
+
+            for (token_pledge, part) in token_pledges.iter_mut().zip(parts.iter()) {
+                if part.amount == 0 {
+                    continue;
+                }
+
+                token_pledge.amount -= part.amount;
+                token_pledge.value -= part.value;
+                self.internal_remove_referred_pledge(pool_id, &account_id, part);
+                let pool_token = pool_token_mut(pool, &part.token_id);
+                pool_token.balance -= part.amount;
+                pool_token.pledged -= part.amount;
+            }
+
+            token_pledges.retain(|token_pledge| token_pledge.amount > 0);
+            self.token_pledges.insert(&(pool_id, account_id.clone()), &token_pledges);
+            parts.retain(|part| part.amount > 0);
+            self.excess_by_backer.insert(&(pool_id, account_id.clone()), &parts);
+
+            pool.total_balance -= value;
+            record.amount -= value;
+            refunded += value;
+            self.internal_release_tier(pool_id, &account_id, record.amount);
+            user_records.insert(&account_id, &record);
+        }
+        self.user_records.insert(&pool_id, &user_records);
+
+        OverfundingSettled {
+            pool_id,
+            policy: &policy,
+            goal: U128(goal),
+            excess: U128(refunded),
+        }
+        .emit();
+    }
+
+    // give every backer voting power for its pledge, one unit of pledged token is one unit of voting power
+    pub(crate) fn internal_assign_voting_power(&mut self, pool: &mut PoolMetadata) {
+        let mut total_voting_power = 0;
+        if let Some(mut user_records) = self.user_records.get(&pool.pool_id) {
+            let mut updated_records = Vec::new();
+            for (user_id, record) in user_records.iter() {
+                let mut updated_record = record.clone();
+                updated_record.voting_power = updated_record.amount;
+                total_voting_power += updated_record.voting_power;
+                updated_records.push((user_id, updated_record));
+            }
+            for (user_id, updated_record) in updated_records {
+                user_records.insert(&user_id, &updated_record);
+            }
+            self.user_records.insert(&pool.pool_id, &user_records);
+        }
+        // withdraw penalties are in the balance but in no record, refunds share them among the backers
+        pool.total_voting_power = total_voting_power;
+    }
+
+    // guards and side effects of a status change made by hand through update_pool_status,
+    // the edge itself is already checked against the status graph
+    pub(crate) fn internal_apply_transition(&mut self, pool: &mut PoolMetadata, next: &Status) {
+        match (&pool.status, next) {
+            (Status::INIT, Status::REJECTED) => self.internal_refund_stake(pool),
+            (Status::INIT, Status::CANCELED) => {
+                let stake = pool.staking_amount;
+                self.internal_return_stake(pool, stake);
+            },
+            (Status::APPROVED, Status::FUNDING) if pool.time_end_pledge <= Timestamp::now() => {
+                env::panic_str("Pool has no funding schedule, the creator opens funding");
+            },
+            (Status::APPROVED, Status::FUNDING) if Timestamp::now() < pool.time_start_pledge => {
+                env::panic_str("Pledge period has not started yet");
+            },
+            (Status::FUNDING, _) => {
+                if Timestamp::now() <= pool.time_end_pledge {
+                    env::panic_str("Funding period has not ended yet");
+                }
+
+                // any pool can be refunded, the other results must match the pledged balance
+                let terms = self.internal_funding_terms(pool.pool_id);
+                let result = funding_result(pool, &terms, next == &Status::WAITING);
+                if next != &Status::REFUNDED && next != &result {
+                    env::panic_str(&format!("Funding result of the pool is {:?}", result));
+                }
+
+                if next == &Status::VOTING {
+                    self.internal_settle_overfunding(pool);
+                }
+
+                self.internal_assign_voting_power(pool);
+                if next == &Status::WAITING {
+                    pool.time_end_pledge += terms.waiting_extension;
+                }
+            },
+            (Status::VOTING, _) => self.internal_end_voting(pool, next),
+            _ => {}
+        }
+    }
+
+    // settle the proposal and milestones of a pool leaving VOTING by hand
+    fn internal_end_voting(&mut self, pool: &mut PoolMetadata, next: &Status) {
+        let mut milestones = self.milestones_by_pool_id.get(&pool.pool_id)
+            .expect("Pool has no milestones");
+        let milestone_index = current_milestone_index(&milestones);
+
+        if next == &Status::SUCCESSFUL && milestone_index.is_some() {
+            env::panic_str("Pool has milestones left to vote on");
+        }
+
+        if let Some(mut proposal) = self.proposal_by_pool_id.get(&pool.pool_id) {
+            if proposal.status == ProposalStatus::ACTIVE {
+                proposal.status = match next {
+                    Status::REFUNDED => ProposalStatus::REJECTED,
+                    _ => ProposalStatus::EXPIRED,
+                };
+                self.proposal_by_pool_id.insert(&pool.pool_id, &proposal);
+            }
+        }
+
+        // unreleased tranches go back to the backers through claim_refund
+        if let (Status::REFUNDED, Some(index)) = (next, milestone_index) {
+            milestones[index].status = MilestoneStatus::FAILED;
+            self.milestones_by_pool_id.insert(&pool.pool_id, &milestones);
+            self.internal_slash_stake(pool);
+        }
+
+        if next == &Status::SUCCESSFUL {
+            self.internal_settle_successful_stake(pool);
+        }
+    }
+
+    // record a pledge of `amount` in the pool's token and return the part accepted by the pool,
+    // the caller sends the rest back
+    pub(crate) fn internal_pledge(&mut self, token_id: &AccountId, pool_id: PoolId, sender_id: &AccountId, amount: u128, tier_index: Option<u32>, referrer_id: Option<&AccountId>) -> Result<u128, RefundReason> {
+        let mut pool = self.pool_metadata_by_id.get(&pool_id)
+            .ok_or(RefundReason::PoolNotFound)?;
+
+        if !matches!(pool.status, Status::FUNDING) {
+            return Err(RefundReason::PoolNotFunding);
+        }
+
+        let current_time = Timestamp::now();
+        if current_time < pool.time_start_pledge || current_time > pool.time_end_pledge {
+            return Err(RefundReason::OutsidePledgePeriod);
+        }
+
+        if !pool.tokens.iter().any(|token| &token.token_id == token_id) {
+            return Err(RefundReason::TokenNotAccepted);
+        }
+
+        let mut user_records = self
+            .user_records
+            .get(&pool_id)
+            .unwrap_or_else(|| {
+                let prefix = LaunchpadStorageKey::user_records_prefix(pool_id);
+                UnorderedMap::new(prefix)
+            });
+
+        let mut user_record = user_records.get(sender_id).unwrap_or(UserTokenDepositRecord {
+            amount: 0,
+            voting_power: 0,
+        });
+
+        if pool.total_balance >= pool_hard_cap(&pool) {
+            return Err(RefundReason::HardCapReached);
+        }
+
+        if pool.max_pledge > 0 && user_record.amount >= pool.max_pledge {
+            return Err(RefundReason::MaxPledgeReached);
+        }
+
+        // caps and the pledge multiple apply to the worth of the pledge in the reference unit,
+        // the part above the caps or below the pledge multiple is refunded
+        let value = mul_div_down(amount, self.internal_token_rate(token_id), RATE_DENOMINATOR);
+        let accepted_value = accepted_pledge_amount(&pool, user_record.amount, value);
+        if accepted_value == 0 || user_record.amount + accepted_value < pool.min_pledge {
+            return Err(RefundReason::BelowMinimumPledge);
+        }
+
+        let accepted = if accepted_value == value {
+            amount
+        } else {
+            mul_div_down(amount, accepted_value, value)
+        };
+
+        // the last check, nothing below can fail once the tier is allocated
+        if let Some(tier_index) = tier_index {
+            self.internal_select_tier(pool_id, sender_id, tier_index, user_record.amount + accepted_value)?;
+        }
+
+        let mut token_pledges = self.internal_token_pledges(&pool, sender_id, &user_record);
+        add_token_pledge(&mut token_pledges, token_id, accepted, accepted_value);
+        self.token_pledges.insert(&(pool_id, sender_id.clone()), &token_pledges);
+
+        // only the first pledge of a backer can name the referrer, so the whole pledge counts towards it
+        let referrer_id = referrer_id.filter(|_| user_record.amount == 0);
+        self.internal_add_referred_pledge(pool_id, sender_id, referrer_id, token_id, accepted, accepted_value);
+
+        user_record.amount += accepted_value;
+        user_records.insert(sender_id, &user_record);
+        self.user_records.insert(&pool_id, &user_records);
+        self.internal_add_backer_pool(sender_id, pool_id);
+
+        let pool_token = pool_token_mut(&mut pool, token_id);
+        pool_token.balance += accepted;
+        pool_token.pledged += accepted;
+        pool.total_balance += accepted_value;
+        self.pool_metadata_by_id.insert(&pool_id, &pool);
+
+        Pledge {
+            pool_id,
+            account_id: sender_id,
+            token_id,
+            amount: U128(accepted),
+        }
+        .emit();
+
+        Ok(accepted)
+    }
+
+    // share of `amount` each token's tranche accounts for, by the tranches' worth at the current rates;
+    // the last token takes the rounding so the shares add up to `amount`
+    pub(crate) fn internal_tranche_values(&self, tranches: &[(AccountId, u128)], amount: u128) -> Vec<u128> {
+        let worths: Vec<u128> = tranches.iter()
+            .map(|(token_id, tranche)| mul_div_down(*tranche, self.internal_token_rate(token_id), RATE_DENOMINATOR))
+            .collect();
+        let total_worth: u128 = worths.iter().sum();
+
+        let mut remaining = amount;
+        let mut values = Vec::with_capacity(worths.len());
+        for (index, worth) in worths.iter().enumerate() {
+            let value = if index == worths.len() - 1 || total_worth == 0 {
+                remaining
+            } else {
+                mul_div_down(amount, *worth, total_worth)
+            };
+            remaining -= value;
+            values.push(value);
+        }
+
+        values
+    }
+
+    // allocate a reward tier to a backer whose pledge reaches its minimum, giving up the tier held before
+    pub(crate) fn internal_select_tier(&mut self, pool_id: PoolId, account_id: &AccountId, tier_index: u32, pledged: u128) -> Result<(), RefundReason> {
+        let key = (pool_id, account_id.clone());
+        let previous_tier_index = self.backer_tiers.get(&key);
+        if previous_tier_index == Some(tier_index) {
+            return Ok(());
+        }
+
+        let mut tiers = self.reward_tiers_by_pool_id.get(&pool_id).unwrap_or_default();
+        let tier = tiers.get_mut(tier_index as usize).ok_or(RefundReason::TierNotFound)?;
+
+        if pledged < tier.min_pledge {
+            return Err(RefundReason::BelowTierMinimum);
+        }
+
+        if tier.quantity > 0 && tier.allocated >= tier.quantity {
+            return Err(RefundReason::TierSoldOut);
+        }
+
+        tier.allocated += 1;
+        if let Some(previous_tier_index) = previous_tier_index {
+            tiers[previous_tier_index as usize].allocated -= 1;
+        }
+
+        self.reward_tiers_by_pool_id.insert(&pool_id, &tiers);
+        self.backer_tiers.insert(&key, &tier_index);
+
+        RewardTierSelected {
+            pool_id,
+            account_id,
+            tier_index,
+            previous_tier_index,
+        }
+        .emit();
+
+        Ok(())
+    }
+
+    // whether the backer may pledge to the pool, a valid signature puts the backer on the pool's allowlist
+    pub(crate) fn internal_check_pool_access(&mut self, pool_id: PoolId, account_id: &AccountId, signature: Option<&str>) -> Result<(), RefundReason> {
+        let access = self.access_by_pool_id.get(&pool_id).unwrap_or(PoolAccess::Public);
+        if access == PoolAccess::Public {
+            return Ok(());
+        }
+
+        let mut allowlist = self.allowlist_by_pool_id.get(&pool_id)
+            .unwrap_or_else(|| UnorderedSet::new(LaunchpadStorageKey::allowlist_prefix(pool_id)));
+        if allowlist.contains(account_id) {
+            return Ok(());
+        }
+
+        if access == PoolAccess::Allowlist {
+            return Err(RefundReason::NotAllowlisted);
+        }
+
+        let signature = signature.ok_or(RefundReason::SignatureRequired)?;
+        let public_key = self.pledge_signer_key.as_deref().ok_or(RefundReason::InvalidSignature)?;
+        if !verify_pledge_signature(public_key, pool_id, account_id, signature) {
+            return Err(RefundReason::InvalidSignature);
+        }
+
+        allowlist.insert(account_id);
+        self.allowlist_by_pool_id.insert(&pool_id, &allowlist);
+
+        Ok(())
+    }
+
+    // count a pledge towards the backer's referrer, attaching the backer to `referrer_id` when it has none yet;
+    // a backer cannot refer itself
+    pub(crate) fn internal_add_referred_pledge(&mut self, pool_id: PoolId, backer_id: &AccountId, referrer_id: Option<&AccountId>, token_id: &AccountId, amount: u128, value: u128) {
+        let key = (pool_id, backer_id.clone());
+        let mut referrals = self.referrals_by_pool_id.get(&pool_id)
+            .unwrap_or_else(|| UnorderedMap::new(LaunchpadStorageKey::referrals_prefix(pool_id)));
+
+        let (referrer_id, mut stats) = match (self.referrer_by_backer.get(&key), referrer_id) {
+            (Some(referrer_id), _) => {
+                let stats = referrals.get(&referrer_id).expect("Referrer has no stats in this pool");
+                (referrer_id, stats)
+            },
+            (None, Some(referrer_id)) if referrer_id != backer_id => {
+                let mut stats = referrals.get(referrer_id).unwrap_or_else(|| ReferralStats {
+                    referrer_id: referrer_id.clone(),
+                    backers: 0,
+                    pledged: 0,
+                    tokens: Vec::new(),
+                    rewarded_tokens: Vec::new(),
+                });
+                stats.backers += 1;
+                self.referrer_by_backer.insert(&key, referrer_id);
+
+                ReferralRecorded {
+                    pool_id,
+                    account_id: backer_id,
+                    referrer_id,
+                }
+                .emit();
+
+                (referrer_id.clone(), stats)
+            },
+            _ => return,
+        };
+
+        add_token_pledge(&mut stats.tokens, token_id, amount, value);
+        stats.pledged += value;
+
+        referrals.insert(&referrer_id, &stats);
+        self.referrals_by_pool_id.insert(&pool_id, &referrals);
+
+        let key = (pool_id, token_id.clone());
+        let mut reserve = self.referral_reserves.get(&key).unwrap_or_default();
+        reserve.referred += amount;
+        self.referral_reserves.insert(&key, &reserve);
+    }
+
+    // take a withdrawn part of a referred pledge out of the referrer's stats
+    pub(crate) fn internal_remove_referred_pledge(&mut self, pool_id: PoolId, backer_id: &AccountId, part: &TokenPledge) {
+        let referrer_id = match self.referrer_by_backer.get(&(pool_id, backer_id.clone())) {
+            Some(referrer_id) => referrer_id,
+            None => return,
+        };
+
+        let mut referrals = self.referrals_by_pool_id.get(&pool_id)
+            .expect("Pool has no referrals");
+        let mut stats = referrals.get(&referrer_id)
+            .expect("Referrer has no stats in this pool");
+
+        if let Some(token) = stats.tokens.iter_mut().find(|token| token.token_id == part.token_id) {
+            token.amount -= part.amount;
+            token.value -= part.value;
+        }
+        stats.tokens.retain(|token| token.amount > 0);
+        stats.pledged -= part.value;
+
+        referrals.insert(&referrer_id, &stats);
+        self.referrals_by_pool_id.insert(&pool_id, &referrals);
+
+        let key = (pool_id, part.token_id.clone());
+        if let Some(mut reserve) = self.referral_reserves.get(&key) {
+            reserve.referred -= part.amount;
+            self.referral_reserves.insert(&key, &reserve);
+        }
+    }
+
+    // give the backer's reward tier back once the pledge left in the pool is below its minimum,
+    // returns the released tier
+    pub(crate) fn internal_release_tier(&mut self, pool_id: PoolId, account_id: &AccountId, pledged: u128) -> Option<u32> {
+        let key = (pool_id, account_id.clone());
+        let tier_index = self.backer_tiers.get(&key)?;
+
+        let mut tiers = self.reward_tiers_by_pool_id.get(&pool_id).unwrap_or_default();
+        let tier = &mut tiers[tier_index as usize];
+        if pledged >= tier.min_pledge {
+            return None;
+        }
+
+        tier.allocated -= 1;
+        self.reward_tiers_by_pool_id.insert(&pool_id, &tiers);
+        self.backer_tiers.remove(&key);
+
+        RewardTierReleased {
+            pool_id,
+            account_id,
+            tier_index,
+        }
+        .emit();
+
+        Some(tier_index)
+    }
+
+    // hand a tier released for a transfer that failed back to the backer, the slot was theirs
+    // so the quantity is not checked again, a tier selected since is kept
+    pub(crate) fn internal_restore_tier(&mut self, pool_id: PoolId, account_id: &AccountId, tier_index: u32) {
+        let key = (pool_id, account_id.clone());
+        if self.backer_tiers.get(&key).is_some() {
+            return;
+        }
+
+        let mut tiers = self.reward_tiers_by_pool_id.get(&pool_id).unwrap_or_default();
+        tiers[tier_index as usize].allocated += 1;
+        self.reward_tiers_by_pool_id.insert(&pool_id, &tiers);
+        self.backer_tiers.insert(&key, &tier_index);
+
+        RewardTierSelected {
+            pool_id,
+            account_id,
+            tier_index,
+            previous_tier_index: None,
+        }
+        .emit();
+    }
+
+    // terms the pool is held to, the contract's with the pool's overrides applied
+    pub(crate) fn internal_funding_terms(&self, pool_id: PoolId) -> FundingTerms {
+        match self.funding_terms_by_pool_id.get(&pool_id) {
+            Some(pool_terms) => pool_terms.apply(&self.funding_terms),
+            None => self.funding_terms.clone(),
+        }
+    }
+
+    // reference units per smallest unit of the token scaled by RATE_DENOMINATOR, 1:1 unless set
+    pub(crate) fn internal_token_rate(&self, token_id: &AccountId) -> u128 {
+        self.token_rates.get(token_id).unwrap_or(RATE_DENOMINATOR)
+    }
+
+    // per-token parts of a backer's pledge, records written before multi-token pools
+    // were pledged in the pool's token at 1:1
+    pub(crate) fn internal_token_pledges(&self, pool: &PoolMetadata, account_id: &AccountId, record: &UserTokenDepositRecord) -> Vec<TokenPledge> {
+        self.token_pledges.get(&(pool.pool_id, account_id.clone())).unwrap_or_else(|| {
+            if record.amount == 0 {
+                Vec::new()
+            } else {
+                vec![TokenPledge { token_id: pool.token_id.clone(), amount: record.amount, value: record.amount }]
+            }
+        })
+    }
+
+    // fee of the token in basis points, the platform-wide fee unless the token has its own
+    pub(crate) fn internal_fee_bps(&self, token_id: &AccountId) -> u16 {
+        self.fee_bps_by_token.get(token_id).unwrap_or(self.platform_fee_bps)
+    }
+
+    // reward owed on referred pledges in a token, never above the token's platform fee
+    pub(crate) fn internal_referral_reward(&self, token_id: &AccountId, referred: u128) -> u128 {
+        let reward_bps = self.referral_reward_bps.min(self.internal_fee_bps(token_id));
+        mul_div_down(referred, reward_bps as u128, BPS_DENOMINATOR)
+    }
+
+    // set aside the part of a tranche's fee the pool still owes its referrers, returns what is left to the platform
+    pub(crate) fn internal_reserve_referral_rewards(&mut self, pool_id: PoolId, token_id: &AccountId, fee: u128) -> u128 {
+        let key = (pool_id, token_id.clone());
+        let mut reserve = match self.referral_reserves.get(&key) {
+            Some(reserve) => reserve,
+            None => return fee,
+        };
+
+        let owed = self.internal_referral_reward(token_id, reserve.referred);
+        let reserved = owed.saturating_sub(reserve.reserved).min(fee);
+        reserve.reserved += reserved;
+        self.referral_reserves.insert(&key, &reserve);
+
+        fee - reserved
+    }
+
+    pub(crate) fn internal_asset_mut(&mut self, token_id: &AccountId) -> &mut Assets {
+        self.list_assets.iter_mut()
+            .find(|asset| &asset.token_id == token_id)
+            .unwrap_or_else(|| env::panic_str("Token is not supported"))
+    }
+
+    // pay the keeper reward to the caller out of the treasury, skipped when the treasury cannot cover it
+    pub(crate) fn internal_reward_keeper(&mut self, pool_id: PoolId) {
+        if self.keeper_reward == 0 || self.treasury.balance < self.keeper_reward {
+            return;
+        }
+
+        self.treasury.balance -= self.keeper_reward;
+        self.treasury.keeper_rewards_paid += self.keeper_reward;
+
+        let keeper_id = env::predecessor_account_id();
+        Promise::new(keeper_id.clone())
+            .transfer(self.keeper_reward);
+
+        KeeperRewarded {
+            pool_id,
+            account_id: &keeper_id,
+            amount: U128(self.keeper_reward),
+        }
+        .emit();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CASES: usize = 500;
+    const BACKER_COUNTS: [usize; 8] = [1, 2, 3, 7, 16, 50, 128, 300];
+
+    // xorshift generator so every run checks the same cases and a failure can be replayed by its seed
+    struct Rng(u64);
+
+    impl Rng {
+        fn next(&mut self) -> u64 {
+            self.0 ^= self.0 << 13;
+            self.0 ^= self.0 >> 7;
+            self.0 ^= self.0 << 17;
+            self.0
+        }
+
+        fn below(&mut self, bound: u64) -> u64 {
+            self.next() % bound
+        }
+
+        // amount in 1..=max, biased towards 1 yocto and the maximum
+        fn amount(&mut self, max: u128) -> u128 {
+            match self.below(4) {
+                0 => 1,
+                1 => max,
+                _ => (((self.next() as u128) << 64) | self.next() as u128) % max + 1,
+            }
+        }
+
+        // amount in 0..=max, biased towards the bounds
+        fn up_to(&mut self, max: u128) -> u128 {
+            match self.below(4) {
+                0 => 0,
+                1 => max,
+                _ => self.amount(max.max(1)).min(max),
+            }
+        }
+    }
+
+    fn token(index: usize) -> AccountId {
+        format!("token{}.test.near", index).parse().unwrap()
+    }
+
+    fn refunded_pool(tokens: Vec<PoolToken>, total_balance: u128, total_voting_power: u128) -> PoolMetadata {
+        PoolMetadata {
+            pool_id: 1,
+            campaign_id: "campaign".to_string(),
+            creator_id: "creator.test.near".parse().unwrap(),
+            staking_amount: 0,
+            status: Status::REFUNDED,
+            token_id: tokens[0].token_id.clone(),
+            total_balance,
+            target_funding: 1,
+            time_init: Timestamp::default(),
+            time_start_pledge: Timestamp::default(),
+            time_end_pledge: Timestamp::default(),
+            funding_duration_days: 1,
+            min_multiple_pledge: 1,
+            total_voting_power,
+            min_pledge: 0,
+            max_pledge: 0,
+            hard_cap: 0,
+            withdraw_penalty_percent: 0,
+            tokens,
+        }
+    }
+
+    #[test]
+    fn mul_div_down_matches_exact_division() {
+        let mut rng = Rng(0x9E37_79B9_7F4A_7C15);
+        for _ in 0..CASES * 10 {
+            // shares are taken with y at most the denominator, so the result fits in x
+            let x = rng.up_to(u128::MAX);
+            let denominator = rng.amount(u128::MAX);
+            let y = rng.up_to(denominator);
+
+            let result = mul_div_down(x, y, denominator);
+            if let Some(product) = x.checked_mul(y) {
+                assert_eq!(result, product / denominator, "{} * {} / {}", x, y, denominator);
+            }
+            assert!(result <= x, "{} * {} / {} should not exceed {}", x, y, denominator, x);
+            assert!(y == denominator || result < x || x == 0, "{} * {} / {} should be below {}", x, y, denominator, x);
+        }
+
+        assert_eq!(mul_div_down(u128::MAX, u128::MAX, u128::MAX), u128::MAX);
+        assert_eq!(mul_div_down(u128::MAX, u128::MAX - 1, u128::MAX), u128::MAX - 1);
+        assert_eq!(mul_div_down(1, 1, u128::MAX), 0);
+        assert_eq!(mul_div_down(1, u128::MAX, u128::MAX), 1);
+    }
+
+    // backers pledge random amounts in up to three tokens and claim in random order; whatever the pool
+    // holds against the pledges, the refunds and the dust left behind add up to the balance exactly
+    #[test]
+    fn refunds_and_dust_sum_to_pool_balance() {
+        let mut rng = Rng(0xD1B5_4A32_D192_ED03);
+        for case in 0..CASES {
+            let backer_count = BACKER_COUNTS[case % BACKER_COUNTS.len()];
+            let token_count = 1 + rng.below(3) as usize;
+            // no token total or voting power total can overflow
+            let max_amount = u128::MAX / (backer_count * 3) as u128;
+
+            let mut pledges: Vec<Vec<TokenPledge>> = Vec::with_capacity(backer_count);
+            for _ in 0..backer_count {
+                let mut backer_pledges = Vec::new();
+                for index in 0..token_count {
+                    if index > 0 && rng.below(2) == 0 {
+                        continue;
+                    }
+                    let amount = rng.amount(max_amount);
+                    // worth at a rate of 1, 1/2 or 1/1000 of the reference unit, never 0
+                    let value = (amount / [1, 2, 1_000][rng.below(3) as usize]).max(1);
+                    backer_pledges.push(TokenPledge { token_id: token(index), amount, value });
+                }
+                pledges.push(backer_pledges);
+            }
+
+            // the pool may hold less than pledged after released tranches or more after withdraw penalties
+            let tokens: Vec<PoolToken> = (0..token_count)
+                .map(|index| {
+                    let pledged: u128 = pledges.iter().flatten()
+                        .filter(|pledge| pledge.token_id == token(index))
+                        .map(|pledge| pledge.amount)
+                        .sum();
+                    let balance = match rng.below(3) {
+                        0 => pledged,
+                        1 => rng.up_to(pledged),
+                        _ => pledged.saturating_add(rng.up_to(pledged)),
+                    };
+                    PoolToken { token_id: token(index), balance, pledged }
+                })
+                .collect();
+            let total_voting_power: u128 = pledges.iter().flatten().map(|pledge| pledge.value).sum();
+            let total_balance = match rng.below(3) {
+                0 => total_voting_power,
+                1 => rng.up_to(total_voting_power),
+                _ => total_voting_power.saturating_add(rng.up_to(total_voting_power)),
+            };
+
+            let mut pool = refunded_pool(tokens.clone(), total_balance, total_voting_power);
+            let mut refunded = vec![0u128; token_count];
+            let mut refunded_value = 0u128;
+
+            // claim in a random order
+            let mut order: Vec<usize> = (0..backer_count).collect();
+            for index in (1..order.len()).rev() {
+                order.swap(index, rng.below(index as u64 + 1) as usize);
+            }
+
+            for backer in order {
+                let refunds = take_refund(&mut pool, &pledges[backer]);
+                assert_eq!(refunds.len(), pledges[backer].len());
+
+                for (pledge, (amount, value)) in pledges[backer].iter().zip(refunds) {
+                    let index = tokens.iter().position(|token| token.token_id == pledge.token_id).unwrap();
+                    let pool_token = &tokens[index];
+
+                    // never below the pro-rata share of the initial balance, and the rounding
+                    // handed on by earlier claims is less than one yocto per backer
+                    let share = mul_div_down(pledge.amount, pool_token.balance, pool_token.pledged);
+                    assert!(amount >= share, "case {}: refund {} below its share {}", case, amount, share);
+                    assert!(amount - share < backer_count as u128, "case {}: refund {} too far above its share {}", case, amount, share);
+
+                    refunded[index] += amount;
+                    refunded_value += value;
+                }
+
+                for (index, pool_token) in pool.tokens.iter().enumerate() {
+                    assert_eq!(refunded[index] + pool_token.balance, tokens[index].balance, "case {}: token {} is not conserved", case, index);
+                }
+                assert_eq!(refunded_value + pool.total_balance, total_balance, "case {}: pool worth is not conserved", case);
+            }
+
+            for pool_token in pool.tokens.iter() {
+                assert_eq!(pool_token.balance, 0, "case {}: dust left in {}", case, pool_token.token_id);
+                assert_eq!(pool_token.pledged, 0, "case {}: pledges left in {}", case, pool_token.token_id);
+            }
+            assert_eq!(pool.total_balance, 0, "case {}: worth left in the pool", case);
+            assert_eq!(pool.total_voting_power, 0, "case {}: voting power left in the pool", case);
+        }
+    }
+
+    #[test]
+    fn edge_pledges_are_refunded() {
+        let tokens = vec![PoolToken { token_id: token(0), balance: 1, pledged: 3 }];
+        let mut pool = refunded_pool(tokens, 1, 3);
+        let pledge = vec![TokenPledge { token_id: token(0), amount: 1, value: 1 }];
+
+        assert_eq!(take_refund(&mut pool, &pledge), vec![(0, 0)]);
+        assert_eq!(take_refund(&mut pool, &pledge), vec![(0, 0)]);
+        assert_eq!(take_refund(&mut pool, &pledge), vec![(1, 1)], "Last backer should take the yocto left.");
+
+        let tokens = vec![PoolToken { token_id: token(0), balance: u128::MAX, pledged: u128::MAX }];
+        let mut pool = refunded_pool(tokens, u128::MAX, u128::MAX);
+        let pledges = [
+            vec![TokenPledge { token_id: token(0), amount: u128::MAX - 1, value: u128::MAX - 1 }],
+            vec![TokenPledge { token_id: token(0), amount: 1, value: 1 }],
+        ];
+
+        assert_eq!(take_refund(&mut pool, &pledges[0]), vec![(u128::MAX - 1, u128::MAX - 1)]);
+        assert_eq!(take_refund(&mut pool, &pledges[1]), vec![(1, 1)]);
+        assert_eq!(pool.tokens[0].balance, 0);
+    }
+
+    // voting power shares decide the same way whatever the scale of the pool
+    #[test]
+    fn tally_outcome_follows_majority_and_quorum() {
+        let mut rng = Rng(0x2545_F491_4F6C_DD1D);
+        for case in 0..CASES * 10 {
+            let total_power = if case % 10 == 0 { 0 } else { rng.up_to(u128::MAX).max(1) };
+            let approve_power = rng.up_to(total_power);
+            let reject_power = rng.up_to(total_power - approve_power);
+            let quorum_percent = 1 + rng.below(100) as u8;
+            let voting_ended = rng.below(2) == 0;
+
+            let outcome = tally_outcome(total_power, approve_power, reject_power, quorum_percent, voting_ended);
+            let quorum_reached = approve_power + reject_power >= mul_div_down(total_power, quorum_percent as u128, 100);
+            let approve_majority = approve_power > total_power - approve_power;
+            let reject_majority = reject_power >= total_power - reject_power;
+
+            if total_power == 0 {
+                let expected = if voting_ended { Some(ProposalStatus::EXPIRED) } else { None };
+                assert_eq!(outcome, expected, "case {}: a pool without voting power can only expire", case);
+                continue;
+            }
+
+            match outcome {
+                Some(ProposalStatus::APPROVED) => {
+                    assert!(approve_power > reject_power, "case {}: approved without more approvals", case);
+                    assert!(approve_majority || (voting_ended && quorum_reached), "case {}: approved too early", case);
+                },
+                Some(ProposalStatus::REJECTED) => {
+                    assert!(!approve_majority, "case {}: rejected against a majority", case);
+                    assert!(reject_majority || (voting_ended && quorum_reached), "case {}: rejected too early", case);
+                },
+                Some(ProposalStatus::EXPIRED) => {
+                    assert!(voting_ended && !quorum_reached, "case {}: expired with a quorum", case);
+                    assert!(!approve_majority && !reject_majority, "case {}: expired with a majority", case);
+                },
+                Some(ProposalStatus::ACTIVE) => panic!("case {}: tally cannot keep a proposal active", case),
+                None => {
+                    assert!(!voting_ended, "case {}: undecided after the deadline", case);
+                    assert!(!approve_majority && !reject_majority, "case {}: undecided with a majority", case);
+                },
+            }
+        }
+    }
+}
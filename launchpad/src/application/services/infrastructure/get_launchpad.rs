@@ -1,7 +1,12 @@
-use near_sdk::{near_bindgen, AccountId, json_types::U128, env};
+use near_sdk::{near_bindgen, AccountId, json_types::U128};
 
+use crate::application::repository::{page_limit, pool_matches_filter, read_state_version};
 use crate::models::{
-    contract::{Launchpad, LaunchpadGet, LaunchpadExt, PoolMetadata, Status, UserTokenDepositRecord, UserRecordDetail}, 
+    contract::{
+        FundingTerms, Launchpad, LaunchpadGet, LaunchpadExt, PoolMetadata, Status, UserRecordDetail,
+        Proposal, Vote, Milestone, PoolFilter, BackerPoolDetail, TokenPledge
+    }, 
+    time::{Duration, Timestamp},
     PoolId
 };
 
@@ -16,28 +21,19 @@ impl LaunchpadGet for Launchpad {
         self.list_assets.iter().any(|asset| asset.token_id == token_id)
     }
 
-    fn get_all_pool(&self) -> Option<Vec<PoolMetadata>> {
-        if self.all_pool_id.is_empty() {
-            return None;
-        }
-        let pools: Vec<PoolMetadata> = self.all_pool_id.iter()
-            .filter_map(|pool_id| self.pool_metadata_by_id.get(&pool_id))
-            .collect();
-        if pools.is_empty() {
-            None
-        } else {
-            Some(pools)
-        }
+    fn get_number_of_pools(&self) -> u64 {
+        self.all_pool_id.len()
     }
-    
-    fn get_pools_by_status(&self, status_str: String) -> Option<Vec<PoolMetadata>> {
-        if self.all_pool_id.is_empty() {
-            return None;
-        }
 
+    fn get_all_pool(&self, from_index: Option<u64>, limit: Option<u64>) -> Option<Vec<PoolMetadata>> {
+        self.get_pools(PoolFilter::default(), from_index, limit)
+    }
+    
+    fn get_pools_by_status(&self, status_str: String, from_index: Option<u64>, limit: Option<u64>) -> Option<Vec<PoolMetadata>> {
         let status = match status_str.as_str() {
             "FUNDING" => Status::FUNDING,
             "INIT" => Status::INIT,
+            "APPROVED" => Status::APPROVED,
             "CLOSED" => Status::CLOSED,
             "WAITING" => Status::WAITING,
             "REJECTED" => Status::REJECTED,
@@ -49,14 +45,43 @@ impl LaunchpadGet for Launchpad {
             _ => return None,
         };
 
-        let pools: Vec<PoolMetadata> = self.all_pool_id.iter()
+        let filter = PoolFilter {
+            status: Some(status),
+            ..PoolFilter::default()
+        };
+        self.get_pools(filter, from_index, limit)
+    }
+
+    // scans `limit` pools starting at `from_index` in creation order and returns those matching the filter,
+    // page through with `from_index += limit` until `get_number_of_pools` is reached
+    fn get_pools(&self, filter: PoolFilter, from_index: Option<u64>, limit: Option<u64>) -> Option<Vec<PoolMetadata>> {
+        let pool_ids = self.all_pool_id.as_vector();
+        let start = from_index.unwrap_or(0);
+        let end = start.saturating_add(page_limit(limit)).min(pool_ids.len());
+
+        let pools: Vec<PoolMetadata> = (start..end)
+            .filter_map(|index| pool_ids.get(index))
+            .filter_map(|pool_id| self.pool_metadata_by_id.get(&pool_id))
+            .filter(|pool| pool_matches_filter(pool, &filter))
+            .collect();
+
+        if pools.is_empty() {
+            None
+        } else {
+            Some(pools)
+        }
+    }
+
+    fn get_pools_by_backer(&self, account_id: AccountId, from_index: Option<u64>, limit: Option<u64>) -> Option<Vec<BackerPoolDetail>> {
+        let pool_ids = self.pools_by_backer.get(&account_id)?;
+        let pools: Vec<BackerPoolDetail> = pool_ids
+            .iter()
+            .skip(from_index.unwrap_or(0) as usize)
+            .take(page_limit(limit) as usize)
             .filter_map(|pool_id| {
                 let pool = self.pool_metadata_by_id.get(&pool_id)?;
-                if pool.status == status {
-                    Some(pool)
-                } else {
-                    None
-                }
+                let record = self.user_records.get(&pool_id).and_then(|records| records.get(&account_id));
+                Some(BackerPoolDetail { pool, record })
             })
             .collect();
 
@@ -87,27 +112,80 @@ impl LaunchpadGet for Launchpad {
         U128(self.min_staking_amount)
     }
 
-    fn get_user_records_by_pool_id(&self, pool_id: PoolId) -> Option<Vec<UserRecordDetail>> {
-        if let Some(user_records) = self.user_records.get(&pool_id) {
-            let records: Vec<UserRecordDetail> = user_records
-                .iter()
-                .map(|(user_id, record)| UserRecordDetail {
-                    user_id,
-                    record: record.clone(),
-                })
-                .collect();
-
-            if records.is_empty() {
-                None
-            } else {
-                Some(records)
-            }
-        } else {
+    fn get_user_records_by_pool_id(&self, pool_id: PoolId, from_index: Option<u64>, limit: Option<u64>) -> Option<Vec<UserRecordDetail>> {
+        let user_records = self.user_records.get(&pool_id)?;
+        let keys = user_records.keys_as_vector();
+        let values = user_records.values_as_vector();
+        let start = from_index.unwrap_or(0);
+        let end = start.saturating_add(page_limit(limit)).min(keys.len());
+
+        let records: Vec<UserRecordDetail> = (start..end)
+            .filter_map(|index| Some(UserRecordDetail {
+                user_id: keys.get(index)?,
+                record: values.get(index)?,
+            }))
+            .collect();
+
+        if records.is_empty() {
             None
+        } else {
+            Some(records)
+        }
+    }
+
+    fn get_token_pledges(&self, pool_id: PoolId, account_id: AccountId) -> Vec<TokenPledge> {
+        let pool = match self.pool_metadata_by_id.get(&pool_id) {
+            Some(pool) => pool,
+            None => return Vec::new(),
+        };
+
+        self.user_records.get(&pool_id)
+            .and_then(|user_records| user_records.get(&account_id))
+            .map_or_else(Vec::new, |record| self.internal_token_pledges(&pool, &account_id, &record))
+    }
+
+    fn get_current_timestamp(&self) -> Timestamp {
+        Timestamp::now()
+    }
+
+    fn get_proposal(&self, pool_id: PoolId) -> Option<Proposal> {
+        self.proposal_by_pool_id.get(&pool_id)
+    }
+
+    fn get_vote(&self, pool_id: PoolId, account_id: AccountId) -> Option<Vote> {
+        self.votes_by_pool_id.get(&pool_id).and_then(|votes| votes.get(&account_id))
+    }
+
+    fn get_milestones(&self, pool_id: PoolId) -> Option<Vec<Milestone>> {
+        self.milestones_by_pool_id.get(&pool_id)
+    }
+
+    // get quorum percentage of total voting power right now
+    fn get_voting_quorum_percent(&self) -> u8 {
+        self.voting_quorum_percent
+    }
+
+    // funding terms of the pools that override none of them
+    fn get_funding_terms(&self) -> FundingTerms {
+        self.funding_terms.clone()
+    }
+
+    // funding terms the pool is held to, its overrides on top of the contract's
+    fn get_pool_funding_terms(&self, pool_id: PoolId) -> Option<FundingTerms> {
+        if !self.pool_metadata_by_id.contains_key(&pool_id) {
+            return None;
         }
+
+        Some(self.internal_funding_terms(pool_id))
+    }
+
+    // get voting duration in nanoseconds right now
+    fn get_voting_duration(&self) -> Duration {
+        self.voting_duration
     }
 
-    fn get_current_timestamp(&self) -> u64 {
-        env::block_timestamp()
+    // layout version of the stored state
+    fn get_state_version(&self) -> u8 {
+        read_state_version()
     }
 }
\ No newline at end of file
@@ -0,0 +1,96 @@
+use near_sdk::{collections::UnorderedSet, env, near_bindgen, AccountId};
+
+use crate::models::events::{AdminTransfer, RoleChanged};
+use crate::models::contract::{Launchpad, LaunchpadAccess, LaunchpadExt, LaunchpadStorageKey, Role};
+
+#[near_bindgen]
+impl LaunchpadAccess for Launchpad {
+
+    /* //////////////////////////////////////////////////////////////
+                            ADMIN FUNCTIONS
+    ////////////////////////////////////////////////////////////// */
+    // admin grants a role to an account
+    fn grant_role(&mut self, role: Role, account_id: AccountId) {
+        self.assert_owner();
+
+        let mut members = self.role_members.get(&role).unwrap_or_else(|| {
+            UnorderedSet::new(LaunchpadStorageKey::role_members_prefix(&role))
+        });
+
+        if !members.insert(&account_id) {
+            env::panic_str("Account already has this role.");
+        }
+        self.role_members.insert(&role, &members);
+
+        RoleChanged {
+            role: &role,
+            account_id: &account_id,
+        }
+        .emit_granted();
+    }
+
+    // admin revokes a role from an account
+    fn revoke_role(&mut self, role: Role, account_id: AccountId) {
+        self.assert_owner();
+
+        let mut members = self.role_members.get(&role)
+            .expect("Account does not have this role.");
+
+        if !members.remove(&account_id) {
+            env::panic_str("Account does not have this role.");
+        }
+        self.role_members.insert(&role, &members);
+
+        RoleChanged {
+            role: &role,
+            account_id: &account_id,
+        }
+        .emit_revoked();
+    }
+
+    // account proposed by change_admin takes over the ownership
+    fn accept_admin(&mut self) {
+        let caller_id = env::predecessor_account_id();
+
+        if self.pending_owner_id.as_ref() != Some(&caller_id) {
+            env::panic_str("Only the proposed admin can accept the ownership.");
+        }
+
+        let old_owner_id = std::mem::replace(&mut self.owner_id, caller_id);
+        self.pending_owner_id = None;
+
+        AdminTransfer {
+            old_owner_id: &old_owner_id,
+            new_owner_id: &self.owner_id,
+        }
+        .emit_accepted();
+    }
+
+    /* //////////////////////////////////////////////////////////////
+                            GETTER FUNCTIONS
+    ////////////////////////////////////////////////////////////// */
+
+    fn has_role(&self, role: Role, account_id: AccountId) -> bool {
+        self.internal_has_role(&role, &account_id)
+    }
+
+    fn get_role_members(&self, role: Role) -> Vec<AccountId> {
+        self.role_members.get(&role).map_or_else(Vec::new, |members| members.to_vec())
+    }
+
+    // roles granted to the account, the admin holds all of them implicitly
+    fn get_roles(&self, account_id: AccountId) -> Vec<Role> {
+        Role::ALL
+            .into_iter()
+            .filter(|role| self.internal_has_role(role, &account_id))
+            .collect()
+    }
+
+    fn get_owner(&self) -> AccountId {
+        self.owner_id.clone()
+    }
+
+    fn get_pending_owner(&self) -> Option<AccountId> {
+        self.pending_owner_id.clone()
+    }
+}
@@ -0,0 +1,122 @@
+use near_sdk::{env, json_types::U128, near_bindgen, serde_json::json};
+
+use crate::application::repository::{current_milestone_index, is_milestone_expired};
+use crate::models::events::{ConfigChanged, PoolStatusChanged};
+use crate::models::{
+    contract::{
+        Launchpad, LaunchpadExt, LaunchpadKeeper, LaunchpadVoting, PoolMetadata, ProposalStatus, Role, Status
+    },
+    time::Timestamp,
+    PoolId
+};
+
+#[near_bindgen]
+impl LaunchpadKeeper for Launchpad {
+
+    /* //////////////////////////////////////////////////////////////
+                            KEEPER FUNCTIONS
+    ////////////////////////////////////////////////////////////// */
+    // anyone can apply the transition a pool is due for by its timestamps, a scheduled pool opens for pledges
+    // once time_start_pledge passes; only the transitions settling the backers' funds earn the keeper reward
+    fn advance_pool(&mut self, pool_id: PoolId) -> PoolMetadata {
+        let mut pool = self.pool_metadata_by_id.get(&pool_id)
+            .expect("Pool does not exist");
+
+        let current_time = Timestamp::now();
+        let old_status = pool.status.clone();
+
+        // the expired stake refund and the scheduled opening are bookkeeping the creator triggers for free
+        let rewarded = match pool.status {
+            Status::INIT if current_time > pool.time_init + self.internal_funding_terms(pool_id).init_expiry => {
+                pool.status = Status::REJECTED;
+                self.internal_refund_stake(&mut pool);
+                false
+            },
+            Status::APPROVED if pool.time_end_pledge.is_set() && current_time >= pool.time_start_pledge => {
+                pool.status = Status::FUNDING;
+                false
+            },
+            Status::FUNDING if current_time > pool.time_end_pledge => {
+                // a pool close to its target waits for the creator to accept it
+                self.internal_finish_funding(&mut pool, true);
+                true
+            },
+            Status::WAITING if current_time > pool.time_end_pledge => {
+                pool.status = Status::REFUNDED;
+                true
+            },
+            Status::VOTING => {
+                let proposal = self.proposal_by_pool_id.get(&pool_id)
+                    .filter(|proposal| proposal.status == ProposalStatus::ACTIVE);
+
+                // the voting transitions store the pool and emit their own events
+                let (pool, rewarded) = match proposal {
+                    Some(proposal) if current_time > proposal.time_end_voting => {
+                        let pool = self.tally_votes(pool_id);
+                        // a missed quorum settles nothing, the creator could otherwise farm rewards with empty proposals
+                        let expired = self.proposal_by_pool_id.get(&pool_id)
+                            .is_some_and(|proposal| proposal.status == ProposalStatus::EXPIRED);
+                        (pool, !expired)
+                    },
+                    Some(_) => env::panic_str("Voting period has not ended yet"),
+                    None => {
+                        let milestones = self.milestones_by_pool_id.get(&pool_id)
+                            .expect("Pool has no milestones");
+                        let expired = current_milestone_index(&milestones)
+                            .is_some_and(|index| is_milestone_expired(&milestones[index], current_time));
+                        if !expired {
+                            env::panic_str("Pool has no transition due");
+                        }
+                        (self.fail_expired_milestone(pool_id), true)
+                    }
+                };
+
+                if rewarded {
+                    self.internal_reward_keeper(pool_id);
+                }
+                return pool;
+            },
+            _ => env::panic_str("Pool has no transition due"),
+        };
+
+        self.pool_metadata_by_id.insert(&pool_id, &pool);
+
+        PoolStatusChanged {
+            pool_id,
+            old_status: &old_status,
+            new_status: &pool.status,
+        }
+        .emit();
+
+        if rewarded {
+            self.internal_reward_keeper(pool_id);
+        }
+
+        pool
+    }
+
+    /* //////////////////////////////////////////////////////////////
+                            ADMIN FUNCTIONS
+    ////////////////////////////////////////////////////////////// */
+    // treasury manager sets the NEAR paid per advanced pool, zero turns the reward off
+    fn set_keeper_reward(&mut self, amount: U128) {
+        self.assert_role(Role::TreasuryManager);
+
+        self.keeper_reward = amount.0;
+
+        ConfigChanged {
+            pool_id: None,
+            setting: "keeper_reward",
+            value: json!(amount),
+        }
+        .emit();
+    }
+
+    /* //////////////////////////////////////////////////////////////
+                            GETTER FUNCTIONS
+    ////////////////////////////////////////////////////////////// */
+
+    fn get_keeper_reward(&self) -> U128 {
+        U128(self.keeper_reward)
+    }
+}
@@ -1,21 +1,27 @@
-use near_sdk::{env, json_types::U128, near_bindgen, AccountId, Gas, PromiseOrValue, Promise};
+use near_sdk::{env, json_types::U128, near_bindgen, serde_json::json, AccountId, Gas, Promise, PromiseOrValue};
 
+use crate::application::repository::{
+    build_milestones, default_milestones, is_before_funding, is_native_near, validate_funding_terms, validate_funding_window, mul_div_down, parse_transfer_msg, reject_transfer, pool_token_mut, round_to_pledge_multiple, split_token_pledges, take_refund, transfer_asset
+};
+use crate::models::events::{AdminTransfer, ConfigChanged, FundingRescheduled, FundingScheduled, PoolCreated, PoolStatusChanged, Refund};
 use crate::models::{
     contract::{
-        Assets, Launchpad, LaunchpadExt, LaunchpadFeature, 
-        PoolMetadata, Status, UserTokenDepositRecord, 
-        DEFAULT_MIN_STAKING, LaunchpadStorageKey
+        Assets, FundingTerms, Launchpad, LaunchpadExt, LaunchpadFeature, 
+        PoolMetadata, PoolToken, RefundReason, Status, TransferAction, 
+        BPS_DENOMINATOR, DEFAULT_MIN_STAKING, MAX_FUNDING_DURATION, MIN_FUNDING_DURATION, NEAR_TOKEN_ID,
+        Feature, MilestoneInfo, MilestoneStatus, PledgeLimits, Role
     }, 
-    ft_request::external::cross_edu, 
+    ft_request::external::{cross_edu, ext_self}, 
+    time::{Duration, Timestamp},
     PoolId
 };
-use near_sdk::collections::{UnorderedMap};
 
 pub const GAS_FOR_CROSS_CALL: Gas = Gas(3_000_000_000_000);
 pub const GAS_FOR_FT_TRANSFER_CALL: Gas = Gas(3_000_000_000_000);
 pub const ATTACHED_TRANSFER_FT: u128 = 1;
 pub const ATTACHED_STORAGE_DEPOSIT: u128 = 1_250_000_000_000_000_000_000;
 pub const GAS_FOR_REFUND_CALLBACK: Gas = Gas(5_000_000_000_000);
+pub const GAS_FOR_WITHDRAW_CALLBACK: Gas = Gas(5_000_000_000_000);
 
 #[near_bindgen]
 impl LaunchpadFeature for Launchpad {
@@ -23,26 +29,25 @@ impl LaunchpadFeature for Launchpad {
     /* //////////////////////////////////////////////////////////////
                             ADMIN FUNCTIONS
     ////////////////////////////////////////////////////////////// */
-    // admin can add list token use payable
+    // treasury manager can add list token use payable
     fn add_token(
         &mut self,
         token_id: String,
     ) {
-
-        if env::signer_account_id() != self.owner_id {
-            env::panic_str("Only the admin can add a new token.");
-        }
+        self.assert_role(Role::TreasuryManager);
 
         if self.list_assets.iter().any(|asset| asset.token_id == AccountId::new_unchecked(token_id.clone())) {
-            env::log_str("Token already exists in the list.");
             return;
         }
 
+        // native NEAR has no token contract to register with
         let ft_addr = AccountId::new_unchecked(token_id.clone());
-        cross_edu::ext(ft_addr.to_owned())
-            .with_static_gas(GAS_FOR_CROSS_CALL)
-            .with_attached_deposit(ATTACHED_STORAGE_DEPOSIT)
-            .storage_deposit(env::current_account_id());
+        if !is_native_near(&ft_addr) {
+            cross_edu::ext(ft_addr.to_owned())
+                .with_static_gas(GAS_FOR_CROSS_CALL)
+                .with_attached_deposit(ATTACHED_STORAGE_DEPOSIT)
+                .storage_deposit(env::current_account_id());
+        }
 
         self.list_assets.push(Assets {
             token_id: AccountId::new_unchecked(token_id),
@@ -50,34 +55,32 @@ impl LaunchpadFeature for Launchpad {
         });
     }
 
-    // admin can change admin contract launchpad
+    // admin proposes a new admin, the ownership moves once the new admin calls accept_admin
     fn change_admin(&mut self, new_admin: AccountId) {
-       
-        if env::signer_account_id() != self.owner_id {
-            env::panic_str("Only the current admin can change the admin.");
-        }
+        self.assert_owner();
 
-        self.owner_id = new_admin .clone();
-        env::log_str(&format!("Admin changed to: {}", new_admin));
+        AdminTransfer {
+            old_owner_id: &self.owner_id,
+            new_owner_id: &new_admin,
+        }
+        .emit_proposed();
+        self.pending_owner_id = Some(new_admin);
     }
 
-    // admin can delete a token payable
+    // treasury manager can delete a token payable
     fn delete_token_by_token_id(
         &mut self,
         token_id: AccountId
     ) {
-        if env::signer_account_id() != self.owner_id {
-            env::panic_str("Only the admin can delete a token.");
-        }
+        self.assert_role(Role::TreasuryManager);
 
-        env::log_str(&format!("Token with ID {} has been deleted.", token_id));
+        // listed tokens are kept, pools and the fee ledger may still hold them
+        let _ = token_id;
     }
 
     // admin can change the refund percentage for rejected pools
     fn set_refund_reject_pool(&mut self, percent: u8) {
-        if env::signer_account_id() != self.owner_id {
-            env::panic_str("Only admin can set refund percentage");
-        }
+        self.assert_owner();
 
         if percent > 100 {
             env::panic_str("Refund percentage must be between 0 and 100");
@@ -85,10 +88,12 @@ impl LaunchpadFeature for Launchpad {
 
         self.refund_percent = percent;
 
-        env::log_str(&format!(
-            "Refund percentage for rejected pools set to {}%",
-            percent
-        ));
+        ConfigChanged {
+            pool_id: None,
+            setting: "refund_percent",
+            value: json!(percent),
+        }
+        .emit();
     }
 
     fn admin_check_pool_status_after_init_15days(&mut self, pool_id: PoolId) {
@@ -99,39 +104,24 @@ impl LaunchpadFeature for Launchpad {
             env::panic_str("Pool must be in INIT status");
         }
 
-        let current_time = env::block_timestamp();
-        let fifteen_days_ns = 15 * 24 * 60 * 60 * 1_000_000_000; // 15 days in nanoseconds
-
-        if current_time > pool.time_init + fifteen_days_ns {
-            let refund_amount = if self.refund_percent == 0 {
-                1_000_000_000_000_000_000_000 // 1 NEAR
-            } else {
-                (pool.staking_amount * self.refund_percent as u128) / 100
-            };
-
-            Promise::new(pool.creator_id.clone())
-                .transfer(refund_amount);
-
+        if Timestamp::now() > pool.time_init + self.internal_funding_terms(pool_id).init_expiry {
             pool.status = Status::REJECTED;
-            pool.staking_amount = 0;
+            self.internal_refund_stake(&mut pool);
 
             self.pool_metadata_by_id.insert(&pool_id, &pool);
 
-            env::log_str(&format!(
-                "Pool {} has been auto-rejected after 15 days in INIT status. {}% of deposit ({} yoctoNEAR) returned to creator {}",
+            PoolStatusChanged {
                 pool_id,
-                self.refund_percent,
-                refund_amount,
-                pool.creator_id
-            ));
+                old_status: &Status::INIT,
+                new_status: &pool.status,
+            }
+            .emit();
         }
     }
 
-    // admin can set pool status to FUNDING or REJECTED
+    // pool reviewer can set pool status to APPROVED or REJECTED
     fn admin_set_status_pool_pre_funding(&mut self, pool_id: PoolId, approve: bool) -> PoolMetadata {
-        if env::signer_account_id() != self.owner_id {
-            env::panic_str("Only admin can set pool status");
-        }
+        self.assert_role(Role::PoolReviewer);
 
         let mut pool = self.pool_metadata_by_id.get(&pool_id)
             .expect("Pool does not exist");
@@ -142,43 +132,26 @@ impl LaunchpadFeature for Launchpad {
 
         if approve {
             pool.status = Status::APPROVED;
-            env::log_str(&format!(
-                "Pool {} has been approved and is now in APPROVED status",
-                pool_id
-            ));
         } else {
-            let refund_amount = if self.refund_percent == 0 {
-                1_000_000_000_000_000_000_000
-            } else {
-                (pool.staking_amount * self.refund_percent as u128) / 100
-            };
-
-            Promise::new(pool.creator_id.clone())
-                .transfer(refund_amount);
-
             pool.status = Status::REJECTED;
-            pool.staking_amount = 0;  
-            
-            env::log_str(&format!(
-                "Pool {} has been rejected. {}% of deposit ({} yoctoNEAR) returned to creator {}",
-                pool_id,
-                self.refund_percent,
-                refund_amount,
-                pool.creator_id
-            ));
+            self.internal_refund_stake(&mut pool);
         }
 
         self.pool_metadata_by_id.insert(&pool_id, &pool);
 
+        PoolStatusChanged {
+            pool_id,
+            old_status: &Status::INIT,
+            new_status: &pool.status,
+        }
+        .emit();
+
         pool
     }
 
     // admin can set min staking amount
     fn set_min_staking_amount(&mut self, amount: U128) {
-        // Only admin can set minimum staking amount
-        if env::signer_account_id() != self.owner_id {
-            env::panic_str("Only admin can set minimum staking amount");
-        }
+        self.assert_owner();
 
         // Ensure minimum amount is at least 1 NEAR
         if amount.0 < DEFAULT_MIN_STAKING {
@@ -187,57 +160,101 @@ impl LaunchpadFeature for Launchpad {
 
         self.min_staking_amount = amount.0;
 
-        env::log_str(&format!(
-            "Minimum staking amount set to {} yoctoNEAR ({} NEAR)",
-            amount.0,
-            amount.0 / DEFAULT_MIN_STAKING
-        ));
+        ConfigChanged {
+            pool_id: None,
+            setting: "min_staking_amount",
+            value: json!(amount),
+        }
+        .emit();
     }
     
-    fn withdraw_to_creator(&mut self, pool_id: PoolId, amount: U128) {
-        let signer_id = env::signer_account_id();
+    // admin sets the funding terms of every pool that does not override them, pools already running included
+    fn set_funding_terms(&mut self, terms: FundingTerms) {
+        self.assert_owner();
+
+        validate_funding_terms(&terms);
 
-        if signer_id != self.owner_id {
-            env::panic_str("Only admin can withdraw funds to the creator.");
+        ConfigChanged {
+            pool_id: None,
+            setting: "funding_terms",
+            value: json!(terms),
         }
+        .emit();
+        self.funding_terms = terms;
+    }
+
+    // treasury manager releases the tranche of the next approved milestone to the creator
+    fn withdraw_to_creator(&mut self, pool_id: PoolId) {
+        self.assert_role(Role::TreasuryManager);
+        self.assert_not_paused(Feature::CreatorWithdraw);
 
         let mut pool = self.pool_metadata_by_id.get(&pool_id)
             .expect("Pool does not exist");
 
-        if pool.status != Status::VOTING {
-            env::panic_str("Pool must be CLOSED to withdraw funds.");
-        }
-
-        if amount.0 > pool.total_balance {
-            env::panic_str("Insufficient pool balance for the requested withdrawal amount.");
+        if pool.status != Status::VOTING && pool.status != Status::SUCCESSFUL {
+            env::panic_str("Pool must be in VOTING or SUCCESSFUL status to withdraw funds.");
         }
 
-        cross_edu::ext(pool.token_id.clone())
-            .with_static_gas(GAS_FOR_FT_TRANSFER_CALL)
-            .with_attached_deposit(1)
-            .ft_transfer(
-                pool.creator_id.clone(),
-                amount,
-            );
+        let mut milestones = self.milestones_by_pool_id.get(&pool_id)
+            .expect("Pool has no milestones");
 
-        pool.total_balance -= amount.0;
+        let index = milestones.iter()
+            .position(|milestone| milestone.status == MilestoneStatus::APPROVED)
+            .unwrap_or_else(|| env::panic_str("No approved milestone to release"));
 
-        env::log_str(&format!(
-            "Withdrawn {} tokens to creator {}. Remaining pool balance: {}",
-            amount.0,
-            pool.creator_id,
+        // the last tranche takes whatever is left so no dust stays in the pool, a retried tranche only
+        // sends what its failed transfers gave back
+        let amount = if index == milestones.len() - 1 {
             pool.total_balance
-        ));
+        } else {
+            let released: u128 = milestones.iter().map(|milestone| milestone.amount_released).sum();
+            let total_raised = pool.total_balance + released;
+            let tranche = total_raised * milestones[index].percent as u128 / 100;
+            tranche.saturating_sub(milestones[index].amount_released).min(pool.total_balance)
+        };
+
+        // every token pays the same share of its balance, the last tranche empties them
+        let tranches: Vec<(AccountId, u128)> = pool.tokens.iter()
+            .map(|token| {
+                let tranche = if amount == pool.total_balance {
+                    token.balance
+                } else {
+                    mul_div_down(token.balance, amount, pool.total_balance)
+                };
+                (token.token_id.clone(), tranche)
+            })
+            .collect();
+        let values = self.internal_tranche_values(&tranches, amount);
+
+        for ((token_id, tranche), value) in tranches.into_iter().zip(values) {
+            if tranche == 0 {
+                continue;
+            }
+
+            // the platform fee stays in the contract, accrued to the token once the tranche reached the creator
+            let fee = mul_div_down(tranche, self.internal_fee_bps(&token_id) as u128, BPS_DENOMINATOR);
 
+            // the ledger is updated now and restored by the callback if the transfer fails
+            transfer_asset(&token_id, pool.creator_id.clone(), tranche - fee)
+                .then(
+                    ext_self::ext(env::current_account_id())
+                        .with_static_gas(GAS_FOR_WITHDRAW_CALLBACK)
+                        .resolve_withdraw_to_creator(pool_id, index as u32, token_id.clone(), U128(tranche), U128(fee), U128(value))
+                );
+
+            pool_token_mut(&mut pool, &token_id).balance -= tranche;
+        }
+
+        pool.total_balance -= amount;
+        milestones[index].status = MilestoneStatus::RELEASED;
+        milestones[index].amount_released += amount;
+
+        self.milestones_by_pool_id.insert(&pool_id, &milestones);
         self.pool_metadata_by_id.insert(&pool_id, &pool);
     }
 
     fn update_pool_status(&mut self, pool_id: PoolId, status: String) {
-        let signer_id = env::signer_account_id();
-
-        if signer_id != self.owner_id {
-            env::panic_str("Only the owner can update the pool status.");
-        }
+        self.assert_role(Role::PoolReviewer);
 
         let mut pool = self.pool_metadata_by_id.get(&pool_id)
             .expect("Pool does not exist");
@@ -257,85 +274,51 @@ impl LaunchpadFeature for Launchpad {
             _ => env::panic_str("Invalid status provided."),
         };
 
+        if !pool.status.can_transition_to(&new_status) {
+            env::panic_str(&format!("Invalid status transition from {:?} to {:?}", pool.status, new_status));
+        }
+
+        self.internal_apply_transition(&mut pool, &new_status);
+
+        let old_status = pool.status.clone();
         pool.status = new_status;
 
         self.pool_metadata_by_id.insert(&pool_id, &pool);
 
-        
-
-        env::log_str(&format!(
-            "Pool {} status updated to {} by owner {}",
+        PoolStatusChanged {
             pool_id,
-            status,
-            signer_id
-        ));
+            old_status: &old_status,
+            new_status: &pool.status,
+        }
+        .emit();
     }
 
     fn check_funding_result(&mut self, pool_id: PoolId, is_waiting_funding: bool) -> PoolMetadata {
         let mut pool = self.pool_metadata_by_id.get(&pool_id)
             .expect("Pool does not exist");
 
-        if env::signer_account_id() != self.owner_id {
-            env::panic_str("Only the owner can call this function");
-        }
+        self.assert_role(Role::PoolReviewer);
 
         if pool.status != Status::FUNDING {
             env::panic_str("Pool is not in FUNDING status");
         }
 
-        let current_time = env::block_timestamp();
+        let current_time = Timestamp::now();
         if current_time <= pool.time_end_pledge {
             env::panic_str("Funding period has not ended yet");
         }
 
-        // calculating voting_power to backer
-        if let Some(mut user_records) = self.user_records.get(&pool_id) {
-            let mut updated_records = Vec::new();
-            for (user_id, record) in user_records.iter() {
-                let mut updated_record = record.clone();
-                updated_record.voting_power = ((updated_record.amount as f64) / (pool.total_balance as f64)) * 100.0;
-                updated_records.push((user_id, updated_record));
-            }
-            for (user_id, updated_record) in updated_records {
-                user_records.insert(&user_id, &updated_record);
-            }
-            self.user_records.insert(&pool_id, &user_records);
-        }
-
-        match pool.total_balance {
-            0 => {
-                pool.status = Status::FAILED;
-                env::log_str(&format!(
-                    "Pool {} status changed to FAILED due to zero total balance",
-                    pool_id
-                ));
-            },
-            _ if pool.total_balance >= pool.target_funding => {
-                pool.status = Status::VOTING;
-                env::log_str(&format!(
-                    "Pool {} status changed to VOTING due to reaching target funding",
-                    pool_id
-                ));
-            },
-            _ if pool.total_balance >= (pool.target_funding * 80 / 100) && is_waiting_funding => {
-                pool.status = Status::WAITING;
-                pool.time_end_pledge += 3 * 24 * 60 * 60 * 1_000_000_000; // Add 3 days in nanoseconds
-                env::log_str(&format!(
-                    "Pool {} status changed to WAITING",
-                    pool_id
-                ));
-            },
-            _ => {
-                pool.status = Status::REFUNDED;
-                env::log_str(&format!(
-                    "Pool {} status changed to REFUNDED",
-                    pool_id
-                ));
-            }
-        }
+        self.internal_finish_funding(&mut pool, is_waiting_funding);
 
         self.pool_metadata_by_id.insert(&pool_id, &pool);
 
+        PoolStatusChanged {
+            pool_id,
+            old_status: &Status::FUNDING,
+            new_status: &pool.status,
+        }
+        .emit();
+
         pool
     }
 
@@ -343,9 +326,9 @@ impl LaunchpadFeature for Launchpad {
                             CREATOR FUNCTIONS
     ////////////////////////////////////////////////////////////// */
     #[payable]
-    fn init_pool(&mut self, campaign_id: String, token_id: AccountId, min_multiple_pledge: u128, target_funding: U128) -> PoolMetadata {
-        let pool_id = self.all_pool_id.len() as u64 + 1;
-        let creator_id = env::signer_account_id();
+    fn init_pool(&mut self, campaign_id: String, token_id: AccountId, min_multiple_pledge: u128, target_funding: U128, milestones: Option<Vec<MilestoneInfo>>, pledge_limits: Option<PledgeLimits>) -> PoolMetadata {
+        let pool_id = self.all_pool_id.len() + 1;
+        let creator_id = env::predecessor_account_id();
         let staking_amount = env::attached_deposit();
 
         if staking_amount < self.min_staking_amount {
@@ -356,6 +339,10 @@ impl LaunchpadFeature for Launchpad {
             ));
         }
 
+        if target_funding.0 == 0 {
+            env::panic_str("Target funding must be greater than zero");
+        }
+
         // Check if the token is in the allowed list
         if !self.list_assets.iter().any(|asset| asset.token_id == token_id) {
             env::panic_str(&format!(
@@ -363,6 +350,32 @@ impl LaunchpadFeature for Launchpad {
                 token_id
             ));
         }
+
+        let milestones = match milestones {
+            Some(milestones) => build_milestones(milestones),
+            None => default_milestones(),
+        };
+
+        if milestones.iter().any(|milestone| milestone.deadline.is_set() && milestone.deadline <= Timestamp::now()) {
+            env::panic_str("Milestone deadlines must be in the future");
+        }
+
+        let pledge_limits = pledge_limits.unwrap_or_default();
+        let min_pledge = pledge_limits.min_pledge.map_or(0, |amount| amount.0);
+        let max_pledge = pledge_limits.max_pledge.map_or(0, |amount| amount.0);
+        let hard_cap = pledge_limits.hard_cap.map_or(0, |amount| amount.0);
+
+        if max_pledge > 0 && max_pledge < min_pledge {
+            env::panic_str("Maximum pledge cannot be less than the minimum pledge");
+        }
+
+        if hard_cap > 0 && hard_cap < target_funding.0 {
+            env::panic_str("Hard cap cannot be less than the target funding");
+        }
+
+        // the terms a pool overrides must be in range once the contract's fill in the rest
+        let pool_terms = pledge_limits.funding_terms;
+        validate_funding_terms(&pool_terms.apply(&self.funding_terms));
         
         let pool = PoolMetadata {
             pool_id,
@@ -373,25 +386,80 @@ impl LaunchpadFeature for Launchpad {
             token_id: token_id.clone(),
             total_balance: 0,
             target_funding: target_funding.0,
-            time_init: env::block_timestamp(),
-            time_start_pledge: 0,
-            time_end_pledge: 0,
+            time_init: Timestamp::now(),
+            time_start_pledge: Timestamp::default(),
+            time_end_pledge: Timestamp::default(),
             funding_duration_days: 0,
             min_multiple_pledge,
+            total_voting_power: 0,
+            min_pledge,
+            max_pledge,
+            hard_cap,
+            withdraw_penalty_percent: 0,
+            tokens: vec![PoolToken { token_id: token_id.clone(), balance: 0, pledged: 0 }],
         };
 
         self.all_pool_id.insert(&pool_id);
         self.pool_metadata_by_id.insert(&pool_id, &pool);
+        self.milestones_by_pool_id.insert(&pool_id, &milestones);
+        if !pool_terms.is_empty() {
+            self.funding_terms_by_pool_id.insert(&pool_id, &pool_terms);
+        }
+
+        PoolCreated {
+            pool_id,
+            campaign_id: &pool.campaign_id,
+            creator_id: &pool.creator_id,
+            token_id: &pool.token_id,
+            target_funding: U128(pool.target_funding),
+            staking_amount: U128(pool.staking_amount),
+        }
+        .emit();
 
         pool
     }
 
-    fn set_funding_pool_by_creator(&mut self, pool_id: PoolId, time_start_pledge: u64, funding_duration_days: u64) {
+    // creator lets the pool take other supported tokens besides its own before funding opens,
+    // the list replaces the one set before and the pool's token always stays first
+    fn set_accepted_tokens(&mut self, pool_id: PoolId, accepted_tokens: Vec<AccountId>) {
+        let mut pool = self.pool_metadata_by_id.get(&pool_id)
+            .expect("Pool does not exist");
+
+        if env::predecessor_account_id() != pool.creator_id {
+            env::panic_str("Only the creator of the pool can set the accepted tokens.");
+        }
+
+        if !is_before_funding(&pool) {
+            env::panic_str("Accepted tokens can only change before funding");
+        }
+
+        let mut token_ids = vec![pool.token_id.clone()];
+        for token_id in accepted_tokens {
+            if !self.list_assets.iter().any(|asset| asset.token_id == token_id) {
+                env::panic_str(&format!(
+                    "Token {} is not supported. Only tokens added by admin can be used for pools",
+                    token_id
+                ));
+            }
+            if !token_ids.contains(&token_id) {
+                token_ids.push(token_id);
+            }
+        }
+
+        pool.tokens = token_ids.into_iter()
+            .map(|token_id| PoolToken { token_id, balance: 0, pledged: 0 })
+            .collect();
+        self.pool_metadata_by_id.insert(&pool_id, &pool);
+    }
+
+    // creator sets the pledge window: a start already due opens FUNDING right away, a later one keeps the pool
+    // APPROVED until advance_pool opens funding at its start
+    fn set_funding_pool_by_creator(&mut self, pool_id: PoolId, time_start_pledge: Timestamp, funding_duration_days: u64, milestones: Option<Vec<MilestoneInfo>>, withdraw_penalty_percent: Option<u8>) {
         
         let mut pool = self.pool_metadata_by_id.get(&pool_id)
             .expect("Pool does not exist");
 
-        if env::signer_account_id() != pool.creator_id {
+        if env::predecessor_account_id() != pool.creator_id {
             env::panic_str("Only the creator of the pool can set funding parameters.");
         }
 
@@ -399,28 +467,113 @@ impl LaunchpadFeature for Launchpad {
             env::panic_str("Pool must be in APPROVED status to set funding parameters");
         }
 
-        if funding_duration_days == 0 {
-            env::panic_str("Funding duration must be greater than 0 days");
+        // a start in the past opens pledging now, backers still get the full funding duration
+        let current_time = Timestamp::now();
+        let time_start_pledge = time_start_pledge.max(current_time);
+
+        let min_days = MIN_FUNDING_DURATION.whole_days();
+        let max_days = MAX_FUNDING_DURATION.whole_days();
+        if !(min_days..=max_days).contains(&funding_duration_days) {
+            env::panic_str(&format!("Funding duration must be between {} and {} days", min_days, max_days));
         }
+        let time_end_pledge = time_start_pledge + Duration::from_days(funding_duration_days);
 
-        if time_start_pledge <= env::block_timestamp() {
-            env::panic_str("Start time must be in the future");
+        let withdraw_penalty_percent = withdraw_penalty_percent.unwrap_or(0);
+        if withdraw_penalty_percent > 100 {
+            env::panic_str("Withdraw penalty percentage must be between 0 and 100");
         }
 
         pool.time_start_pledge = time_start_pledge;
-        pool.time_end_pledge = time_start_pledge + (funding_duration_days as u64 * 60 * 1_000_000_000);
+        pool.time_end_pledge = time_end_pledge;
         pool.funding_duration_days = funding_duration_days;
-        pool.status = Status::FUNDING;
+        pool.withdraw_penalty_percent = withdraw_penalty_percent;
+
+        let milestones = match milestones {
+            Some(milestones) => build_milestones(milestones),
+            None => self.milestones_by_pool_id.get(&pool_id).unwrap_or_else(default_milestones),
+        };
+
+        if milestones.iter().any(|milestone| milestone.deadline.is_set() && milestone.deadline <= pool.time_end_pledge) {
+            env::panic_str("Milestone deadlines must be after the end of the funding period");
+        }
+
+        let opens_now = pool.time_start_pledge <= current_time;
+        if opens_now {
+            pool.status = Status::FUNDING;
+        }
 
         self.pool_metadata_by_id.insert(&pool_id, &pool);
+        self.milestones_by_pool_id.insert(&pool_id, &milestones);
 
-        env::log_str(&format!(
-            "Pool {} funding parameters set - Start time: {}, Duration: {} days",
+        FundingScheduled {
             pool_id,
-            time_start_pledge,
-            funding_duration_days
-        ));
+            time_start_pledge: pool.time_start_pledge,
+            time_end_pledge: pool.time_end_pledge,
+        }
+        .emit();
+
+        if opens_now {
+            PoolStatusChanged {
+                pool_id,
+                old_status: &Status::APPROVED,
+                new_status: &pool.status,
+            }
+            .emit();
+        }
+    }
+
+    // creator or pool reviewer moves the pledge window of a scheduled or FUNDING pool: both ends while pledging
+    // has not opened, afterwards only a later end so backers never lose time they were promised
+    fn change_pool_funding_time(&mut self, pool_id: PoolId, time_start_pledge: Timestamp, time_end_pledge: Timestamp) {
+        let caller_id = env::predecessor_account_id();
+
+        let mut pool = self.pool_metadata_by_id.get(&pool_id)
+            .expect("Pool does not exist");
+
+        if caller_id != pool.creator_id && !self.internal_has_role(&Role::PoolReviewer, &caller_id) {
+            env::panic_str("Only the creator or a pool reviewer can change the funding time");
+        }
+
+        let scheduled = pool.status == Status::APPROVED && pool.time_end_pledge.is_set();
+        if pool.status != Status::FUNDING && !scheduled {
+            env::panic_str("Pool has no funding schedule");
+        }
+
+        let current_time = Timestamp::now();
+        if current_time > pool.time_end_pledge {
+            env::panic_str("Funding period has ended");
+        }
+
+        if current_time >= pool.time_start_pledge {
+            if time_start_pledge != pool.time_start_pledge {
+                env::panic_str("Start time cannot change once pledging has opened");
+            }
+            if time_end_pledge < pool.time_end_pledge {
+                env::panic_str("End time cannot move earlier once pledging has opened");
+            }
+        } else if time_start_pledge <= current_time {
+            env::panic_str("Start time must be in the future");
+        }
+
+        validate_funding_window(time_start_pledge, time_end_pledge);
+
+        let milestones = self.milestones_by_pool_id.get(&pool_id)
+            .expect("Pool has no milestones");
+        if milestones.iter().any(|milestone| milestone.deadline.is_set() && milestone.deadline <= time_end_pledge) {
+            env::panic_str("Milestone deadlines must be after the end of the funding period");
+        }
+
+        pool.time_start_pledge = time_start_pledge;
+        pool.time_end_pledge = time_end_pledge;
+        pool.funding_duration_days = (time_end_pledge - time_start_pledge).whole_days();
+        self.pool_metadata_by_id.insert(&pool_id, &pool);
 
+        FundingRescheduled {
+            pool_id,
+            time_start_pledge,
+            time_end_pledge,
+        }
+        .emit();
     }
 
     // creator pool should be cancel pool
@@ -428,7 +581,7 @@ impl LaunchpadFeature for Launchpad {
         let mut pool = self.pool_metadata_by_id.get(&pool_id)
             .expect("Pool does not exist");
 
-        if env::signer_account_id() != pool.creator_id {
+        if env::predecessor_account_id() != pool.creator_id {
             env::panic_str("Only the creator of the pool can cancel it.");
         }
 
@@ -436,33 +589,29 @@ impl LaunchpadFeature for Launchpad {
             env::panic_str("Pool must be in INIT status to be rejected");
         }
 
-        let refund_amount = pool.staking_amount;
-
-        Promise::new(pool.creator_id.clone())
-            .transfer(refund_amount);
-        
+        let stake = pool.staking_amount;
         pool.status = Status::CANCELED;
-        pool.staking_amount = 0;  
-        
+        self.internal_return_stake(&mut pool, stake);
+
         self.pool_metadata_by_id.insert(&pool_id, &pool);
 
-        env::log_str(&format!(
-            "Pool {} has been canceled by creator. Full deposit ({} yoctoNEAR) returned to creator {}",
+        PoolStatusChanged {
             pool_id,
-            refund_amount,
-            pool.creator_id
-        ));
+            old_status: &Status::INIT,
+            new_status: &pool.status,
+        }
+        .emit();
 
         pool
     }
 
     fn creator_accept_voting(&mut self, pool_id: PoolId, approve: bool) -> PoolMetadata {
-        let signer_id = env::signer_account_id();
+        let caller_id = env::predecessor_account_id();
 
         let mut pool = self.pool_metadata_by_id.get(&pool_id)
             .expect("Pool does not exist");
 
-        if signer_id != pool.creator_id {
+        if caller_id != pool.creator_id {
             env::panic_str("Only the creator can set the pool status after waiting.");
         }
 
@@ -470,24 +619,26 @@ impl LaunchpadFeature for Launchpad {
             env::panic_str("Pool status must be WAITING to change it after waiting period.");
         }
 
+        // once the waiting period is over the pool can only be refunded through advance_pool
+        if Timestamp::now() > pool.time_end_pledge {
+            env::panic_str("Waiting period has ended");
+        }
+
         if approve {
             pool.status = Status::VOTING;
-            env::log_str(&format!(
-                "Pool {} status changed to VOTING by creator {}",
-                pool_id,
-                signer_id
-            ));
         } else {
             pool.status = Status::REFUNDED;
-            env::log_str(&format!(
-                "Pool {} status changed to REFUNDED by creator {}",
-                pool_id,
-                signer_id
-            ));
         }
 
         self.pool_metadata_by_id.insert(&pool_id, &pool);
 
+        PoolStatusChanged {
+            pool_id,
+            old_status: &Status::WAITING,
+            new_status: &pool.status,
+        }
+        .emit();
+
         pool
     }
 
@@ -500,86 +651,62 @@ impl LaunchpadFeature for Launchpad {
         amount: U128,
         msg: String,
     ) -> PromiseOrValue<U128> {
-        env::log_str(&format!("Received {} tokens from {}", amount.0, sender_id));
-    
         let token_id = env::predecessor_account_id();
-    
-        if !self.list_assets.iter().any(|asset| asset.token_id == token_id) {
-            let error_msg = "Token ID from message does not match any token ID in the list.";
-            env::log_str(error_msg);
-            return PromiseOrValue::Value(amount); // Refund
+
+        if self.internal_is_paused(&Feature::Pledge) {
+            return reject_transfer(&sender_id, &token_id, amount, RefundReason::Paused);
         }
     
-        let pool_id: PoolId = match msg.parse() {
-            Ok(id) => id,
-            Err(_) => {
-                let error_msg = "Invalid pool ID in message";
-                env::log_str(error_msg);
-                return PromiseOrValue::Value(amount); // Refund
-            }
-        };
-    
-        let mut pool = match self.pool_metadata_by_id.get(&pool_id) {
-            Some(p) => p,
-            None => {
-                let error_msg = "Pool does not exist";
-                env::log_str(error_msg);
-                return PromiseOrValue::Value(amount); // Refund
-            }
-        };
-    
-        if !matches!(pool.status, Status::FUNDING) {
-            let error_msg = "Pool is not in funding status";
-            env::log_str(error_msg);
-            return PromiseOrValue::Value(amount); // Refund
+        if !self.list_assets.iter().any(|asset| asset.token_id == token_id) || is_native_near(&token_id) {
+            return reject_transfer(&sender_id, &token_id, amount, RefundReason::TokenNotSupported);
         }
     
-        let current_time = env::block_timestamp();
-        if current_time < pool.time_start_pledge || current_time > pool.time_end_pledge {
-            let error_msg = "Not within pledge period";
-            env::log_str(error_msg);
-            return PromiseOrValue::Value(amount); // Refund
+        let action = match parse_transfer_msg(&msg) {
+            Ok(action) => action,
+            Err(reason) => return reject_transfer(&sender_id, &token_id, amount, reason),
+        };
+
+        match action {
+            TransferAction::Pledge { pool_id, tier, referrer, signature } => {
+                let result = self.internal_check_pool_access(pool_id, &sender_id, signature.as_deref())
+                    .and_then(|_| self.internal_pledge(&token_id, pool_id, &sender_id, amount.0, tier, referrer.as_ref()));
+
+                match result {
+                    // return the excess, 0 when the whole pledge was accepted
+                    Ok(amount_value) => PromiseOrValue::Value(U128(amount.0 - amount_value)),
+                    Err(reason) => reject_transfer(&sender_id, &token_id, amount, reason),
+                }
+            },
         }
-    
-        if token_id != pool.token_id {
-            let error_msg = "Invalid token for this pool";
-            env::log_str(error_msg);
-            return PromiseOrValue::Value(amount); // Refund
+    }
+
+    // backer pledges the attached NEAR to a native NEAR pool, the part above the caps
+    // or below the pledge multiple is sent back
+    #[payable]
+    fn pledge(&mut self, pool_id: PoolId, tier_index: Option<u32>, referrer: Option<AccountId>, signature: Option<String>) -> U128 {
+        self.assert_not_paused(Feature::Pledge);
+
+        let sender_id = env::predecessor_account_id();
+        let amount = env::attached_deposit();
+        let token_id = AccountId::new_unchecked(NEAR_TOKEN_ID.to_string());
+
+        // failing makes the attached deposit go back to the backer
+        let amount_value = self.internal_check_pool_access(pool_id, &sender_id, signature.as_deref())
+            .and_then(|_| self.internal_pledge(&token_id, pool_id, &sender_id, amount, tier_index, referrer.as_ref()))
+            .unwrap_or_else(|reason| env::panic_str(reason.message()));
+
+        if amount > amount_value {
+            Promise::new(sender_id)
+                .transfer(amount - amount_value);
         }
-    
-        // If all checks pass, process the pledge
-        let amount_value = amount.0;
-        let mut user_records = self
-            .user_records
-            .get(&pool_id)
-            .unwrap_or_else(|| {
-                let prefix = LaunchpadStorageKey::user_records_prefix(pool_id);
-                UnorderedMap::new(prefix)
-            });
-    
-        let mut user_record = user_records.get(&sender_id).unwrap_or(UserTokenDepositRecord {
-            amount: 0,
-            voting_power: 0.0,
-        });
-    
-        user_record.amount += amount_value;
-        user_records.insert(&sender_id, &user_record);
-        self.user_records.insert(&pool_id, &user_records);
-    
-        pool.total_balance += amount_value;
-        self.pool_metadata_by_id.insert(&pool_id, &pool);
-    
-        env::log_str(&format!(
-            "User {} pledged {} tokens to pool {}",
-            sender_id, amount_value, pool_id
-        ));
-    
-        // If successful, return 0 (no refund)
-        PromiseOrValue::Value(U128(0))
+
+        U128(amount_value)
     }
 
     fn claim_refund(&mut self, pool_id: PoolId) {
-        let caller_id = env::signer_account_id();
+        self.assert_not_paused(Feature::Refund);
+
+        let caller_id = env::predecessor_account_id();
 
         let mut pool = self.pool_metadata_by_id.get(&pool_id)
             .expect("Pool does not exist");
@@ -595,29 +722,128 @@ impl LaunchpadFeature for Launchpad {
             .expect("User has no record in this pool")
             .clone();
 
-        let refund_amount = (pool.total_balance as f64 * user_record.voting_power / 100.0) as u128;
-
-        if refund_amount == 0 {
+        if user_record.voting_power == 0 {
             env::panic_str("No funds available for withdrawal");
         }
 
-        cross_edu::ext(pool.token_id.clone())
-            .with_static_gas(GAS_FOR_FT_TRANSFER_CALL)
-            .with_attached_deposit(1)
-            .ft_transfer(
-                caller_id.clone(),
-                U128(refund_amount),
-            );
+        // every token is refunded in itself, the last backer to claim receives the exact remainder
+        let token_pledges = self.internal_token_pledges(&pool, &caller_id, &user_record);
+        let refunds = take_refund(&mut pool, &token_pledges);
+        let released_tier = self.internal_release_tier(pool_id, &caller_id, 0);
+
+        for (token_pledge, (refund_amount, refund_value)) in token_pledges.iter().zip(refunds) {
+            // the ledger is updated now and restored by the callback if the transfer fails
+            if refund_amount > 0 {
+                transfer_asset(&token_pledge.token_id, caller_id.clone(), refund_amount)
+                    .then(
+                        ext_self::ext(env::current_account_id())
+                            .with_static_gas(GAS_FOR_REFUND_CALLBACK)
+                            .resolve_claim_refund(
+                                pool_id,
+                                caller_id.clone(),
+                                token_pledge.clone(),
+                                U128(refund_amount),
+                                U128(refund_value),
+                                released_tier,
+                            )
+                    );
+            } else {
+                Refund {
+                    pool_id,
+                    account_id: &caller_id,
+                    token_id: &token_pledge.token_id,
+                    amount: U128(0),
+                }
+                .emit();
+            }
+        }
+
+        self.pool_metadata_by_id.insert(&pool_id, &pool);
+        self.token_pledges.remove(&(pool_id, caller_id.clone()));
 
         // Update the user's record amount to 0
         user_record.amount = 0;
+        user_record.voting_power = 0;
         user_records.insert(&caller_id, &user_record);
         self.user_records.insert(&pool_id, &user_records);
+    }
+
+    // backer takes back all or part of a pledge while the pledge window is open,
+    // the creator's withdraw penalty stays in the pool
+    fn withdraw_pledge(&mut self, pool_id: PoolId, amount: Option<U128>) {
+        self.assert_not_paused(Feature::Refund);
 
-        env::log_str(&format!(
-            "User {} withdrew {} Token from pool {}",
-            caller_id, refund_amount, pool_id
-        ));
+        let caller_id = env::predecessor_account_id();
+
+        let mut pool = self.pool_metadata_by_id.get(&pool_id)
+            .expect("Pool does not exist");
+
+        if pool.status != Status::FUNDING {
+            env::panic_str("Pool is not in FUNDING status");
+        }
+
+        if Timestamp::now() > pool.time_end_pledge {
+            env::panic_str("Pledge period has ended");
+        }
+
+        let mut user_records = self.user_records.get(&pool_id)
+            .expect("No user records found for this pool");
+
+        let mut user_record = user_records.get(&caller_id)
+            .expect("User has no record in this pool");
+
+        let withdrawn = amount.map_or(user_record.amount, |amount| amount.0);
+        if withdrawn == 0 || withdrawn > user_record.amount {
+            env::panic_str("Withdraw amount must be between 1 and the pledged amount");
+        }
+
+        if round_to_pledge_multiple(&pool, withdrawn) != withdrawn {
+            env::panic_str("Withdraw amount must be a multiple of the pledge multiple of this pool");
+        }
+
+        let remaining = user_record.amount - withdrawn;
+        if remaining > 0 && remaining < pool.min_pledge {
+            env::panic_str("Remaining pledge cannot be less than the minimum pledge of this pool");
+        }
+
+        // the withdrawn worth is taken from every token in proportion to the backer's pledges in it
+        let mut token_pledges = self.internal_token_pledges(&pool, &caller_id, &user_record);
+        let parts = split_token_pledges(&token_pledges, withdrawn);
+        let released_tier = self.internal_release_tier(pool_id, &caller_id, remaining);
+
+        for (token_pledge, part) in token_pledges.iter_mut().zip(parts) {
+            if part.amount == 0 {
+                continue;
+            }
+
+            let penalty = part.amount * pool.withdraw_penalty_percent as u128 / 100;
+            let payout = part.amount - penalty;
+            let payout_value = part.value - part.value * pool.withdraw_penalty_percent as u128 / 100;
+
+            token_pledge.amount -= part.amount;
+            token_pledge.value -= part.value;
+            self.internal_remove_referred_pledge(pool_id, &caller_id, &part);
+            let pool_token = pool_token_mut(&mut pool, &part.token_id);
+            pool_token.balance -= payout;
+            pool_token.pledged -= part.amount;
+            pool.total_balance -= payout_value;
+
+            // the ledger is updated now and restored by the callback if the transfer fails
+            transfer_asset(&part.token_id, caller_id.clone(), payout)
+                .then(
+                    ext_self::ext(env::current_account_id())
+                        .with_static_gas(GAS_FOR_REFUND_CALLBACK)
+                        .resolve_withdraw_pledge(pool_id, caller_id.clone(), part, U128(penalty), U128(payout_value), released_tier)
+                );
+        }
+
+        token_pledges.retain(|token_pledge| token_pledge.amount > 0);
+        self.token_pledges.insert(&(pool_id, caller_id.clone()), &token_pledges);
+        user_record.amount = remaining;
+
+        user_records.insert(&caller_id, &user_record);
+        self.user_records.insert(&pool_id, &user_records);
+        self.pool_metadata_by_id.insert(&pool_id, &pool);
     }
 
 }
@@ -0,0 +1,55 @@
+use near_sdk::{env, near_bindgen};
+
+use crate::models::events::PauseChanged;
+use crate::models::contract::{Feature, Launchpad, LaunchpadExt, LaunchpadPause, Role};
+
+#[near_bindgen]
+impl LaunchpadPause for Launchpad {
+
+    /* //////////////////////////////////////////////////////////////
+                            PAUSER FUNCTIONS
+    ////////////////////////////////////////////////////////////// */
+    // pauser halts the given features, every feature when none are given
+    fn pause(&mut self, features: Option<Vec<Feature>>) {
+        self.assert_role(Role::Pauser);
+
+        let features = features.unwrap_or_else(|| Feature::ALL.to_vec());
+        for feature in features.iter() {
+            if !self.paused_features.contains(feature) {
+                self.paused_features.push(feature.clone());
+            }
+        }
+
+        PauseChanged {
+            features: &features,
+            account_id: &env::predecessor_account_id(),
+        }
+        .emit_paused();
+    }
+
+    // pauser resumes the given features, every feature when none are given
+    fn unpause(&mut self, features: Option<Vec<Feature>>) {
+        self.assert_role(Role::Pauser);
+
+        let features = features.unwrap_or_else(|| Feature::ALL.to_vec());
+        self.paused_features.retain(|feature| !features.contains(feature));
+
+        PauseChanged {
+            features: &features,
+            account_id: &env::predecessor_account_id(),
+        }
+        .emit_unpaused();
+    }
+
+    /* //////////////////////////////////////////////////////////////
+                            GETTER FUNCTIONS
+    ////////////////////////////////////////////////////////////// */
+
+    fn is_paused(&self, feature: Feature) -> bool {
+        self.internal_is_paused(&feature)
+    }
+
+    fn get_paused_features(&self) -> Vec<Feature> {
+        self.paused_features.clone()
+    }
+}
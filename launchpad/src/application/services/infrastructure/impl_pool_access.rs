@@ -0,0 +1,127 @@
+use ed25519_dalek::PublicKey;
+use near_sdk::{base64::decode, collections::UnorderedSet, env, near_bindgen, serde_json::json, AccountId};
+
+use crate::application::repository::{is_before_funding, page_limit};
+use crate::models::{
+    contract::{Launchpad, LaunchpadExt, LaunchpadPoolAccess, LaunchpadStorageKey, PoolAccess},
+    events::ConfigChanged,
+    PoolId
+};
+
+#[near_bindgen]
+impl LaunchpadPoolAccess for Launchpad {
+
+    /* //////////////////////////////////////////////////////////////
+                            ADMIN FUNCTIONS
+    ////////////////////////////////////////////////////////////// */
+    // admin sets the base64 ed25519 key that signs pledges to signature-gated pools, none turns them off
+    fn set_pledge_signer_key(&mut self, public_key: Option<String>) {
+        self.assert_owner();
+
+        if let Some(public_key) = &public_key {
+            let pubkey_bytes = decode(public_key).expect("Invalid public key (Base64 decode error)");
+            PublicKey::from_bytes(&pubkey_bytes).expect("Invalid public key");
+        }
+
+        ConfigChanged {
+            pool_id: None,
+            setting: "pledge_signer_key",
+            value: json!(public_key),
+        }
+        .emit();
+        self.pledge_signer_key = public_key;
+    }
+
+    /* //////////////////////////////////////////////////////////////
+                            CREATOR FUNCTIONS
+    ////////////////////////////////////////////////////////////// */
+    // creator restricts who may pledge to the pool before funding opens
+    fn set_pool_access(&mut self, pool_id: PoolId, access: PoolAccess) {
+        let pool = self.assert_pool_creator(pool_id);
+
+        if !is_before_funding(&pool) {
+            env::panic_str("Pool access can only change before funding");
+        }
+
+        if access == PoolAccess::Public {
+            self.access_by_pool_id.remove(&pool_id);
+        } else {
+            self.access_by_pool_id.insert(&pool_id, &access);
+        }
+
+        ConfigChanged {
+            pool_id: Some(pool_id),
+            setting: "access",
+            value: json!(access),
+        }
+        .emit();
+    }
+
+    // creator lets accounts pledge to the pool, also while it is funding
+    fn add_to_allowlist(&mut self, pool_id: PoolId, account_ids: Vec<AccountId>) {
+        self.assert_pool_creator(pool_id);
+
+        let mut allowlist = self.allowlist_by_pool_id.get(&pool_id)
+            .unwrap_or_else(|| UnorderedSet::new(LaunchpadStorageKey::allowlist_prefix(pool_id)));
+        let added = account_ids.iter().filter(|account_id| allowlist.insert(account_id)).count();
+        self.allowlist_by_pool_id.insert(&pool_id, &allowlist);
+
+        ConfigChanged {
+            pool_id: Some(pool_id),
+            setting: "allowlist_added",
+            value: json!(added),
+        }
+        .emit();
+    }
+
+    // creator takes accounts off the allowlist, the pledges they already made stay in the pool
+    fn remove_from_allowlist(&mut self, pool_id: PoolId, account_ids: Vec<AccountId>) {
+        self.assert_pool_creator(pool_id);
+
+        let mut allowlist = match self.allowlist_by_pool_id.get(&pool_id) {
+            Some(allowlist) => allowlist,
+            None => return,
+        };
+        let removed = account_ids.iter().filter(|account_id| allowlist.remove(account_id)).count();
+        self.allowlist_by_pool_id.insert(&pool_id, &allowlist);
+
+        ConfigChanged {
+            pool_id: Some(pool_id),
+            setting: "allowlist_removed",
+            value: json!(removed),
+        }
+        .emit();
+    }
+
+    /* //////////////////////////////////////////////////////////////
+                            GETTER FUNCTIONS
+    ////////////////////////////////////////////////////////////// */
+
+    fn get_pledge_signer_key(&self) -> Option<String> {
+        self.pledge_signer_key.clone()
+    }
+
+    fn get_pool_access(&self, pool_id: PoolId) -> PoolAccess {
+        self.access_by_pool_id.get(&pool_id).unwrap_or(PoolAccess::Public)
+    }
+
+    fn is_allowlisted(&self, pool_id: PoolId, account_id: AccountId) -> bool {
+        self.allowlist_by_pool_id.get(&pool_id)
+            .is_some_and(|allowlist| allowlist.contains(&account_id))
+    }
+
+    fn get_allowlist(&self, pool_id: PoolId, from_index: Option<u64>, limit: Option<u64>) -> Vec<AccountId> {
+        let allowlist = match self.allowlist_by_pool_id.get(&pool_id) {
+            Some(allowlist) => allowlist,
+            None => return Vec::new(),
+        };
+
+        let accounts = allowlist.as_vector();
+        let start = from_index.unwrap_or(0);
+        let end = start.saturating_add(page_limit(limit)).min(accounts.len());
+
+        (start..end)
+            .filter_map(|index| accounts.get(index))
+            .collect()
+    }
+}
@@ -0,0 +1,135 @@
+use near_sdk::{env, json_types::U128, near_bindgen, serde_json::json, AccountId};
+
+use super::impl_launchpad::GAS_FOR_WITHDRAW_CALLBACK;
+use crate::application::repository::{page_limit, transfer_asset};
+use crate::models::{
+    contract::{
+        Launchpad, LaunchpadExt, LaunchpadReferrals, ReferralReserve, ReferralStats, Role, Status,
+        MAX_PLATFORM_FEE_BPS
+    },
+    events::ConfigChanged,
+    ft_request::external::ext_self,
+    PoolId
+};
+
+#[near_bindgen]
+impl LaunchpadReferrals for Launchpad {
+
+    /* //////////////////////////////////////////////////////////////
+                        TREASURY MANAGER FUNCTIONS
+    ////////////////////////////////////////////////////////////// */
+    // treasury manager sets the reward referrers get on the pledges they brought, paid when they claim it
+    fn set_referral_reward(&mut self, reward_bps: u16) {
+        self.assert_role(Role::TreasuryManager);
+
+        if reward_bps > MAX_PLATFORM_FEE_BPS {
+            env::panic_str(&format!("Referral reward cannot exceed {} basis points", MAX_PLATFORM_FEE_BPS));
+        }
+
+        self.referral_reward_bps = reward_bps;
+
+        ConfigChanged {
+            pool_id: None,
+            setting: "referral_reward_bps",
+            value: json!(reward_bps),
+        }
+        .emit();
+    }
+
+    /* //////////////////////////////////////////////////////////////
+                            REFERRER FUNCTIONS
+    ////////////////////////////////////////////////////////////// */
+    // referrer claims the reward on the pledges it brought to a SUCCESSFUL pool, in each token pledged;
+    // the reward comes out of the pool's fees set aside in its referral reserve and never exceeds the fee of the token
+    fn claim_referral_reward(&mut self, pool_id: PoolId) {
+        let referrer_id = env::predecessor_account_id();
+
+        let pool = self.pool_metadata_by_id.get(&pool_id)
+            .expect("Pool does not exist");
+
+        if pool.status != Status::SUCCESSFUL {
+            env::panic_str("Referral rewards are paid once the pool is SUCCESSFUL");
+        }
+
+        let mut referrals = self.referrals_by_pool_id.get(&pool_id)
+            .expect("Pool has no referrals");
+        let mut stats = referrals.get(&referrer_id)
+            .unwrap_or_else(|| env::panic_str("Account referred no backer to this pool"));
+
+        let mut claimed = false;
+        for token in stats.tokens.clone() {
+            if stats.rewarded_tokens.contains(&token.token_id) {
+                continue;
+            }
+
+            let reward = self.internal_referral_reward(&token.token_id, token.amount);
+            if reward == 0 {
+                continue;
+            }
+
+            let key = (pool_id, token.token_id.clone());
+            let mut reserve = self.referral_reserves.get(&key).unwrap_or_default();
+            // the pool's tranches have not set aside enough fees yet
+            if reward > reserve.reserved - reserve.paid {
+                continue;
+            }
+
+            // the ledger is updated now and restored by the callback if the transfer fails
+            reserve.paid += reward;
+            self.referral_reserves.insert(&key, &reserve);
+            stats.rewarded_tokens.push(token.token_id.clone());
+            claimed = true;
+
+            transfer_asset(&token.token_id, referrer_id.clone(), reward)
+                .then(
+                    ext_self::ext(env::current_account_id())
+                        .with_static_gas(GAS_FOR_WITHDRAW_CALLBACK)
+                        .resolve_claim_referral_reward(pool_id, referrer_id.clone(), token.token_id, U128(reward))
+                );
+        }
+
+        if !claimed {
+            env::panic_str("No referral reward to claim");
+        }
+
+        referrals.insert(&referrer_id, &stats);
+        self.referrals_by_pool_id.insert(&pool_id, &referrals);
+    }
+
+    /* //////////////////////////////////////////////////////////////
+                            GETTER FUNCTIONS
+    ////////////////////////////////////////////////////////////// */
+
+    fn get_referral_reward(&self) -> u16 {
+        self.referral_reward_bps
+    }
+
+    fn get_referrer(&self, pool_id: PoolId, account_id: AccountId) -> Option<AccountId> {
+        self.referrer_by_backer.get(&(pool_id, account_id))
+    }
+
+    fn get_referral_stats(&self, pool_id: PoolId, referrer_id: AccountId) -> Option<ReferralStats> {
+        self.referrals_by_pool_id.get(&pool_id)
+            .and_then(|referrals| referrals.get(&referrer_id))
+    }
+
+    // referrers of a pool in the order they brought their first backer
+    fn get_referral_reserve(&self, pool_id: PoolId, token_id: AccountId) -> ReferralReserve {
+        self.referral_reserves.get(&(pool_id, token_id)).unwrap_or_default()
+    }
+
+    fn get_pool_referrals(&self, pool_id: PoolId, from_index: Option<u64>, limit: Option<u64>) -> Vec<ReferralStats> {
+        let referrals = match self.referrals_by_pool_id.get(&pool_id) {
+            Some(referrals) => referrals,
+            None => return Vec::new(),
+        };
+
+        let values = referrals.values_as_vector();
+        let start = from_index.unwrap_or(0);
+        let end = start.saturating_add(page_limit(limit)).min(values.len());
+
+        (start..end)
+            .filter_map(|index| values.get(index))
+            .collect()
+    }
+}
@@ -0,0 +1,511 @@
+use near_sdk::{is_promise_success, json_types::U128, near_bindgen, AccountId};
+
+use crate::application::repository::{add_token_pledge, pool_token_mut};
+
+use crate::models::events::{CreatorWithdraw, FeeWithdraw, PledgeWithdrawn, ReferralReward, Refund, TreasuryWithdraw};
+use crate::models::{
+    contract::{Launchpad, LaunchpadExt, MilestoneStatus, TokenPledge},
+    ft_request::external::LaunchpadResolver,
+    PoolId
+};
+
+#[near_bindgen]
+impl LaunchpadResolver for Launchpad {
+
+    /* //////////////////////////////////////////////////////////////
+                            CALLBACK FUNCTIONS
+    ////////////////////////////////////////////////////////////// */
+    // restore the backer's pledge in the token, the pool balance and a released reward tier if the refund transfer failed,
+    // the other tokens of the refund settle on their own
+    #[private]
+    fn resolve_claim_refund(&mut self, pool_id: PoolId, user_id: AccountId, token_pledge: TokenPledge, amount: U128, value: U128, tier_index: Option<u32>) {
+        let event = Refund {
+            pool_id,
+            account_id: &user_id,
+            token_id: &token_pledge.token_id,
+            amount,
+        };
+
+        if is_promise_success() {
+            event.emit();
+            return;
+        }
+
+        let mut pool = self.pool_metadata_by_id.get(&pool_id)
+            .expect("Pool does not exist");
+        let mut user_records = self.user_records.get(&pool_id)
+            .expect("No user records found for this pool");
+        let mut user_record = user_records.get(&user_id)
+            .expect("User has no record in this pool");
+
+        let mut token_pledges = self.internal_token_pledges(&pool, &user_id, &user_record);
+        add_token_pledge(&mut token_pledges, &token_pledge.token_id, token_pledge.amount, token_pledge.value);
+        self.token_pledges.insert(&(pool_id, user_id.clone()), &token_pledges);
+
+        let pool_token = pool_token_mut(&mut pool, &token_pledge.token_id);
+        pool_token.balance += amount.0;
+        pool_token.pledged += token_pledge.amount;
+        pool.total_balance += value.0;
+        pool.total_voting_power += token_pledge.value;
+        user_record.amount += token_pledge.value;
+        user_record.voting_power += token_pledge.value;
+        if let Some(tier_index) = tier_index {
+            self.internal_restore_tier(pool_id, &user_id, tier_index);
+        }
+
+        user_records.insert(&user_id, &user_record);
+        self.user_records.insert(&pool_id, &user_records);
+        self.pool_metadata_by_id.insert(&pool_id, &pool);
+
+        event.emit_failed();
+    }
+
+    // restore the backer's pledge in the token and a released reward tier if the transfer of the withdrawn amount failed,
+    // the penalty never left the pool
+    #[private]
+    fn resolve_withdraw_pledge(&mut self, pool_id: PoolId, user_id: AccountId, token_pledge: TokenPledge, penalty: U128, value: U128, tier_index: Option<u32>) {
+        let amount = token_pledge.amount - penalty.0;
+        let event = PledgeWithdrawn {
+            pool_id,
+            account_id: &user_id,
+            token_id: &token_pledge.token_id,
+            amount: U128(amount),
+            penalty,
+        };
+
+        if is_promise_success() {
+            event.emit();
+            return;
+        }
+
+        let mut pool = self.pool_metadata_by_id.get(&pool_id)
+            .expect("Pool does not exist");
+        let mut user_records = self.user_records.get(&pool_id)
+            .expect("No user records found for this pool");
+        let mut user_record = user_records.get(&user_id)
+            .expect("User has no record in this pool");
+
+        let mut token_pledges = self.internal_token_pledges(&pool, &user_id, &user_record);
+        add_token_pledge(&mut token_pledges, &token_pledge.token_id, token_pledge.amount, token_pledge.value);
+        self.token_pledges.insert(&(pool_id, user_id.clone()), &token_pledges);
+        self.internal_add_referred_pledge(pool_id, &user_id, None, &token_pledge.token_id, token_pledge.amount, token_pledge.value);
+
+        let pool_token = pool_token_mut(&mut pool, &token_pledge.token_id);
+        pool_token.balance += amount;
+        pool_token.pledged += token_pledge.amount;
+        pool.total_balance += value.0;
+        user_record.amount += token_pledge.value;
+        if let Some(tier_index) = tier_index {
+            self.internal_restore_tier(pool_id, &user_id, tier_index);
+        }
+
+        user_records.insert(&user_id, &user_record);
+        self.user_records.insert(&pool_id, &user_records);
+        self.pool_metadata_by_id.insert(&pool_id, &pool);
+
+        event.emit_failed();
+    }
+
+    // put the NEAR back into the treasury if the transfer failed
+    #[private]
+    fn resolve_withdraw_treasury(&mut self, receiver_id: AccountId, amount: U128) {
+        let event = TreasuryWithdraw {
+            receiver_id: &receiver_id,
+            amount,
+        };
+
+        if is_promise_success() {
+            event.emit();
+            return;
+        }
+
+        self.treasury.balance += amount.0;
+        self.treasury.withdrawn -= amount.0;
+
+        event.emit_failed();
+    }
+
+    // put the fees back into the token's balance if the transfer to the collector failed
+    #[private]
+    fn resolve_withdraw_fees(&mut self, token_id: AccountId, receiver_id: AccountId, amount: U128) {
+        let event = FeeWithdraw {
+            token_id: &token_id,
+            receiver_id: &receiver_id,
+            amount,
+        };
+
+        if is_promise_success() {
+            event.emit();
+            return;
+        }
+
+        self.internal_asset_mut(&token_id).balances += amount.0;
+
+        event.emit_failed();
+    }
+
+    // put the reward back into the token's fees and let the referrer claim it again if the transfer failed
+    #[private]
+    fn resolve_claim_referral_reward(&mut self, pool_id: PoolId, referrer_id: AccountId, token_id: AccountId, amount: U128) {
+        let event = ReferralReward {
+            pool_id,
+            referrer_id: &referrer_id,
+            token_id: &token_id,
+            amount,
+        };
+
+        if is_promise_success() {
+            event.emit();
+            return;
+        }
+
+        let mut referrals = self.referrals_by_pool_id.get(&pool_id)
+            .expect("Pool has no referrals");
+        let mut stats = referrals.get(&referrer_id)
+            .expect("Referrer has no stats in this pool");
+
+        stats.rewarded_tokens.retain(|rewarded_token_id| rewarded_token_id != &token_id);
+        referrals.insert(&referrer_id, &stats);
+        self.referrals_by_pool_id.insert(&pool_id, &referrals);
+
+        let key = (pool_id, token_id.clone());
+        let mut reserve = self.referral_reserves.get(&key).expect("Pool has no referral reserve");
+        reserve.paid -= amount.0;
+        self.referral_reserves.insert(&key, &reserve);
+
+        event.emit_failed();
+    }
+
+    // let the backer claim the token's part of the excess again if the transfer failed
+    #[private]
+    fn resolve_claim_excess(&mut self, pool_id: PoolId, user_id: AccountId, token_pledge: TokenPledge) {
+        let event = Refund {
+            pool_id,
+            account_id: &user_id,
+            token_id: &token_pledge.token_id,
+            amount: U128(token_pledge.amount),
+        };
+
+        if is_promise_success() {
+            event.emit();
+            return;
+        }
+
+        let key = (pool_id, user_id.clone());
+        let mut excess = self.excess_by_backer.get(&key).unwrap_or_default();
+        add_token_pledge(&mut excess, &token_pledge.token_id, token_pledge.amount, token_pledge.value);
+        self.excess_by_backer.insert(&key, &excess);
+
+        event.emit_failed();
+    }
+
+    // accrue the fee once the token's part of the tranche reached the creator, put the part back into the pool
+    // if the transfer failed, the milestone is released again once no part of it reached the creator
+    #[private]
+    fn resolve_withdraw_to_creator(&mut self, pool_id: PoolId, milestone_index: u32, token_id: AccountId, amount: U128, fee: U128, value: U128) {
+        let mut pool = self.pool_metadata_by_id.get(&pool_id)
+            .expect("Pool does not exist");
+        let mut milestones = self.milestones_by_pool_id.get(&pool_id)
+            .expect("Pool has no milestones");
+        let creator_id = pool.creator_id.clone();
+
+        let event = CreatorWithdraw {
+            pool_id,
+            creator_id: &creator_id,
+            milestone_index,
+            token_id: &token_id,
+            amount: U128(amount.0 - fee.0),
+            fee,
+        };
+
+        if is_promise_success() {
+            let platform_fee = self.internal_reserve_referral_rewards(pool_id, &token_id, fee.0);
+            self.internal_asset_mut(&token_id).balances += platform_fee;
+            event.emit();
+            return;
+        }
+
+        // any failed token reopens the milestone so withdraw_to_creator can send what came back
+        let milestone = &mut milestones[milestone_index as usize];
+        milestone.amount_released -= value.0;
+        milestone.status = MilestoneStatus::APPROVED;
+        pool_token_mut(&mut pool, &token_id).balance += amount.0;
+        pool.total_balance += value.0;
+
+        self.milestones_by_pool_id.insert(&pool_id, &milestones);
+        self.pool_metadata_by_id.insert(&pool_id, &pool);
+
+        event.emit_failed();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use near_sdk::collections::UnorderedMap;
+    use near_sdk::test_utils::VMContextBuilder;
+    use near_sdk::{testing_env, PromiseResult, RuntimeFeesConfig, VMConfig};
+
+    use super::*;
+    use crate::models::contract::{
+        Assets, LaunchpadFeature, LaunchpadKeeper, LaunchpadReferrals, LaunchpadStorageKey, LaunchpadTreasury, Milestone, PoolMetadata, PoolToken, RewardTier, Status, UserTokenDepositRecord
+    };
+    use crate::models::time::Timestamp;
+
+    const POOL_ID: PoolId = 1;
+    const PLEDGE: u128 = 1_000;
+
+    fn account(name: &str) -> AccountId {
+        format!("{}.test.near", name).parse().unwrap()
+    }
+
+    fn set_caller(caller_id: &AccountId, promise_results: Vec<PromiseResult>) {
+        let context = VMContextBuilder::new()
+            .current_account_id(account("launchpad"))
+            .predecessor_account_id(caller_id.clone())
+            .build();
+        testing_env!(context, VMConfig::test(), RuntimeFeesConfig::test(), Default::default(), promise_results);
+    }
+
+    // a pool holding one backer's pledge, who took the only slot of a reward tier
+    fn contract_with_backer(status: Status) -> Launchpad {
+        set_caller(&account("owner"), Vec::new());
+        let mut contract = Launchpad::new(account("owner"));
+        let backer_id = account("backer");
+
+        contract.all_pool_id.insert(&POOL_ID);
+        contract.pool_metadata_by_id.insert(&POOL_ID, &PoolMetadata {
+            pool_id: POOL_ID,
+            campaign_id: "campaign".to_string(),
+            creator_id: account("creator"),
+            staking_amount: 0,
+            status,
+            token_id: account("token"),
+            total_balance: PLEDGE,
+            target_funding: PLEDGE * 2,
+            time_init: Timestamp(0),
+            time_start_pledge: Timestamp(0),
+            time_end_pledge: Timestamp(u64::MAX),
+            funding_duration_days: 1,
+            min_multiple_pledge: 1,
+            total_voting_power: PLEDGE,
+            min_pledge: 0,
+            max_pledge: 0,
+            hard_cap: 0,
+            withdraw_penalty_percent: 0,
+            tokens: vec![PoolToken { token_id: account("token"), balance: PLEDGE, pledged: PLEDGE }],
+        });
+
+        let mut user_records = UnorderedMap::new(LaunchpadStorageKey::user_records_prefix(POOL_ID));
+        user_records.insert(&backer_id, &UserTokenDepositRecord { amount: PLEDGE, voting_power: PLEDGE });
+        contract.user_records.insert(&POOL_ID, &user_records);
+
+        contract.reward_tiers_by_pool_id.insert(&POOL_ID, &vec![RewardTier {
+            min_pledge: PLEDGE,
+            quantity: 1,
+            allocated: 1,
+            description: "tier".to_string(),
+        }]);
+        contract.backer_tiers.insert(&(POOL_ID, backer_id), &0);
+        contract
+    }
+
+    fn token_pledge() -> TokenPledge {
+        TokenPledge { token_id: account("token"), amount: PLEDGE, value: PLEDGE }
+    }
+
+    fn allocated(contract: &Launchpad) -> u32 {
+        contract.reward_tiers_by_pool_id.get(&POOL_ID).unwrap()[0].allocated
+    }
+
+    // a successful pool whose only milestone was approved, with 5 of the token accrued in fees and a 1% fee
+    fn contract_with_tranche() -> Launchpad {
+        let mut contract = contract_with_backer(Status::VOTING);
+        contract.list_assets.push(Assets { token_id: account("token"), balances: 5 });
+        contract.platform_fee_bps = 100;
+        contract.milestones_by_pool_id.insert(&POOL_ID, &vec![Milestone {
+            percent: 100,
+            deadline: Timestamp(0),
+            status: MilestoneStatus::APPROVED,
+            amount_released: 0,
+        }]);
+        contract
+    }
+
+    fn fees(contract: &Launchpad) -> u128 {
+        contract.list_assets.iter().find(|asset| asset.token_id == account("token")).unwrap().balances
+    }
+
+    #[test]
+    fn failed_refund_restores_reward_tier() {
+        let mut contract = contract_with_backer(Status::REFUNDED);
+        let backer_id = account("backer");
+
+        set_caller(&backer_id, Vec::new());
+        contract.claim_refund(POOL_ID);
+        assert_eq!(contract.backer_tiers.get(&(POOL_ID, backer_id.clone())), None);
+        assert_eq!(allocated(&contract), 0);
+
+        set_caller(&account("launchpad"), vec![PromiseResult::Failed]);
+        contract.resolve_claim_refund(POOL_ID, backer_id.clone(), token_pledge(), U128(PLEDGE), U128(PLEDGE), Some(0));
+        assert_eq!(contract.backer_tiers.get(&(POOL_ID, backer_id.clone())), Some(0));
+        assert_eq!(allocated(&contract), 1);
+        assert_eq!(contract.user_records.get(&POOL_ID).unwrap().get(&backer_id).unwrap().amount, PLEDGE);
+    }
+
+    #[test]
+    fn failed_withdraw_restores_reward_tier() {
+        let mut contract = contract_with_backer(Status::FUNDING);
+        let backer_id = account("backer");
+
+        set_caller(&backer_id, Vec::new());
+        contract.withdraw_pledge(POOL_ID, None);
+        assert_eq!(contract.backer_tiers.get(&(POOL_ID, backer_id.clone())), None);
+        assert_eq!(allocated(&contract), 0);
+
+        set_caller(&account("launchpad"), vec![PromiseResult::Failed]);
+        contract.resolve_withdraw_pledge(POOL_ID, backer_id.clone(), token_pledge(), U128(0), U128(PLEDGE), Some(0));
+        assert_eq!(contract.backer_tiers.get(&(POOL_ID, backer_id.clone())), Some(0));
+        assert_eq!(allocated(&contract), 1);
+        assert_eq!(contract.user_records.get(&POOL_ID).unwrap().get(&backer_id).unwrap().amount, PLEDGE);
+    }
+
+    #[test]
+    fn failed_tranche_after_fee_withdraw_keeps_fee_ledger() {
+        let mut contract = contract_with_tranche();
+
+        set_caller(&account("owner"), Vec::new());
+        contract.withdraw_to_creator(POOL_ID);
+        assert_eq!(fees(&contract), 5, "The fee should accrue only once the tranche reached the creator.");
+
+        // the fees accrued so far leave while the tranche is in flight
+        contract.withdraw_fees(account("token"), None);
+        assert_eq!(fees(&contract), 0);
+
+        set_caller(&account("launchpad"), vec![PromiseResult::Failed]);
+        contract.resolve_withdraw_to_creator(POOL_ID, 0, account("token"), U128(PLEDGE), U128(PLEDGE / 100), U128(PLEDGE));
+        assert_eq!(fees(&contract), 0);
+
+        let pool = contract.pool_metadata_by_id.get(&POOL_ID).unwrap();
+        assert_eq!((pool.total_balance, pool.tokens[0].balance), (PLEDGE, PLEDGE));
+        let milestone = &contract.milestones_by_pool_id.get(&POOL_ID).unwrap()[0];
+        assert_eq!((&milestone.status, milestone.amount_released), (&MilestoneStatus::APPROVED, 0));
+    }
+
+    #[test]
+    fn delivered_tranche_accrues_fee() {
+        let mut contract = contract_with_tranche();
+
+        set_caller(&account("owner"), Vec::new());
+        contract.withdraw_to_creator(POOL_ID);
+
+        set_caller(&account("launchpad"), vec![PromiseResult::Successful(Vec::new())]);
+        contract.resolve_withdraw_to_creator(POOL_ID, 0, account("token"), U128(PLEDGE), U128(PLEDGE / 100), U128(PLEDGE));
+        assert_eq!(fees(&contract), 5 + PLEDGE / 100);
+        assert_eq!(contract.pool_metadata_by_id.get(&POOL_ID).unwrap().total_balance, 0);
+    }
+
+    #[test]
+    fn referral_share_of_the_fee_is_reserved_for_the_pool() {
+        let mut contract = contract_with_tranche();
+        contract.referral_reward_bps = 50;
+        contract.internal_add_referred_pledge(POOL_ID, &account("backer"), Some(&account("referrer")), &account("token"), PLEDGE, PLEDGE);
+
+        set_caller(&account("owner"), Vec::new());
+        contract.withdraw_to_creator(POOL_ID);
+        set_caller(&account("launchpad"), vec![PromiseResult::Successful(Vec::new())]);
+        contract.resolve_withdraw_to_creator(POOL_ID, 0, account("token"), U128(PLEDGE), U128(PLEDGE / 100), U128(PLEDGE));
+
+        // half of the 1% fee is owed to the referrer, only the other half accrues to the platform
+        let reward = PLEDGE / 200;
+        assert_eq!(fees(&contract), 5 + PLEDGE / 100 - reward);
+        assert_eq!(contract.get_referral_reserve(POOL_ID, account("token")).reserved, reward);
+
+        set_caller(&account("owner"), Vec::new());
+        contract.withdraw_fees(account("token"), None);
+        assert_eq!(fees(&contract), 0);
+
+        let mut pool = contract.pool_metadata_by_id.get(&POOL_ID).unwrap();
+        pool.status = Status::SUCCESSFUL;
+        contract.pool_metadata_by_id.insert(&POOL_ID, &pool);
+
+        set_caller(&account("referrer"), Vec::new());
+        contract.claim_referral_reward(POOL_ID);
+        assert_eq!(contract.get_referral_reserve(POOL_ID, account("token")).paid, reward);
+
+        set_caller(&account("launchpad"), vec![PromiseResult::Failed]);
+        contract.resolve_claim_referral_reward(POOL_ID, account("referrer"), account("token"), U128(reward));
+        assert_eq!(contract.get_referral_reserve(POOL_ID, account("token")).paid, 0);
+        assert_eq!(fees(&contract), 0, "A failed reward should go back to the pool's reserve, not to the fees.");
+    }
+
+    #[test]
+    fn partially_failed_tranche_can_be_retried() {
+        let mut contract = contract_with_tranche();
+        contract.list_assets.push(Assets { token_id: account("token2"), balances: 0 });
+        contract.milestones_by_pool_id.insert(&POOL_ID, &vec![
+            Milestone { percent: 50, deadline: Timestamp(0), status: MilestoneStatus::APPROVED, amount_released: 0 },
+            Milestone { percent: 50, deadline: Timestamp(0), status: MilestoneStatus::PENDING, amount_released: 0 },
+        ]);
+        let mut pool = contract.pool_metadata_by_id.get(&POOL_ID).unwrap();
+        pool.tokens = vec![
+            PoolToken { token_id: account("token"), balance: PLEDGE / 2, pledged: PLEDGE / 2 },
+            PoolToken { token_id: account("token2"), balance: PLEDGE / 2, pledged: PLEDGE / 2 },
+        ];
+        contract.pool_metadata_by_id.insert(&POOL_ID, &pool);
+
+        set_caller(&account("owner"), Vec::new());
+        contract.withdraw_to_creator(POOL_ID);
+
+        // the first token reaches the creator, the second one bounces
+        let tranche = PLEDGE / 4;
+        set_caller(&account("launchpad"), vec![PromiseResult::Successful(Vec::new())]);
+        contract.resolve_withdraw_to_creator(POOL_ID, 0, account("token"), U128(tranche), U128(tranche / 100), U128(tranche));
+        set_caller(&account("launchpad"), vec![PromiseResult::Failed]);
+        contract.resolve_withdraw_to_creator(POOL_ID, 0, account("token2"), U128(tranche), U128(tranche / 100), U128(tranche));
+
+        let milestone = &contract.milestones_by_pool_id.get(&POOL_ID).unwrap()[0];
+        assert_eq!((&milestone.status, milestone.amount_released), (&MilestoneStatus::APPROVED, tranche));
+        assert_eq!(contract.pool_metadata_by_id.get(&POOL_ID).unwrap().total_balance, PLEDGE - tranche);
+
+        // the retry only sends the value that came back
+        set_caller(&account("owner"), Vec::new());
+        contract.withdraw_to_creator(POOL_ID);
+
+        let milestone = &contract.milestones_by_pool_id.get(&POOL_ID).unwrap()[0];
+        assert_eq!((&milestone.status, milestone.amount_released), (&MilestoneStatus::RELEASED, PLEDGE / 2));
+        assert_eq!(contract.pool_metadata_by_id.get(&POOL_ID).unwrap().total_balance, PLEDGE / 2);
+    }
+
+    #[test]
+    fn expired_init_pool_pays_no_keeper_reward() {
+        let mut contract = contract_with_backer(Status::INIT);
+        let mut pool = contract.pool_metadata_by_id.get(&POOL_ID).unwrap();
+        pool.staking_amount = 10 * contract.keeper_reward;
+        contract.pool_metadata_by_id.insert(&POOL_ID, &pool);
+        contract.treasury.balance = contract.keeper_reward;
+
+        // the creator's own expired pool refunds the stake without draining the treasury
+        let context = VMContextBuilder::new()
+            .current_account_id(account("launchpad"))
+            .predecessor_account_id(account("creator"))
+            .block_timestamp(100 * 24 * 60 * 60 * 1_000_000_000)
+            .build();
+        testing_env!(context);
+        let pool = contract.advance_pool(POOL_ID);
+        assert_eq!(pool.status, Status::REJECTED);
+
+        let retained = contract.treasury.retained_stakes;
+        assert_eq!(contract.treasury.keeper_rewards_paid, 0);
+        assert_eq!(contract.treasury.balance, contract.keeper_reward + retained);
+    }
+
+    #[test]
+    fn restored_tier_does_not_replace_a_newer_one() {
+        let mut contract = contract_with_backer(Status::REFUNDED);
+        let backer_id = account("backer");
+
+        set_caller(&account("launchpad"), vec![PromiseResult::Failed]);
+        contract.resolve_claim_refund(POOL_ID, backer_id.clone(), token_pledge(), U128(PLEDGE), U128(PLEDGE), Some(0));
+        assert_eq!(allocated(&contract), 1, "A tier the backer still holds should not be allocated twice.");
+    }
+}
@@ -0,0 +1,57 @@
+use near_sdk::{env, near_bindgen, serde_json::json, AccountId};
+
+use crate::application::repository::{build_reward_tiers, is_before_funding};
+use crate::models::{
+    contract::{BackerTier, Launchpad, LaunchpadExt, LaunchpadRewards, RewardTier, RewardTierInfo},
+    events::ConfigChanged,
+    PoolId
+};
+
+#[near_bindgen]
+impl LaunchpadRewards for Launchpad {
+
+    /* //////////////////////////////////////////////////////////////
+                            CREATOR FUNCTIONS
+    ////////////////////////////////////////////////////////////// */
+    // creator declares the reward tiers of the pool before funding opens, replacing the ones set before
+    fn set_reward_tiers(&mut self, pool_id: PoolId, tiers: Vec<RewardTierInfo>) {
+        let pool = self.pool_metadata_by_id.get(&pool_id)
+            .expect("Pool does not exist");
+
+        if env::predecessor_account_id() != pool.creator_id {
+            env::panic_str("Only the creator of the pool can set reward tiers.");
+        }
+
+        if !is_before_funding(&pool) {
+            env::panic_str("Reward tiers can only change before funding");
+        }
+
+        let tiers = build_reward_tiers(tiers);
+        self.reward_tiers_by_pool_id.insert(&pool_id, &tiers);
+
+        ConfigChanged {
+            pool_id: Some(pool_id),
+            setting: "reward_tiers",
+            value: json!(tiers),
+        }
+        .emit();
+    }
+
+    /* //////////////////////////////////////////////////////////////
+                            GETTER FUNCTIONS
+    ////////////////////////////////////////////////////////////// */
+
+    fn get_reward_tiers(&self, pool_id: PoolId) -> Vec<RewardTier> {
+        self.reward_tiers_by_pool_id.get(&pool_id).unwrap_or_default()
+    }
+
+    // tier a backer holds, earned once the pool ends SUCCESSFUL
+    fn get_backer_tier(&self, pool_id: PoolId, account_id: AccountId) -> Option<BackerTier> {
+        let tier_index = self.backer_tiers.get(&(pool_id, account_id))?;
+        let tier = self.reward_tiers_by_pool_id.get(&pool_id)?
+            .get(tier_index as usize)?
+            .clone();
+
+        Some(BackerTier { tier_index, tier })
+    }
+}
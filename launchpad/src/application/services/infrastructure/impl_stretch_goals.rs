@@ -0,0 +1,103 @@
+use near_sdk::{env, near_bindgen, serde_json::json, AccountId};
+
+use super::impl_launchpad::GAS_FOR_REFUND_CALLBACK;
+use crate::application::repository::{build_stretch_goals, is_before_funding, transfer_asset};
+use crate::models::{
+    contract::{
+        Feature, Launchpad, LaunchpadExt, LaunchpadStretchGoals, OverfundingPolicy, StretchGoal, StretchGoalInfo, TokenPledge
+    },
+    events::ConfigChanged,
+    ft_request::external::ext_self,
+    PoolId
+};
+
+#[near_bindgen]
+impl LaunchpadStretchGoals for Launchpad {
+
+    /* //////////////////////////////////////////////////////////////
+                            CREATOR FUNCTIONS
+    ////////////////////////////////////////////////////////////// */
+    // creator sets the goals above the funding target the pool works towards, unlocked when funding closes
+    fn set_stretch_goals(&mut self, pool_id: PoolId, goals: Vec<StretchGoalInfo>) {
+        let pool = self.assert_pool_creator(pool_id);
+
+        if !is_before_funding(&pool) {
+            env::panic_str("Stretch goals can only change before funding");
+        }
+
+        let goals = build_stretch_goals(&pool, goals);
+        if goals.is_empty() {
+            self.stretch_goals_by_pool_id.remove(&pool_id);
+        } else {
+            self.stretch_goals_by_pool_id.insert(&pool_id, &goals);
+        }
+
+        ConfigChanged {
+            pool_id: Some(pool_id),
+            setting: "stretch_goals",
+            value: json!(goals),
+        }
+        .emit();
+    }
+
+    // creator chooses what happens to the raise above the highest goal reached once funding closes
+    fn set_overfunding_policy(&mut self, pool_id: PoolId, policy: OverfundingPolicy) {
+        let pool = self.assert_pool_creator(pool_id);
+
+        if !is_before_funding(&pool) {
+            env::panic_str("Overfunding policy can only change before funding");
+        }
+
+        if policy == OverfundingPolicy::AcceptAll {
+            self.overfunding_by_pool_id.remove(&pool_id);
+        } else {
+            self.overfunding_by_pool_id.insert(&pool_id, &policy);
+        }
+
+        ConfigChanged {
+            pool_id: Some(pool_id),
+            setting: "overfunding_policy",
+            value: json!(policy),
+        }
+        .emit();
+    }
+
+    /* //////////////////////////////////////////////////////////////
+                            USER FUNCTIONS
+    ////////////////////////////////////////////////////////////// */
+    // backer takes back the part of its pledge the overfunding policy set aside, in each token pledged
+    fn claim_excess(&mut self, pool_id: PoolId) {
+        self.assert_not_paused(Feature::Refund);
+
+        let caller_id = env::predecessor_account_id();
+
+        let excess = self.excess_by_backer.remove(&(pool_id, caller_id.clone()))
+            .unwrap_or_else(|| env::panic_str("No excess pledge to claim in this pool"));
+
+        // the ledger is updated now and restored by the callback if the transfer fails
+        for token_pledge in excess {
+            transfer_asset(&token_pledge.token_id, caller_id.clone(), token_pledge.amount)
+                .then(
+                    ext_self::ext(env::current_account_id())
+                        .with_static_gas(GAS_FOR_REFUND_CALLBACK)
+                        .resolve_claim_excess(pool_id, caller_id.clone(), token_pledge)
+                );
+        }
+    }
+
+    /* //////////////////////////////////////////////////////////////
+                            GETTER FUNCTIONS
+    ////////////////////////////////////////////////////////////// */
+
+    fn get_stretch_goals(&self, pool_id: PoolId) -> Vec<StretchGoal> {
+        self.stretch_goals_by_pool_id.get(&pool_id).unwrap_or_default()
+    }
+
+    fn get_overfunding_policy(&self, pool_id: PoolId) -> OverfundingPolicy {
+        self.overfunding_by_pool_id.get(&pool_id).unwrap_or(OverfundingPolicy::AcceptAll)
+    }
+
+    fn get_excess(&self, pool_id: PoolId, account_id: AccountId) -> Vec<TokenPledge> {
+        self.excess_by_backer.get(&(pool_id, account_id)).unwrap_or_default()
+    }
+}
@@ -0,0 +1,207 @@
+use near_sdk::{env, json_types::U128, near_bindgen, serde_json::json, AccountId, Promise};
+
+use super::impl_launchpad::GAS_FOR_WITHDRAW_CALLBACK;
+use crate::application::repository::transfer_asset;
+use crate::models::{
+    contract::{
+        Assets, Launchpad, LaunchpadExt, LaunchpadTreasury, Role, TreasuryLedger,
+        MAX_PLATFORM_FEE_BPS
+    },
+    events::ConfigChanged,
+    ft_request::external::ext_self,
+};
+
+#[near_bindgen]
+impl LaunchpadTreasury for Launchpad {
+
+    /* //////////////////////////////////////////////////////////////
+                            ADMIN FUNCTIONS
+    ////////////////////////////////////////////////////////////// */
+    // admin withdraws NEAR kept by the treasury, all of it to the admin when nothing is given
+    fn withdraw_treasury(&mut self, amount: Option<U128>, receiver_id: Option<AccountId>) {
+        self.assert_owner();
+
+        let amount = amount.map_or(self.treasury.balance, |amount| amount.0);
+        if amount == 0 || amount > self.treasury.balance {
+            env::panic_str("Withdraw amount must be between 1 and the treasury balance");
+        }
+
+        let receiver_id = receiver_id.unwrap_or_else(env::predecessor_account_id);
+
+        // the ledger is updated now and restored by the callback if the transfer fails
+        self.treasury.balance -= amount;
+        self.treasury.withdrawn += amount;
+
+        Promise::new(receiver_id.clone())
+            .transfer(amount)
+            .then(
+                ext_self::ext(env::current_account_id())
+                    .with_static_gas(GAS_FOR_WITHDRAW_CALLBACK)
+                    .resolve_withdraw_treasury(receiver_id, U128(amount))
+            );
+    }
+
+    // admin sets the share of the stake a creator loses when a milestone fails
+    fn set_stake_slash_percent(&mut self, percent: u8) {
+        self.assert_owner();
+
+        if percent > 100 {
+            env::panic_str("Slash percentage must be between 0 and 100");
+        }
+
+        self.stake_slash_percent = percent;
+
+        ConfigChanged {
+            pool_id: None,
+            setting: "stake_slash_percent",
+            value: json!(percent),
+        }
+        .emit();
+    }
+
+    // admin sets the account platform fees are withdrawn to
+    fn set_fee_collector(&mut self, account_id: AccountId) {
+        self.assert_owner();
+
+        ConfigChanged {
+            pool_id: None,
+            setting: "fee_collector",
+            value: json!(account_id),
+        }
+        .emit();
+        self.fee_collector_id = account_id;
+    }
+
+    /* //////////////////////////////////////////////////////////////
+                        TREASURY MANAGER FUNCTIONS
+    ////////////////////////////////////////////////////////////// */
+    // treasury manager sets the fee kept from tranches of tokens without a fee of their own
+    fn set_platform_fee(&mut self, fee_bps: u16) {
+        self.assert_role(Role::TreasuryManager);
+
+        if fee_bps > MAX_PLATFORM_FEE_BPS {
+            env::panic_str(&format!("Platform fee cannot exceed {} basis points", MAX_PLATFORM_FEE_BPS));
+        }
+
+        self.platform_fee_bps = fee_bps;
+
+        ConfigChanged {
+            pool_id: None,
+            setting: "platform_fee_bps",
+            value: json!(fee_bps),
+        }
+        .emit();
+    }
+
+    // treasury manager sets the fee of one token, none makes it use the platform fee again
+    fn set_token_fee(&mut self, token_id: AccountId, fee_bps: Option<u16>) {
+        self.assert_role(Role::TreasuryManager);
+
+        if !self.list_assets.iter().any(|asset| asset.token_id == token_id) {
+            env::panic_str("Token is not supported");
+        }
+
+        match fee_bps {
+            Some(fee_bps) if fee_bps > MAX_PLATFORM_FEE_BPS => {
+                env::panic_str(&format!("Platform fee cannot exceed {} basis points", MAX_PLATFORM_FEE_BPS));
+            },
+            Some(fee_bps) => {
+                self.fee_bps_by_token.insert(&token_id, &fee_bps);
+            },
+            None => {
+                self.fee_bps_by_token.remove(&token_id);
+            },
+        }
+
+        ConfigChanged {
+            pool_id: None,
+            setting: "token_fee_bps",
+            value: json!({"token_id": token_id, "fee_bps": fee_bps}),
+        }
+        .emit();
+    }
+
+    // treasury manager sets how many reference units one unit of a token is worth, scaled by
+    // RATE_DENOMINATOR; pledges made before keep the worth they were accepted at
+    fn set_token_rate(&mut self, token_id: AccountId, rate: Option<U128>) {
+        self.assert_role(Role::TreasuryManager);
+
+        if !self.list_assets.iter().any(|asset| asset.token_id == token_id) {
+            env::panic_str("Token is not supported");
+        }
+
+        match rate {
+            Some(rate) if rate.0 == 0 => {
+                env::panic_str("Conversion rate must be greater than zero");
+            },
+            Some(rate) => {
+                self.token_rates.insert(&token_id, &rate.0);
+            },
+            None => {
+                self.token_rates.remove(&token_id);
+            },
+        }
+
+        ConfigChanged {
+            pool_id: None,
+            setting: "token_rate",
+            value: json!({"token_id": token_id, "rate": rate}),
+        }
+        .emit();
+    }
+
+    // the fee collector or a treasury manager sends the accrued fees of a token to the fee collector,
+    // all of them when no amount is given; the referral reserves of the pools are never part of them
+    fn withdraw_fees(&mut self, token_id: AccountId, amount: Option<U128>) {
+        if env::predecessor_account_id() != self.fee_collector_id {
+            self.assert_role(Role::TreasuryManager);
+        }
+
+        let receiver_id = self.fee_collector_id.clone();
+        let asset = self.internal_asset_mut(&token_id);
+
+        let amount = amount.map_or(asset.balances, |amount| amount.0);
+        if amount == 0 || amount > asset.balances {
+            env::panic_str("Withdraw amount must be between 1 and the accrued fees");
+        }
+
+        // the ledger is updated now and restored by the callback if the transfer fails
+        asset.balances -= amount;
+
+        transfer_asset(&token_id, receiver_id.clone(), amount)
+            .then(
+                ext_self::ext(env::current_account_id())
+                    .with_static_gas(GAS_FOR_WITHDRAW_CALLBACK)
+                    .resolve_withdraw_fees(token_id, receiver_id, U128(amount))
+            );
+    }
+
+    /* //////////////////////////////////////////////////////////////
+                            GETTER FUNCTIONS
+    ////////////////////////////////////////////////////////////// */
+
+    fn get_platform_fee(&self, token_id: Option<AccountId>) -> u16 {
+        token_id.map_or(self.platform_fee_bps, |token_id| self.internal_fee_bps(&token_id))
+    }
+
+    fn get_fee_collector(&self) -> AccountId {
+        self.fee_collector_id.clone()
+    }
+
+    // supported tokens with the platform fees accrued in each
+    fn get_assets(&self) -> Vec<Assets> {
+        self.list_assets.clone()
+    }
+
+    fn get_token_rate(&self, token_id: AccountId) -> U128 {
+        U128(self.internal_token_rate(&token_id))
+    }
+
+    fn get_treasury(&self) -> TreasuryLedger {
+        self.treasury.clone()
+    }
+
+    fn get_stake_slash_percent(&self) -> u8 {
+        self.stake_slash_percent
+    }
+}
@@ -0,0 +1,284 @@
+use near_sdk::{env, json_types::U128, near_bindgen, serde_json::json};
+
+use crate::application::repository::{current_milestone_index, is_milestone_expired, tally_outcome};
+use crate::models::events::{ConfigChanged, MilestoneFailed, PoolStatusChanged, ProposalCreated, ProposalFinalized, VoteCast};
+use crate::models::{
+    contract::{
+        Launchpad, LaunchpadExt, LaunchpadVoting, LaunchpadStorageKey,
+        MilestoneStatus, PoolMetadata, Proposal, ProposalStatus, Status, Vote
+    },
+    time::{Duration, Timestamp},
+    PoolId
+};
+use near_sdk::collections::UnorderedMap;
+
+#[near_bindgen]
+impl LaunchpadVoting for Launchpad {
+
+    /* //////////////////////////////////////////////////////////////
+                            ADMIN FUNCTIONS
+    ////////////////////////////////////////////////////////////// */
+    // admin can change the quorum (percent of total voting power) and voting duration (nanoseconds)
+    fn set_voting_config(&mut self, quorum_percent: u8, duration: Duration) {
+        self.assert_owner();
+
+        if quorum_percent == 0 || quorum_percent > 100 {
+            env::panic_str("Quorum percentage must be between 1 and 100");
+        }
+
+        if duration.is_zero() {
+            env::panic_str("Voting duration must be greater than 0");
+        }
+
+        self.voting_quorum_percent = quorum_percent;
+        self.voting_duration = duration;
+
+        ConfigChanged {
+            pool_id: None,
+            setting: "voting_config",
+            value: json!({"quorum_percent": quorum_percent, "duration": duration}),
+        }
+        .emit();
+    }
+
+    /* //////////////////////////////////////////////////////////////
+                            CREATOR FUNCTIONS
+    ////////////////////////////////////////////////////////////// */
+    // creator submits a disbursement request for the current milestone of a pool in VOTING status
+    fn create_proposal(&mut self, pool_id: PoolId, description: String) -> Proposal {
+        let pool = self.pool_metadata_by_id.get(&pool_id)
+            .expect("Pool does not exist");
+
+        if env::predecessor_account_id() != pool.creator_id {
+            env::panic_str("Only the creator of the pool can create a proposal.");
+        }
+
+        if pool.status != Status::VOTING {
+            env::panic_str("Pool must be in VOTING status to create a proposal");
+        }
+
+        // without voting power no proposal could ever reach quorum
+        if pool.total_voting_power == 0 {
+            env::panic_str("Pool has no voting power to vote on a proposal");
+        }
+
+        if let Some(proposal) = self.proposal_by_pool_id.get(&pool_id) {
+            if proposal.status == ProposalStatus::ACTIVE {
+                env::panic_str("Pool already has an active proposal");
+            }
+        }
+
+        let milestones = self.milestones_by_pool_id.get(&pool_id)
+            .expect("Pool has no milestones");
+
+        if milestones.iter().any(|milestone| milestone.status == MilestoneStatus::APPROVED) {
+            env::panic_str("Approved milestone must be released before creating a new proposal");
+        }
+
+        let milestone_index = current_milestone_index(&milestones)
+            .unwrap_or_else(|| env::panic_str("Pool has no pending milestone"));
+
+        let current_time = Timestamp::now();
+        if is_milestone_expired(&milestones[milestone_index], current_time) {
+            env::panic_str("Milestone deadline has passed");
+        }
+
+        let proposal = Proposal {
+            pool_id,
+            milestone_index: milestone_index as u32,
+            description,
+            time_start_voting: current_time,
+            time_end_voting: current_time + self.voting_duration,
+            approve_power: 0,
+            reject_power: 0,
+            status: ProposalStatus::ACTIVE,
+        };
+
+        self.proposal_by_pool_id.insert(&pool_id, &proposal);
+        self.votes_by_pool_id.insert(&pool_id, &UnorderedMap::new(LaunchpadStorageKey::votes_prefix(pool_id)));
+
+        ProposalCreated {
+            pool_id,
+            milestone_index: proposal.milestone_index,
+            time_end_voting: proposal.time_end_voting,
+        }
+        .emit();
+
+        proposal
+    }
+
+    /* //////////////////////////////////////////////////////////////
+                            USER FUNCTIONS
+    ////////////////////////////////////////////////////////////// */
+    // backer votes on the active proposal with the voting power computed at the end of funding
+    fn vote(&mut self, pool_id: PoolId, approve: bool) {
+        let voter_id = env::predecessor_account_id();
+
+        let mut proposal = self.proposal_by_pool_id.get(&pool_id)
+            .expect("Pool has no proposal");
+
+        if proposal.status != ProposalStatus::ACTIVE {
+            env::panic_str("Proposal is not active");
+        }
+
+        if Timestamp::now() > proposal.time_end_voting {
+            env::panic_str("Voting period has ended");
+        }
+
+        let user_records = self.user_records.get(&pool_id)
+            .expect("No user records found for this pool");
+
+        let record = user_records.get(&voter_id)
+            .expect("User has no record in this pool");
+
+        if record.voting_power == 0 {
+            env::panic_str("User has no voting power in this pool");
+        }
+
+        let mut votes = self.votes_by_pool_id.get(&pool_id)
+            .expect("No votes found for this pool");
+
+        if votes.get(&voter_id).is_some() {
+            env::panic_str("User has already voted on this proposal");
+        }
+
+        if approve {
+            proposal.approve_power += record.voting_power;
+        } else {
+            proposal.reject_power += record.voting_power;
+        }
+
+        votes.insert(&voter_id, &Vote {
+            approve,
+            voting_power: record.voting_power,
+        });
+        self.votes_by_pool_id.insert(&pool_id, &votes);
+        self.proposal_by_pool_id.insert(&pool_id, &proposal);
+
+        VoteCast {
+            pool_id,
+            account_id: &voter_id,
+            approve,
+            voting_power: U128(record.voting_power),
+        }
+        .emit();
+    }
+
+    // anyone can tally once the deadline has passed or a side holds the majority of voting power
+    fn tally_votes(&mut self, pool_id: PoolId) -> PoolMetadata {
+        let mut pool = self.pool_metadata_by_id.get(&pool_id)
+            .expect("Pool does not exist");
+
+        let mut proposal = self.proposal_by_pool_id.get(&pool_id)
+            .expect("Pool has no proposal");
+
+        if proposal.status != ProposalStatus::ACTIVE {
+            env::panic_str("Proposal is not active");
+        }
+
+        let mut milestones = self.milestones_by_pool_id.get(&pool_id)
+            .expect("Pool has no milestones");
+        let milestone_index = proposal.milestone_index as usize;
+
+        let old_status = pool.status.clone();
+        let voting_ended = Timestamp::now() > proposal.time_end_voting;
+        proposal.status = tally_outcome(
+            pool.total_voting_power,
+            proposal.approve_power,
+            proposal.reject_power,
+            self.voting_quorum_percent,
+            voting_ended
+        )
+        .unwrap_or_else(|| env::panic_str("Voting period has not ended yet"));
+
+        match proposal.status {
+            ProposalStatus::APPROVED => {
+                milestones[milestone_index].status = MilestoneStatus::APPROVED;
+                // the pool keeps voting until its last milestone is approved
+                if milestone_index == milestones.len() - 1 {
+                    pool.status = Status::SUCCESSFUL;
+                    self.internal_settle_successful_stake(&mut pool);
+                }
+            },
+            ProposalStatus::REJECTED => {
+                // unreleased tranches go back to the backers pro-rata through claim_refund
+                milestones[milestone_index].status = MilestoneStatus::FAILED;
+                pool.status = Status::REFUNDED;
+                self.internal_slash_stake(&mut pool);
+            },
+            // a missed quorum only ends the proposal, the creator can propose again
+            // until the milestone deadline lets fail_expired_milestone refund the backers
+            _ => {},
+        }
+
+        self.proposal_by_pool_id.insert(&pool_id, &proposal);
+        self.milestones_by_pool_id.insert(&pool_id, &milestones);
+        self.pool_metadata_by_id.insert(&pool_id, &pool);
+
+        ProposalFinalized {
+            pool_id,
+            milestone_index: proposal.milestone_index,
+            status: &proposal.status,
+            approve_power: U128(proposal.approve_power),
+            reject_power: U128(proposal.reject_power),
+        }
+        .emit();
+        if pool.status != old_status {
+            PoolStatusChanged {
+                pool_id,
+                old_status: &old_status,
+                new_status: &pool.status,
+            }
+            .emit();
+        }
+
+        pool
+    }
+
+    // anyone can fail the current milestone once its deadline passed without approval
+    fn fail_expired_milestone(&mut self, pool_id: PoolId) -> PoolMetadata {
+        let mut pool = self.pool_metadata_by_id.get(&pool_id)
+            .expect("Pool does not exist");
+
+        if pool.status != Status::VOTING {
+            env::panic_str("Pool must be in VOTING status");
+        }
+
+        if let Some(proposal) = self.proposal_by_pool_id.get(&pool_id) {
+            if proposal.status == ProposalStatus::ACTIVE {
+                env::panic_str("Active proposal must be tallied first");
+            }
+        }
+
+        let mut milestones = self.milestones_by_pool_id.get(&pool_id)
+            .expect("Pool has no milestones");
+
+        let milestone_index = current_milestone_index(&milestones)
+            .unwrap_or_else(|| env::panic_str("Pool has no pending milestone"));
+
+        if !is_milestone_expired(&milestones[milestone_index], Timestamp::now()) {
+            env::panic_str("Milestone deadline has not passed yet");
+        }
+
+        milestones[milestone_index].status = MilestoneStatus::FAILED;
+        pool.status = Status::REFUNDED;
+        self.internal_slash_stake(&mut pool);
+
+        self.milestones_by_pool_id.insert(&pool_id, &milestones);
+        self.pool_metadata_by_id.insert(&pool_id, &pool);
+
+        MilestoneFailed {
+            pool_id,
+            milestone_index: milestone_index as u32,
+        }
+        .emit();
+        PoolStatusChanged {
+            pool_id,
+            old_status: &Status::VOTING,
+            new_status: &pool.status,
+        }
+        .emit();
+
+        pool
+    }
+}
@@ -1,4 +1,12 @@
-use near_sdk::env;
-
 pub mod impl_launchpad;
-pub mod get_launchpad;
\ No newline at end of file
+pub mod get_launchpad;
+pub mod impl_voting;
+pub mod impl_resolver;
+pub mod impl_access;
+pub mod impl_pause;
+pub mod impl_keeper;
+pub mod impl_treasury;
+pub mod impl_rewards;
+pub mod impl_referrals;
+pub mod impl_pool_access;
+pub mod impl_stretch_goals;
\ No newline at end of file
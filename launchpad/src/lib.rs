@@ -1,25 +1,39 @@
-use models::contract::{Launchpad, LaunchpadStorageKey, LaunchpadExt, DEFAULT_MIN_STAKING};
+use application::repository::{default_milestones, read_state_version, write_state_version};
+use models::contract::{
+    FundingTerms, Launchpad, LaunchpadStorageKey, LaunchpadExt, PoolMetadata, PoolToken, ReferralReserve, TreasuryLedger, UserTokenDepositRecord,
+    DEFAULT_KEEPER_REWARD, DEFAULT_MIN_STAKING, DEFAULT_STAKE_SLASH_PERCENT, DEFAULT_VOTING_QUORUM_PERCENT, DEFAULT_VOTING_DURATION, STATE_VERSION
+};
+use models::migration::{
+    read_old_pool, LaunchpadV1, LaunchpadV2, LaunchpadV5, LaunchpadV6, LaunchpadV7, LaunchpadV8, LaunchpadV9, LaunchpadV10, LaunchpadV11, LaunchpadV12, LaunchpadV13, LaunchpadV14, OldLaunchpad, PoolMetadataV3, PoolMetadataV4, PoolMetadataV8
+};
+use models::time::{Duration, Timestamp};
 use near_sdk::borsh::BorshSerialize;
 use near_sdk::{
-    collections::{LookupMap, UnorderedSet},
-    env, near_bindgen, AccountId,
+    collections::{LookupMap, UnorderedMap, UnorderedSet},
+    env, near_bindgen, AccountId, Gas, Promise,
 };
 
 pub mod application;
 pub mod models;
 
+// gas kept by `upgrade` itself, the rest of the prepaid gas goes to `migrate`
+const GAS_FOR_UPGRADE: Gas = Gas(10_000_000_000_000);
+
 #[near_bindgen]
 impl Launchpad {
     #[init]
     pub fn init() -> Self {
-        let owner_id = env::signer_account_id();
+        let owner_id = env::predecessor_account_id();
 
         Self::new(owner_id)
     }
 
     #[init]
     pub fn new(owner_id: AccountId) -> Self {
+        write_state_version();
+
         Self {
+            fee_collector_id: owner_id.clone(),
             owner_id,
             all_pool_id: UnorderedSet::new(LaunchpadStorageKey::AllPoolId.try_to_vec().unwrap()),
             list_assets: Vec::new(),
@@ -27,6 +41,601 @@ impl Launchpad {
             min_staking_amount: DEFAULT_MIN_STAKING,
             refund_percent: 0,
             user_records: LookupMap::new(LaunchpadStorageKey::UserRecordsMap.try_to_vec().unwrap()),
+            voting_quorum_percent: DEFAULT_VOTING_QUORUM_PERCENT,
+            voting_duration: DEFAULT_VOTING_DURATION,
+            proposal_by_pool_id: LookupMap::new(LaunchpadStorageKey::ProposalByPoolId.try_to_vec().unwrap()),
+            votes_by_pool_id: LookupMap::new(LaunchpadStorageKey::VotesMap.try_to_vec().unwrap()),
+            milestones_by_pool_id: LookupMap::new(LaunchpadStorageKey::MilestonesByPoolId.try_to_vec().unwrap()),
+            pools_by_backer: LookupMap::new(LaunchpadStorageKey::PoolsByBacker.try_to_vec().unwrap()),
+            pending_owner_id: None,
+            role_members: LookupMap::new(LaunchpadStorageKey::RoleMembers.try_to_vec().unwrap()),
+            paused_features: Vec::new(),
+            keeper_reward: DEFAULT_KEEPER_REWARD,
+            treasury: TreasuryLedger::default(),
+            stake_slash_percent: DEFAULT_STAKE_SLASH_PERCENT,
+            platform_fee_bps: 0,
+            fee_bps_by_token: LookupMap::new(LaunchpadStorageKey::FeeBpsByToken.try_to_vec().unwrap()),
+            token_rates: LookupMap::new(LaunchpadStorageKey::TokenRates.try_to_vec().unwrap()),
+            token_pledges: LookupMap::new(LaunchpadStorageKey::TokenPledges.try_to_vec().unwrap()),
+            reward_tiers_by_pool_id: LookupMap::new(LaunchpadStorageKey::RewardTiersByPoolId.try_to_vec().unwrap()),
+            backer_tiers: LookupMap::new(LaunchpadStorageKey::BackerTiers.try_to_vec().unwrap()),
+            referral_reward_bps: 0,
+            referrals_by_pool_id: LookupMap::new(LaunchpadStorageKey::ReferralsByPoolId.try_to_vec().unwrap()),
+            referrer_by_backer: LookupMap::new(LaunchpadStorageKey::ReferrerByBacker.try_to_vec().unwrap()),
+            pledge_signer_key: None,
+            access_by_pool_id: LookupMap::new(LaunchpadStorageKey::AccessByPoolId.try_to_vec().unwrap()),
+            allowlist_by_pool_id: LookupMap::new(LaunchpadStorageKey::AllowlistByPoolId.try_to_vec().unwrap()),
+            stretch_goals_by_pool_id: LookupMap::new(LaunchpadStorageKey::StretchGoalsByPoolId.try_to_vec().unwrap()),
+            overfunding_by_pool_id: LookupMap::new(LaunchpadStorageKey::OverfundingByPoolId.try_to_vec().unwrap()),
+            excess_by_backer: LookupMap::new(LaunchpadStorageKey::ExcessByBacker.try_to_vec().unwrap()),
+            funding_terms: FundingTerms::default(),
+            funding_terms_by_pool_id: LookupMap::new(LaunchpadStorageKey::FundingTermsByPoolId.try_to_vec().unwrap()),
+            referral_reserves: LookupMap::new(LaunchpadStorageKey::ReferralReserves.try_to_vec().unwrap()),
+        }
+    }
+
+    // admin deploys the wasm passed as the raw call input and migrates the state in the same batch,
+    // a failing migration reverts the deployment
+    pub fn upgrade(&self) -> Promise {
+        self.assert_owner();
+
+        let code = env::input().expect("Missing contract code");
+        let migrate_gas = env::prepaid_gas() - env::used_gas() - GAS_FOR_UPGRADE;
+
+        Promise::new(env::current_account_id())
+            .deploy_contract(code)
+            .function_call("migrate".to_string(), Vec::new(), 0, migrate_gas)
+    }
+
+    // bring the stored state up to STATE_VERSION, a no-op rewrite when only the code changed
+    #[private]
+    #[init(ignore_state)]
+    pub fn migrate() -> Self {
+        let version = read_state_version();
+        let mut contract = match version {
+            0 => Self::migrate_from_v0(),
+            1 => Self::migrate_from_v1(),
+            2 => Self::migrate_from_v2(),
+            // versions 3 to 5 share a root layout, only their pools differ
+            3..=5 => Self::migrate_from_v5(),
+            6 => Self::migrate_from_v6(),
+            7 => Self::migrate_from_v7(),
+            8 => Self::migrate_from_v8(),
+            9 => Self::migrate_from_v9(),
+            10 => Self::migrate_from_v10(),
+            11 => Self::migrate_from_v11(),
+            12 => Self::migrate_from_v12(),
+            13 => Self::migrate_from_v13(),
+            14 => Self::migrate_from_v14(),
+            STATE_VERSION => env::state_read().expect("Failed to read state"),
+            version => env::panic_str(&format!("Unknown state version {}", version)),
+        };
+
+        // version 0 pools are already rewritten by migrate_from_v0
+        if (1..STATE_VERSION).contains(&version) {
+            contract.migrate_pools(version);
+        }
+
+        // referrals exist since version 11
+        if (11..STATE_VERSION).contains(&version) {
+            contract.migrate_referral_reserves();
+        }
+
+        write_state_version();
+        contract
+    }
+
+    // rewrite pools stored in an older layout, every layout converts into the next one
+    fn migrate_pools(&mut self, version: u8) {
+        for pool_id in self.all_pool_id.to_vec() {
+            let pool: Option<PoolMetadata> = match version {
+                1..=3 => read_old_pool::<PoolMetadataV3>(pool_id)
+                    .map(|pool| PoolMetadataV8::from(PoolMetadataV4::from(pool)).into()),
+                4 => read_old_pool::<PoolMetadataV4>(pool_id).map(|pool| PoolMetadataV8::from(pool).into()),
+                5..=8 => read_old_pool::<PoolMetadataV8>(pool_id).map(PoolMetadata::from),
+                _ => None,
+            };
+
+            if let Some(mut pool) = pool {
+                // single-token layouts, the balance also holds what never was a pledge such as kept withdraw penalties
+                pool.tokens[0].pledged = self.user_records.get(&pool_id)
+                    .map(|records| records.values().map(|record| record.amount).sum())
+                    .unwrap_or(0);
+                self.pool_metadata_by_id.insert(&pool_id, &pool);
+            }
         }
     }
+
+    // set aside the referral rewards owed by the existing pools: the rewards already paid count as reserved,
+    // the others are taken from the fees accrued by pools that released a tranche and from their next tranches
+    fn migrate_referral_reserves(&mut self) {
+        for pool_id in self.all_pool_id.to_vec() {
+            let referrals = match self.referrals_by_pool_id.get(&pool_id) {
+                Some(referrals) => referrals,
+                None => continue,
+            };
+
+            let mut reserves: Vec<(AccountId, ReferralReserve)> = Vec::new();
+            for stats in referrals.values() {
+                for token in stats.tokens {
+                    let index = match reserves.iter().position(|(token_id, _)| token_id == &token.token_id) {
+                        Some(index) => index,
+                        None => {
+                            reserves.push((token.token_id.clone(), ReferralReserve::default()));
+                            reserves.len() - 1
+                        }
+                    };
+                    let reward = self.internal_referral_reward(&token.token_id, token.amount);
+                    let reserve = &mut reserves[index].1;
+                    reserve.referred += token.amount;
+                    if stats.rewarded_tokens.contains(&token.token_id) {
+                        reserve.reserved += reward;
+                        reserve.paid += reward;
+                    }
+                }
+            }
+
+            let released = self.milestones_by_pool_id.get(&pool_id)
+                .is_some_and(|milestones| milestones.iter().any(|milestone| milestone.amount_released > 0));
+
+            for (token_id, mut reserve) in reserves {
+                if released {
+                    let owed = self.internal_referral_reward(&token_id, reserve.referred);
+                    if let Some(asset) = self.list_assets.iter_mut().find(|asset| asset.token_id == token_id) {
+                        let taken = owed.saturating_sub(reserve.reserved).min(asset.balances);
+                        asset.balances -= taken;
+                        reserve.reserved += taken;
+                    }
+                }
+                self.referral_reserves.insert(&(pool_id, token_id), &reserve);
+            }
+        }
+    }
+
+    // existing pools have no referral reserves yet, migrate_referral_reserves sets them aside
+    fn migrate_from_v14() -> Self {
+        let old: LaunchpadV14 = env::state_read().expect("Failed to read old state");
+
+        let mut contract = Self::new(old.owner_id);
+        contract.all_pool_id = old.all_pool_id;
+        contract.list_assets = old.list_assets;
+        contract.pool_metadata_by_id = old.pool_metadata_by_id;
+        contract.min_staking_amount = old.min_staking_amount;
+        contract.refund_percent = old.refund_percent;
+        contract.user_records = old.user_records;
+        contract.voting_quorum_percent = old.voting_quorum_percent;
+        contract.voting_duration = old.voting_duration;
+        contract.proposal_by_pool_id = old.proposal_by_pool_id;
+        contract.votes_by_pool_id = old.votes_by_pool_id;
+        contract.milestones_by_pool_id = old.milestones_by_pool_id;
+        contract.pools_by_backer = old.pools_by_backer;
+        contract.pending_owner_id = old.pending_owner_id;
+        contract.role_members = old.role_members;
+        contract.paused_features = old.paused_features;
+        contract.keeper_reward = old.keeper_reward;
+        contract.treasury = old.treasury;
+        contract.stake_slash_percent = old.stake_slash_percent;
+        contract.platform_fee_bps = old.platform_fee_bps;
+        contract.fee_bps_by_token = old.fee_bps_by_token;
+        contract.fee_collector_id = old.fee_collector_id;
+        contract.token_rates = old.token_rates;
+        contract.token_pledges = old.token_pledges;
+        contract.reward_tiers_by_pool_id = old.reward_tiers_by_pool_id;
+        contract.backer_tiers = old.backer_tiers;
+        contract.referral_reward_bps = old.referral_reward_bps;
+        contract.referrals_by_pool_id = old.referrals_by_pool_id;
+        contract.referrer_by_backer = old.referrer_by_backer;
+        contract.pledge_signer_key = old.pledge_signer_key;
+        contract.access_by_pool_id = old.access_by_pool_id;
+        contract.allowlist_by_pool_id = old.allowlist_by_pool_id;
+        contract.stretch_goals_by_pool_id = old.stretch_goals_by_pool_id;
+        contract.overfunding_by_pool_id = old.overfunding_by_pool_id;
+        contract.excess_by_backer = old.excess_by_backer;
+        contract.funding_terms = old.funding_terms;
+        contract.funding_terms_by_pool_id = old.funding_terms_by_pool_id;
+        contract
+    }
+
+    // existing pools follow the default funding terms, which keep the previous fixed values
+    fn migrate_from_v13() -> Self {
+        let old: LaunchpadV13 = env::state_read().expect("Failed to read old state");
+
+        let mut contract = Self::new(old.owner_id);
+        contract.all_pool_id = old.all_pool_id;
+        contract.list_assets = old.list_assets;
+        contract.pool_metadata_by_id = old.pool_metadata_by_id;
+        contract.min_staking_amount = old.min_staking_amount;
+        contract.refund_percent = old.refund_percent;
+        contract.user_records = old.user_records;
+        contract.voting_quorum_percent = old.voting_quorum_percent;
+        contract.voting_duration = Duration::from_nanos(old.voting_duration);
+        contract.proposal_by_pool_id = old.proposal_by_pool_id;
+        contract.votes_by_pool_id = old.votes_by_pool_id;
+        contract.milestones_by_pool_id = old.milestones_by_pool_id;
+        contract.pools_by_backer = old.pools_by_backer;
+        contract.pending_owner_id = old.pending_owner_id;
+        contract.role_members = old.role_members;
+        contract.paused_features = old.paused_features;
+        contract.keeper_reward = old.keeper_reward;
+        contract.treasury = old.treasury;
+        contract.stake_slash_percent = old.stake_slash_percent;
+        contract.platform_fee_bps = old.platform_fee_bps;
+        contract.fee_bps_by_token = old.fee_bps_by_token;
+        contract.fee_collector_id = old.fee_collector_id;
+        contract.token_rates = old.token_rates;
+        contract.token_pledges = old.token_pledges;
+        contract.reward_tiers_by_pool_id = old.reward_tiers_by_pool_id;
+        contract.backer_tiers = old.backer_tiers;
+        contract.referral_reward_bps = old.referral_reward_bps;
+        contract.referrals_by_pool_id = old.referrals_by_pool_id;
+        contract.referrer_by_backer = old.referrer_by_backer;
+        contract.pledge_signer_key = old.pledge_signer_key;
+        contract.access_by_pool_id = old.access_by_pool_id;
+        contract.allowlist_by_pool_id = old.allowlist_by_pool_id;
+        contract.stretch_goals_by_pool_id = old.stretch_goals_by_pool_id;
+        contract.overfunding_by_pool_id = old.overfunding_by_pool_id;
+        contract.excess_by_backer = old.excess_by_backer;
+        contract
+    }
+
+    // existing pools have no stretch goals and keep their whole raise
+    fn migrate_from_v12() -> Self {
+        let old: LaunchpadV12 = env::state_read().expect("Failed to read old state");
+
+        let mut contract = Self::new(old.owner_id);
+        contract.all_pool_id = old.all_pool_id;
+        contract.list_assets = old.list_assets;
+        contract.pool_metadata_by_id = old.pool_metadata_by_id;
+        contract.min_staking_amount = old.min_staking_amount;
+        contract.refund_percent = old.refund_percent;
+        contract.user_records = old.user_records;
+        contract.voting_quorum_percent = old.voting_quorum_percent;
+        contract.voting_duration = Duration::from_nanos(old.voting_duration);
+        contract.proposal_by_pool_id = old.proposal_by_pool_id;
+        contract.votes_by_pool_id = old.votes_by_pool_id;
+        contract.milestones_by_pool_id = old.milestones_by_pool_id;
+        contract.pools_by_backer = old.pools_by_backer;
+        contract.pending_owner_id = old.pending_owner_id;
+        contract.role_members = old.role_members;
+        contract.paused_features = old.paused_features;
+        contract.keeper_reward = old.keeper_reward;
+        contract.treasury = old.treasury;
+        contract.stake_slash_percent = old.stake_slash_percent;
+        contract.platform_fee_bps = old.platform_fee_bps;
+        contract.fee_bps_by_token = old.fee_bps_by_token;
+        contract.fee_collector_id = old.fee_collector_id;
+        contract.token_rates = old.token_rates;
+        contract.token_pledges = old.token_pledges;
+        contract.reward_tiers_by_pool_id = old.reward_tiers_by_pool_id;
+        contract.backer_tiers = old.backer_tiers;
+        contract.referral_reward_bps = old.referral_reward_bps;
+        contract.referrals_by_pool_id = old.referrals_by_pool_id;
+        contract.referrer_by_backer = old.referrer_by_backer;
+        contract.pledge_signer_key = old.pledge_signer_key;
+        contract.access_by_pool_id = old.access_by_pool_id;
+        contract.allowlist_by_pool_id = old.allowlist_by_pool_id;
+        contract
+    }
+
+    // existing pools stay public
+    fn migrate_from_v11() -> Self {
+        let old: LaunchpadV11 = env::state_read().expect("Failed to read old state");
+
+        let mut contract = Self::new(old.owner_id);
+        contract.all_pool_id = old.all_pool_id;
+        contract.list_assets = old.list_assets;
+        contract.pool_metadata_by_id = old.pool_metadata_by_id;
+        contract.min_staking_amount = old.min_staking_amount;
+        contract.refund_percent = old.refund_percent;
+        contract.user_records = old.user_records;
+        contract.voting_quorum_percent = old.voting_quorum_percent;
+        contract.voting_duration = Duration::from_nanos(old.voting_duration);
+        contract.proposal_by_pool_id = old.proposal_by_pool_id;
+        contract.votes_by_pool_id = old.votes_by_pool_id;
+        contract.milestones_by_pool_id = old.milestones_by_pool_id;
+        contract.pools_by_backer = old.pools_by_backer;
+        contract.pending_owner_id = old.pending_owner_id;
+        contract.role_members = old.role_members;
+        contract.paused_features = old.paused_features;
+        contract.keeper_reward = old.keeper_reward;
+        contract.treasury = old.treasury;
+        contract.stake_slash_percent = old.stake_slash_percent;
+        contract.platform_fee_bps = old.platform_fee_bps;
+        contract.fee_bps_by_token = old.fee_bps_by_token;
+        contract.fee_collector_id = old.fee_collector_id;
+        contract.token_rates = old.token_rates;
+        contract.token_pledges = old.token_pledges;
+        contract.reward_tiers_by_pool_id = old.reward_tiers_by_pool_id;
+        contract.backer_tiers = old.backer_tiers;
+        contract.referral_reward_bps = old.referral_reward_bps;
+        contract.referrals_by_pool_id = old.referrals_by_pool_id;
+        contract.referrer_by_backer = old.referrer_by_backer;
+        contract
+    }
+
+    // existing pledges have no referrer and no referral reward is set
+    fn migrate_from_v10() -> Self {
+        let old: LaunchpadV10 = env::state_read().expect("Failed to read old state");
+
+        let mut contract = Self::new(old.owner_id);
+        contract.all_pool_id = old.all_pool_id;
+        contract.list_assets = old.list_assets;
+        contract.pool_metadata_by_id = old.pool_metadata_by_id;
+        contract.min_staking_amount = old.min_staking_amount;
+        contract.refund_percent = old.refund_percent;
+        contract.user_records = old.user_records;
+        contract.voting_quorum_percent = old.voting_quorum_percent;
+        contract.voting_duration = Duration::from_nanos(old.voting_duration);
+        contract.proposal_by_pool_id = old.proposal_by_pool_id;
+        contract.votes_by_pool_id = old.votes_by_pool_id;
+        contract.milestones_by_pool_id = old.milestones_by_pool_id;
+        contract.pools_by_backer = old.pools_by_backer;
+        contract.pending_owner_id = old.pending_owner_id;
+        contract.role_members = old.role_members;
+        contract.paused_features = old.paused_features;
+        contract.keeper_reward = old.keeper_reward;
+        contract.treasury = old.treasury;
+        contract.stake_slash_percent = old.stake_slash_percent;
+        contract.platform_fee_bps = old.platform_fee_bps;
+        contract.fee_bps_by_token = old.fee_bps_by_token;
+        contract.fee_collector_id = old.fee_collector_id;
+        contract.token_rates = old.token_rates;
+        contract.token_pledges = old.token_pledges;
+        contract.reward_tiers_by_pool_id = old.reward_tiers_by_pool_id;
+        contract.backer_tiers = old.backer_tiers;
+        contract
+    }
+
+    // existing pools offer no reward tiers
+    fn migrate_from_v9() -> Self {
+        let old: LaunchpadV9 = env::state_read().expect("Failed to read old state");
+
+        let mut contract = Self::new(old.owner_id);
+        contract.all_pool_id = old.all_pool_id;
+        contract.list_assets = old.list_assets;
+        contract.pool_metadata_by_id = old.pool_metadata_by_id;
+        contract.min_staking_amount = old.min_staking_amount;
+        contract.refund_percent = old.refund_percent;
+        contract.user_records = old.user_records;
+        contract.voting_quorum_percent = old.voting_quorum_percent;
+        contract.voting_duration = Duration::from_nanos(old.voting_duration);
+        contract.proposal_by_pool_id = old.proposal_by_pool_id;
+        contract.votes_by_pool_id = old.votes_by_pool_id;
+        contract.milestones_by_pool_id = old.milestones_by_pool_id;
+        contract.pools_by_backer = old.pools_by_backer;
+        contract.pending_owner_id = old.pending_owner_id;
+        contract.role_members = old.role_members;
+        contract.paused_features = old.paused_features;
+        contract.keeper_reward = old.keeper_reward;
+        contract.treasury = old.treasury;
+        contract.stake_slash_percent = old.stake_slash_percent;
+        contract.platform_fee_bps = old.platform_fee_bps;
+        contract.fee_bps_by_token = old.fee_bps_by_token;
+        contract.fee_collector_id = old.fee_collector_id;
+        contract.token_rates = old.token_rates;
+        contract.token_pledges = old.token_pledges;
+        contract
+    }
+
+    // every token is worth 1:1 the reference unit until a rate is set
+    fn migrate_from_v8() -> Self {
+        let old: LaunchpadV8 = env::state_read().expect("Failed to read old state");
+
+        let mut contract = Self::new(old.owner_id);
+        contract.all_pool_id = old.all_pool_id;
+        contract.list_assets = old.list_assets;
+        contract.pool_metadata_by_id = old.pool_metadata_by_id;
+        contract.min_staking_amount = old.min_staking_amount;
+        contract.refund_percent = old.refund_percent;
+        contract.user_records = old.user_records;
+        contract.voting_quorum_percent = old.voting_quorum_percent;
+        contract.voting_duration = Duration::from_nanos(old.voting_duration);
+        contract.proposal_by_pool_id = old.proposal_by_pool_id;
+        contract.votes_by_pool_id = old.votes_by_pool_id;
+        contract.milestones_by_pool_id = old.milestones_by_pool_id;
+        contract.pools_by_backer = old.pools_by_backer;
+        contract.pending_owner_id = old.pending_owner_id;
+        contract.role_members = old.role_members;
+        contract.paused_features = old.paused_features;
+        contract.keeper_reward = old.keeper_reward;
+        contract.treasury = old.treasury;
+        contract.stake_slash_percent = old.stake_slash_percent;
+        contract.platform_fee_bps = old.platform_fee_bps;
+        contract.fee_bps_by_token = old.fee_bps_by_token;
+        contract.fee_collector_id = old.fee_collector_id;
+        contract
+    }
+
+    // no fee is charged until one is configured, the admin collects the fees
+    fn migrate_from_v7() -> Self {
+        let old: LaunchpadV7 = env::state_read().expect("Failed to read old state");
+
+        let mut contract = Self::new(old.owner_id);
+        contract.all_pool_id = old.all_pool_id;
+        contract.list_assets = old.list_assets;
+        contract.pool_metadata_by_id = old.pool_metadata_by_id;
+        contract.min_staking_amount = old.min_staking_amount;
+        contract.refund_percent = old.refund_percent;
+        contract.user_records = old.user_records;
+        contract.voting_quorum_percent = old.voting_quorum_percent;
+        contract.voting_duration = Duration::from_nanos(old.voting_duration);
+        contract.proposal_by_pool_id = old.proposal_by_pool_id;
+        contract.votes_by_pool_id = old.votes_by_pool_id;
+        contract.milestones_by_pool_id = old.milestones_by_pool_id;
+        contract.pools_by_backer = old.pools_by_backer;
+        contract.pending_owner_id = old.pending_owner_id;
+        contract.role_members = old.role_members;
+        contract.paused_features = old.paused_features;
+        contract.keeper_reward = old.keeper_reward;
+        contract.treasury = old.treasury;
+        contract.stake_slash_percent = old.stake_slash_percent;
+        contract
+    }
+
+    // the treasury starts empty, stakes kept before the upgrade stay unaccounted
+    fn migrate_from_v6() -> Self {
+        let old: LaunchpadV6 = env::state_read().expect("Failed to read old state");
+
+        let mut contract = Self::new(old.owner_id);
+        contract.all_pool_id = old.all_pool_id;
+        contract.list_assets = old.list_assets;
+        contract.pool_metadata_by_id = old.pool_metadata_by_id;
+        contract.min_staking_amount = old.min_staking_amount;
+        contract.refund_percent = old.refund_percent;
+        contract.user_records = old.user_records;
+        contract.voting_quorum_percent = old.voting_quorum_percent;
+        contract.voting_duration = Duration::from_nanos(old.voting_duration);
+        contract.proposal_by_pool_id = old.proposal_by_pool_id;
+        contract.votes_by_pool_id = old.votes_by_pool_id;
+        contract.milestones_by_pool_id = old.milestones_by_pool_id;
+        contract.pools_by_backer = old.pools_by_backer;
+        contract.pending_owner_id = old.pending_owner_id;
+        contract.role_members = old.role_members;
+        contract.paused_features = old.paused_features;
+        contract.keeper_reward = old.keeper_reward;
+        contract
+    }
+
+    // keepers earn the default reward after the upgrade
+    fn migrate_from_v5() -> Self {
+        let old: LaunchpadV5 = env::state_read().expect("Failed to read old state");
+
+        let mut contract = Self::new(old.owner_id);
+        contract.all_pool_id = old.all_pool_id;
+        contract.list_assets = old.list_assets;
+        contract.pool_metadata_by_id = old.pool_metadata_by_id;
+        contract.min_staking_amount = old.min_staking_amount;
+        contract.refund_percent = old.refund_percent;
+        contract.user_records = old.user_records;
+        contract.voting_quorum_percent = old.voting_quorum_percent;
+        contract.voting_duration = Duration::from_nanos(old.voting_duration);
+        contract.proposal_by_pool_id = old.proposal_by_pool_id;
+        contract.votes_by_pool_id = old.votes_by_pool_id;
+        contract.milestones_by_pool_id = old.milestones_by_pool_id;
+        contract.pools_by_backer = old.pools_by_backer;
+        contract.pending_owner_id = old.pending_owner_id;
+        contract.role_members = old.role_members;
+        contract.paused_features = old.paused_features;
+        contract
+    }
+
+    // nothing is paused after the upgrade
+    fn migrate_from_v2() -> Self {
+        let old: LaunchpadV2 = env::state_read().expect("Failed to read old state");
+
+        let mut contract = Self::new(old.owner_id);
+        contract.all_pool_id = old.all_pool_id;
+        contract.list_assets = old.list_assets;
+        contract.pool_metadata_by_id = old.pool_metadata_by_id;
+        contract.min_staking_amount = old.min_staking_amount;
+        contract.refund_percent = old.refund_percent;
+        contract.user_records = old.user_records;
+        contract.voting_quorum_percent = old.voting_quorum_percent;
+        contract.voting_duration = Duration::from_nanos(old.voting_duration);
+        contract.proposal_by_pool_id = old.proposal_by_pool_id;
+        contract.votes_by_pool_id = old.votes_by_pool_id;
+        contract.milestones_by_pool_id = old.milestones_by_pool_id;
+        contract.pools_by_backer = old.pools_by_backer;
+        contract.pending_owner_id = old.pending_owner_id;
+        contract.role_members = old.role_members;
+        contract
+    }
+
+    // roles and the pending admin start empty, the admin keeps full control
+    fn migrate_from_v1() -> Self {
+        let old: LaunchpadV1 = env::state_read().expect("Failed to read old state");
+
+        let mut contract = Self::new(old.owner_id);
+        contract.all_pool_id = old.all_pool_id;
+        contract.list_assets = old.list_assets;
+        contract.pool_metadata_by_id = old.pool_metadata_by_id;
+        contract.min_staking_amount = old.min_staking_amount;
+        contract.refund_percent = old.refund_percent;
+        contract.user_records = old.user_records;
+        contract.voting_quorum_percent = old.voting_quorum_percent;
+        contract.voting_duration = Duration::from_nanos(old.voting_duration);
+        contract.proposal_by_pool_id = old.proposal_by_pool_id;
+        contract.votes_by_pool_id = old.votes_by_pool_id;
+        contract.milestones_by_pool_id = old.milestones_by_pool_id;
+        contract.pools_by_backer = old.pools_by_backer;
+        contract
+    }
+
+    // rewrite state deployed with f64 voting power into integer shares
+    fn migrate_from_v0() -> Self {
+        let mut old: OldLaunchpad = env::state_read().expect("Failed to read old state");
+
+        let mut contract = Self::new(old.owner_id.clone());
+        contract.all_pool_id = old.all_pool_id;
+        contract.list_assets = old.list_assets;
+        contract.min_staking_amount = old.min_staking_amount;
+        contract.refund_percent = old.refund_percent;
+
+        for pool_id in contract.all_pool_id.to_vec() {
+            let old_pool = match old.pool_metadata_by_id.remove(&pool_id) {
+                Some(pool) => pool,
+                None => continue,
+            };
+
+            let mut total_balance = old_pool.total_balance;
+            let mut total_voting_power = 0;
+            // outstanding pledges, the balance also holds what never was a pledge
+            let mut pledged = 0;
+
+            if let Some(mut old_records) = old.user_records.remove(&pool_id) {
+                let entries: Vec<_> = old_records.iter().collect();
+                old_records.clear();
+
+                let mut records = UnorderedMap::new(LaunchpadStorageKey::user_records_prefix(pool_id));
+                for (user_id, old_record) in entries {
+                    let claimed = old_record.amount == 0 && old_record.voting_power > 0.0;
+                    if claimed {
+                        // refund already paid with the old formula, take it out of the pool
+                        let paid = (old_pool.total_balance as f64 * old_record.voting_power / 100.0) as u128;
+                        total_balance -= paid.min(total_balance);
+                    }
+
+                    let voting_power = if old_record.voting_power > 0.0 { old_record.amount } else { 0 };
+                    total_voting_power += voting_power;
+                    pledged += old_record.amount;
+
+                    records.insert(&user_id, &UserTokenDepositRecord {
+                        amount: old_record.amount,
+                        voting_power,
+                    });
+                    contract.internal_add_backer_pool(&user_id, pool_id);
+                }
+                contract.user_records.insert(&pool_id, &records);
+            }
+
+            contract.pool_metadata_by_id.insert(&pool_id, &PoolMetadata {
+                pool_id: old_pool.pool_id,
+                campaign_id: old_pool.campaign_id,
+                creator_id: old_pool.creator_id,
+                staking_amount: old_pool.staking_amount,
+                status: old_pool.status,
+                tokens: vec![PoolToken {
+                    token_id: old_pool.token_id.clone(),
+                    balance: total_balance,
+                    pledged,
+                }],
+                token_id: old_pool.token_id,
+                total_balance,
+                target_funding: old_pool.target_funding,
+                time_init: Timestamp::from_nanos(old_pool.time_init),
+                time_start_pledge: Timestamp::from_nanos(old_pool.time_start_pledge),
+                time_end_pledge: Timestamp::from_nanos(old_pool.time_end_pledge),
+                funding_duration_days: old_pool.funding_duration_days,
+                min_multiple_pledge: old_pool.min_multiple_pledge,
+                total_voting_power,
+                min_pledge: 0,
+                max_pledge: 0,
+                hard_cap: 0,
+                withdraw_penalty_percent: 0,
+            });
+            contract.milestones_by_pool_id.insert(&pool_id, &default_milestones());
+        }
+
+        contract
+    }
 }
\ No newline at end of file
@@ -8,9 +8,42 @@ use near_sdk::{
     json_types::U128
 };
 
+use super::time::{Duration, Timestamp};
 use super::PoolId;
 
 pub const DEFAULT_MIN_STAKING: u128 = 1_000_000_000_000_000_000_000; // 1 NEAR
+pub const DEFAULT_VOTING_QUORUM_PERCENT: u8 = 50;
+pub const DEFAULT_VOTING_DURATION: Duration = Duration::from_days(3);
+pub const DEFAULT_INIT_EXPIRY_DURATION: Duration = Duration::from_days(15);
+pub const DEFAULT_WAITING_EXTENSION_DURATION: Duration = Duration::from_days(3);
+pub const DEFAULT_WAITING_THRESHOLD_PERCENT: u8 = 80;
+pub const MIN_WAITING_THRESHOLD_PERCENT: u8 = 50;
+pub const MAX_WAITING_THRESHOLD_PERCENT: u8 = 99;
+pub const MIN_GRACE_PERIOD: Duration = Duration::from_days(1);
+pub const MAX_WAITING_EXTENSION_DURATION: Duration = Duration::from_days(30);
+pub const MAX_INIT_EXPIRY_DURATION: Duration = Duration::from_days(90);
+/// Bounds of a pledge window, set with `set_funding_pool_by_creator` or `change_pool_funding_time`.
+pub const MIN_FUNDING_DURATION: Duration = Duration::from_days(1);
+pub const MAX_FUNDING_DURATION: Duration = Duration::from_days(90);
+pub const DEFAULT_KEEPER_REWARD: u128 = 10_000_000_000_000_000_000_000; // 0.01 NEAR
+pub const DEFAULT_STAKE_SLASH_PERCENT: u8 = 100;
+/// Token ID of pools funded with native NEAR through `pledge` instead of a NEP-141 token.
+pub const NEAR_TOKEN_ID: &str = "near";
+pub const MAX_PLATFORM_FEE_BPS: u16 = 5_000; // 50%
+pub const BPS_DENOMINATOR: u128 = 10_000;
+/// Conversion rates are reference units per smallest token unit, scaled by this denominator.
+pub const RATE_DENOMINATOR: u128 = 1_000_000_000_000_000_000_000_000; // 1e24, a rate of 1:1
+pub const MAX_MILESTONES: usize = 10;
+pub const MAX_REWARD_TIERS: usize = 10;
+pub const MAX_TIER_DESCRIPTION_LEN: usize = 256;
+pub const MAX_STRETCH_GOALS: usize = 10;
+pub const MAX_GOAL_DESCRIPTION_LEN: usize = 256;
+/// Highest `version` of the JSON `ft_on_transfer` message this contract understands.
+pub const TRANSFER_MSG_VERSION: u8 = 1;
+pub const DEFAULT_PAGE_LIMIT: u64 = 50;
+pub const MAX_PAGE_LIMIT: u64 = 100;
+pub const STATE_VERSION: u8 = 15; // bump together with a migration arm in `migrate`
+pub const STATE_VERSION_KEY: &[u8] = b"STATE_VERSION";
 
 
 #[near_bindgen]
@@ -24,6 +57,62 @@ pub struct Launchpad {
     pub min_staking_amount: u128,
     pub refund_percent: u8,
     pub user_records: LookupMap<PoolId, UnorderedMap<AccountId, UserTokenDepositRecord>>,
+    pub voting_quorum_percent: u8,
+    pub voting_duration: Duration,
+    pub proposal_by_pool_id: LookupMap<PoolId, Proposal>,
+    pub votes_by_pool_id: LookupMap<PoolId, UnorderedMap<AccountId, Vote>>,
+    pub milestones_by_pool_id: LookupMap<PoolId, Vec<Milestone>>,
+    pub pools_by_backer: LookupMap<AccountId, UnorderedSet<PoolId>>,
+    /// Account proposed by `change_admin`, becomes the owner once it calls `accept_admin`.
+    pub pending_owner_id: Option<AccountId>,
+    pub role_members: LookupMap<Role, UnorderedSet<AccountId>>,
+    /// Features halted by a pauser, see `Feature`.
+    pub paused_features: Vec<Feature>,
+    /// NEAR paid to the account that triggers a due transition through `advance_pool`.
+    pub keeper_reward: u128,
+    /// NEAR kept by the platform from the creators' stakes, see `TreasuryLedger`.
+    pub treasury: TreasuryLedger,
+    /// Percentage of the creator's stake moved to the treasury when a milestone fails.
+    pub stake_slash_percent: u8,
+    /// Fee in basis points kept from every tranche released to a creator, accrued in `Assets::balances`
+    /// apart from the share set aside in `referral_reserves`.
+    pub platform_fee_bps: u16,
+    /// Fees of the tokens that do not use `platform_fee_bps`.
+    pub fee_bps_by_token: LookupMap<AccountId, u16>,
+    /// Account the accrued fees are withdrawn to.
+    pub fee_collector_id: AccountId,
+    /// Conversion rates of the tokens that are not worth 1:1 the reference unit, see `RATE_DENOMINATOR`.
+    pub token_rates: LookupMap<AccountId, u128>,
+    /// Per-token part of each backer's pledge; a backer missing here pledged only the pool's `token_id`.
+    pub token_pledges: LookupMap<(PoolId, AccountId), Vec<TokenPledge>>,
+    /// Reward tiers creators offer on their pools, see `RewardTier`.
+    pub reward_tiers_by_pool_id: LookupMap<PoolId, Vec<RewardTier>>,
+    /// Index of the reward tier each backer holds in a pool.
+    pub backer_tiers: LookupMap<(PoolId, AccountId), u32>,
+    /// Reward in basis points of the referred pledges, paid to referrers out of the platform fee.
+    pub referral_reward_bps: u16,
+    /// Referrers of each pool with the pledges they brought, see `ReferralStats`.
+    pub referrals_by_pool_id: LookupMap<PoolId, UnorderedMap<AccountId, ReferralStats>>,
+    /// Referrer a backer pledged through, set by the backer's first pledge in the pool.
+    pub referrer_by_backer: LookupMap<(PoolId, AccountId), AccountId>,
+    /// Base64 ed25519 public key of the platform, signs the pledges to `PoolAccess::Signature` pools.
+    pub pledge_signer_key: Option<String>,
+    /// Who may pledge to each restricted pool, a pool missing here is public.
+    pub access_by_pool_id: LookupMap<PoolId, PoolAccess>,
+    /// Backers allowed to pledge to restricted pools.
+    pub allowlist_by_pool_id: LookupMap<PoolId, UnorderedSet<AccountId>>,
+    /// Funding goals above the target creators set on their pools, see `StretchGoal`.
+    pub stretch_goals_by_pool_id: LookupMap<PoolId, Vec<StretchGoal>>,
+    /// What happens to the raise above the highest goal reached, a pool missing here keeps all of it.
+    pub overfunding_by_pool_id: LookupMap<PoolId, OverfundingPolicy>,
+    /// Per-token part of each backer's pledge set aside by the overfunding policy, claimed with `claim_excess`.
+    pub excess_by_backer: LookupMap<(PoolId, AccountId), Vec<TokenPledge>>,
+    /// Funding terms of the pools that override none of them.
+    pub funding_terms: FundingTerms,
+    /// Terms creators override in `init_pool`, the others follow `funding_terms`.
+    pub funding_terms_by_pool_id: LookupMap<PoolId, PoolFundingTerms>,
+    /// Part of each pool's platform fee in a token owed to its referrers, see `ReferralReserve`.
+    pub referral_reserves: LookupMap<(PoolId, AccountId), ReferralReserve>,
 }
 
 #[derive(BorshDeserialize, BorshSerialize, Deserialize, Serialize, Clone)]
@@ -34,14 +123,106 @@ pub struct PoolMetadata {
     pub creator_id: AccountId,
     pub staking_amount: u128,
     pub status: Status,
+    /// First of the accepted tokens, kept for clients that only know single-token pools.
     pub token_id: AccountId,
+    /// Worth of the funds held, in the reference unit of `target_funding` and the pledge limits.
     pub total_balance: u128,
     pub target_funding: u128,
-    pub time_init: u64,
-    pub time_start_pledge: u64,
-    pub time_end_pledge: u64,
+    pub time_init: Timestamp,
+    pub time_start_pledge: Timestamp,
+    pub time_end_pledge: Timestamp,
     pub funding_duration_days: u64,
     pub min_multiple_pledge: u128,
+    /// Sum of the backers' voting power still outstanding; set when funding ends
+    /// and reduced as backers claim refunds.
+    pub total_voting_power: u128,
+    /// Smallest total pledge of one backer, 0 for no minimum.
+    pub min_pledge: u128,
+    /// Largest total pledge of one backer, 0 for no maximum.
+    pub max_pledge: u128,
+    /// Pledges stop once the pool holds this amount, 0 to stop at `target_funding`.
+    pub hard_cap: u128,
+    /// Percentage of a pledge withdrawn during funding that stays in the pool.
+    pub withdraw_penalty_percent: u8,
+    /// Tokens the pool accepts, starting with `token_id`, with what the pool holds of each.
+    pub tokens: Vec<PoolToken>,
+}
+
+/// Accepted token of a pool.
+#[derive(BorshDeserialize, BorshSerialize, Deserialize, Serialize, Clone)]
+#[serde(crate = "near_sdk::serde")]
+pub struct PoolToken {
+    pub token_id: AccountId,
+    pub balance: u128,
+    /// Pledges in this token not yet withdrawn or refunded; refunds share `balance` by them.
+    pub pledged: u128,
+}
+
+/// Part of a backer's pledge made in one token.
+#[derive(BorshDeserialize, BorshSerialize, Deserialize, Serialize, Clone)]
+#[serde(crate = "near_sdk::serde")]
+pub struct TokenPledge {
+    pub token_id: AccountId,
+    pub amount: u128,
+    /// Worth of `amount` in the reference unit at the rate of the time it was pledged.
+    pub value: u128,
+}
+
+/// Optional pledge bounds and funding terms declared by the creator in `init_pool`.
+#[derive(Deserialize, Serialize, Clone, Default)]
+#[serde(crate = "near_sdk::serde")]
+pub struct PledgeLimits {
+    pub min_pledge: Option<U128>,
+    pub max_pledge: Option<U128>,
+    pub hard_cap: Option<U128>,
+    #[serde(flatten)]
+    pub funding_terms: PoolFundingTerms,
+}
+
+/// When a pool below its target waits for its creator and how long the grace periods last.
+#[derive(BorshDeserialize, BorshSerialize, Deserialize, Serialize, Clone, PartialEq, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct FundingTerms {
+    /// Percent of the target a pool must raise to wait for its creator instead of being refunded.
+    pub waiting_threshold_percent: u8,
+    /// Time the creator of a WAITING pool has to accept it.
+    pub waiting_extension: Duration,
+    /// Time an INIT pool waits for review before it can be rejected.
+    pub init_expiry: Duration,
+}
+
+impl Default for FundingTerms {
+    fn default() -> Self {
+        Self {
+            waiting_threshold_percent: DEFAULT_WAITING_THRESHOLD_PERCENT,
+            waiting_extension: DEFAULT_WAITING_EXTENSION_DURATION,
+            init_expiry: DEFAULT_INIT_EXPIRY_DURATION,
+        }
+    }
+}
+
+/// Funding terms a pool overrides, the ones left out follow the contract's `FundingTerms`.
+#[derive(BorshDeserialize, BorshSerialize, Deserialize, Serialize, Clone, Default)]
+#[serde(crate = "near_sdk::serde")]
+pub struct PoolFundingTerms {
+    pub waiting_threshold_percent: Option<u8>,
+    pub waiting_extension: Option<Duration>,
+    pub init_expiry: Option<Duration>,
+}
+
+impl PoolFundingTerms {
+    pub fn is_empty(&self) -> bool {
+        self.waiting_threshold_percent.is_none() && self.waiting_extension.is_none() && self.init_expiry.is_none()
+    }
+
+    // the pool's terms on top of the contract's
+    pub fn apply(&self, defaults: &FundingTerms) -> FundingTerms {
+        FundingTerms {
+            waiting_threshold_percent: self.waiting_threshold_percent.unwrap_or(defaults.waiting_threshold_percent),
+            waiting_extension: self.waiting_extension.unwrap_or(defaults.waiting_extension),
+            init_expiry: self.init_expiry.unwrap_or(defaults.init_expiry),
+        }
+    }
 }
 
 #[derive(BorshDeserialize, BorshSerialize, Deserialize, Serialize, Clone)]
@@ -51,6 +232,20 @@ pub struct Assets {
     pub balances: u128,
 }
 
+/// NEAR owned by the platform, apart from the stakes still held for their pools.
+#[derive(BorshDeserialize, BorshSerialize, Deserialize, Serialize, Clone, Default)]
+#[serde(crate = "near_sdk::serde")]
+pub struct TreasuryLedger {
+    /// Available to `withdraw_treasury` and keeper rewards.
+    pub balance: u128,
+    /// Kept from the stakes of rejected pools.
+    pub retained_stakes: u128,
+    /// Slashed from the stakes of pools with a failed milestone.
+    pub slashed_stakes: u128,
+    pub keeper_rewards_paid: u128,
+    pub withdrawn: u128,
+}
+
 #[derive(BorshDeserialize, BorshSerialize, Deserialize, Serialize, Clone, Debug, PartialEq)]
 #[serde(crate = "near_sdk::serde")]
 pub enum Status {
@@ -71,7 +266,7 @@ pub enum Status {
 #[serde(crate = "near_sdk::serde")]
 pub struct UserTokenDepositRecord {
     pub amount: u128, // pledge amount if backer deposited +amount
-    pub voting_power: f64, // 0
+    pub voting_power: u128, // share of the pool, equal to amount once funding ends
 }
 
 #[derive(BorshDeserialize, BorshSerialize, Deserialize, Serialize, Clone)]
@@ -81,12 +276,324 @@ pub struct UserRecordDetail {
     pub record: UserTokenDepositRecord,
 }
 
+/// Pool backed by an account together with the account's record in it.
+#[derive(Deserialize, Serialize, Clone)]
+#[serde(crate = "near_sdk::serde")]
+pub struct BackerPoolDetail {
+    pub pool: PoolMetadata,
+    pub record: Option<UserTokenDepositRecord>,
+}
+
+/// Criteria for `get_pools`; every field left out matches all pools.
+/// `created_from`/`created_to` bound `PoolMetadata::time_init` (inclusive).
+#[derive(Deserialize, Serialize, Clone, Default)]
+#[serde(crate = "near_sdk::serde")]
+pub struct PoolFilter {
+    pub status: Option<Status>,
+    pub creator_id: Option<AccountId>,
+    pub token_id: Option<AccountId>,
+    pub created_from: Option<Timestamp>,
+    pub created_to: Option<Timestamp>,
+}
+
+/// Tranche of the raised funds, released to the creator once backers approve it.
+/// A `deadline` of 0 means the milestone has no deadline.
+#[derive(BorshDeserialize, BorshSerialize, Deserialize, Serialize, Clone)]
+#[serde(crate = "near_sdk::serde")]
+pub struct Milestone {
+    pub percent: u8,
+    pub deadline: Timestamp,
+    pub status: MilestoneStatus,
+    pub amount_released: u128,
+}
+
+#[derive(BorshDeserialize, BorshSerialize, Deserialize, Serialize, Clone, Debug, PartialEq)]
+#[serde(crate = "near_sdk::serde")]
+pub enum MilestoneStatus {
+    PENDING,
+    APPROVED,
+    RELEASED,
+    FAILED
+}
+
+#[derive(Deserialize, Serialize, Clone)]
+#[serde(crate = "near_sdk::serde")]
+pub struct MilestoneInfo {
+    pub percent: u8,
+    pub deadline: Timestamp,
+}
+
+/// Perk a backer earns by pledging at least `min_pledge` to the pool, in the pool's reference unit.
+/// A `quantity` of 0 means the tier is unlimited.
+#[derive(BorshDeserialize, BorshSerialize, Deserialize, Serialize, Clone)]
+#[serde(crate = "near_sdk::serde")]
+pub struct RewardTier {
+    pub min_pledge: u128,
+    pub quantity: u32,
+    pub allocated: u32,
+    pub description: String,
+}
+
+/// Reward tier declared by the creator in `set_reward_tiers`.
+#[derive(Deserialize, Serialize, Clone)]
+#[serde(crate = "near_sdk::serde")]
+pub struct RewardTierInfo {
+    pub min_pledge: U128,
+    pub quantity: Option<u32>,
+    pub description: String,
+}
+
+/// Funding goal above `target_funding`, in the pool's reference unit, unlocked when funding
+/// closes with at least `amount` raised.
+#[derive(BorshDeserialize, BorshSerialize, Deserialize, Serialize, Clone)]
+#[serde(crate = "near_sdk::serde")]
+pub struct StretchGoal {
+    pub amount: u128,
+    pub description: String,
+    pub unlocked: bool,
+}
+
+/// Stretch goal declared by the creator in `set_stretch_goals`.
+#[derive(Deserialize, Serialize, Clone)]
+#[serde(crate = "near_sdk::serde")]
+pub struct StretchGoalInfo {
+    pub amount: U128,
+    pub description: String,
+}
+
+/// What a pool does with the raise above its highest goal reached (the target or an unlocked
+/// stretch goal) when funding closes successfully. Pledges never go above the hard cap.
+#[derive(BorshDeserialize, BorshSerialize, Deserialize, Serialize, Clone, PartialEq, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub enum OverfundingPolicy {
+    AcceptAll,    // keeps the whole raise
+    CapAndRefund, // keeps the goal, the backers who joined last get the excess back
+    ProRata,      // keeps the goal, every backer gets the same share of its pledge back
+}
+
+/// Pledges a referrer brought to a pool. `tokens` holds the referred pledges still in the pool,
+/// the referral reward is a share of them.
+#[derive(BorshDeserialize, BorshSerialize, Deserialize, Serialize, Clone)]
+#[serde(crate = "near_sdk::serde")]
+pub struct ReferralStats {
+    pub referrer_id: AccountId,
+    /// Backers who made their first pledge in the pool through the referrer.
+    pub backers: u32,
+    /// Worth of the referred pledges in the reference unit.
+    pub pledged: u128,
+    pub tokens: Vec<TokenPledge>,
+    /// Tokens the referral reward was already paid in.
+    pub rewarded_tokens: Vec<AccountId>,
+}
+
+/// Referral rewards of a pool in one token. The platform fee of the pool's tranches fills `reserved`
+/// up to the reward owed on `referred` before any of it accrues to `Assets::balances`.
+#[derive(BorshDeserialize, BorshSerialize, Deserialize, Serialize, Clone, Default)]
+#[serde(crate = "near_sdk::serde")]
+pub struct ReferralReserve {
+    /// Referred pledges of all referrers still in the pool.
+    pub referred: u128,
+    /// Fees set aside so far, `reserved - paid` is left for the referrers who did not claim yet.
+    pub reserved: u128,
+    pub paid: u128,
+}
+
+/// Reward tier held by a backer together with its index in the pool's tiers.
+#[derive(Deserialize, Serialize, Clone)]
+#[serde(crate = "near_sdk::serde")]
+pub struct BackerTier {
+    pub tier_index: u32,
+    pub tier: RewardTier,
+}
+
+/// Disbursement request submitted by the creator of a pool in VOTING status
+/// for its current milestone.
+#[derive(BorshDeserialize, BorshSerialize, Deserialize, Serialize, Clone)]
+#[serde(crate = "near_sdk::serde")]
+pub struct Proposal {
+    pub pool_id: PoolId,
+    pub milestone_index: u32,
+    pub description: String,
+    pub time_start_voting: Timestamp,
+    pub time_end_voting: Timestamp,
+    pub approve_power: u128,
+    pub reject_power: u128,
+    pub status: ProposalStatus,
+}
+
+#[derive(BorshDeserialize, BorshSerialize, Deserialize, Serialize, Clone, Debug, PartialEq)]
+#[serde(crate = "near_sdk::serde")]
+pub enum ProposalStatus {
+    ACTIVE,
+    APPROVED,
+    REJECTED,
+    EXPIRED
+}
+
+#[derive(BorshDeserialize, BorshSerialize, Deserialize, Serialize, Clone)]
+#[serde(crate = "near_sdk::serde")]
+pub struct Vote {
+    pub approve: bool,
+    pub voting_power: u128,
+}
+
+/// Roles the owner can grant. The owner implicitly holds every role.
+#[derive(BorshDeserialize, BorshSerialize, Deserialize, Serialize, Clone, PartialEq, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub enum Role {
+    PoolReviewer,    // approves, rejects and settles pools
+    TreasuryManager, // manages supported tokens and releases funds to creators
+    Pauser,          // pauses the launchpad in an emergency
+}
+
+impl Status {
+    pub const ALL: [Status; 11] = [
+        Status::INIT, Status::APPROVED, Status::FUNDING, Status::REJECTED, Status::CANCELED, Status::FAILED,
+        Status::WAITING, Status::REFUNDED, Status::VOTING, Status::CLOSED, Status::SUCCESSFUL
+    ];
+
+    /// Statuses a pool can move to from this one, every status change follows these edges.
+    pub fn next_statuses(&self) -> &'static [Status] {
+        match self {
+            Status::INIT => &[Status::APPROVED, Status::REJECTED, Status::CANCELED],
+            Status::APPROVED => &[Status::FUNDING],
+            Status::FUNDING => &[Status::FAILED, Status::WAITING, Status::REFUNDED, Status::VOTING],
+            Status::WAITING => &[Status::REFUNDED, Status::VOTING],
+            Status::VOTING => &[Status::REFUNDED, Status::CLOSED, Status::SUCCESSFUL],
+            Status::REJECTED | Status::CANCELED | Status::FAILED
+                | Status::REFUNDED | Status::CLOSED | Status::SUCCESSFUL => &[],
+        }
+    }
+
+    pub fn can_transition_to(&self, next: &Status) -> bool {
+        self.next_statuses().contains(next)
+    }
+}
+
+impl Role {
+    pub const ALL: [Role; 3] = [Role::PoolReviewer, Role::TreasuryManager, Role::Pauser];
+}
+
+/// Who may pledge to a pool. Accounts on the pool's allowlist can always pledge to a restricted pool,
+/// a valid platform signature adds the backer to it.
+#[derive(BorshDeserialize, BorshSerialize, Deserialize, Serialize, Clone, PartialEq, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub enum PoolAccess {
+    Public,
+    Allowlist, // only accounts the creator put on the allowlist
+    Signature, // backers presenting a signature of "<pool_id>:<account_id>" by the platform key
+}
+
+/// Parts of the launchpad a pauser can halt independently during an incident.
+#[derive(BorshDeserialize, BorshSerialize, Deserialize, Serialize, Clone, PartialEq, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub enum Feature {
+    Pledge,          // pledges through ft_on_transfer, refunded while paused
+    Refund,          // claim_refund
+    CreatorWithdraw, // withdraw_to_creator
+}
+
+impl Feature {
+    pub const ALL: [Feature; 3] = [Feature::Pledge, Feature::Refund, Feature::CreatorWithdraw];
+}
+
+/// JSON message of `ft_on_transfer`, e.g. `{"version": 1, "action": "pledge", "pool_id": 1, "tier": 2, "referrer": "alice.near"}`.
+/// A missing `version` is read as the current one. Pledges to `PoolAccess::Signature` pools carry a base64 `signature`.
+#[derive(Deserialize, Serialize, Clone, Debug, PartialEq)]
+#[serde(crate = "near_sdk::serde")]
+pub struct TransferMsg {
+    #[serde(default = "TransferMsg::current_version")]
+    pub version: u8,
+    #[serde(flatten)]
+    pub action: TransferAction,
+}
+
+impl TransferMsg {
+    fn current_version() -> u8 {
+        TRANSFER_MSG_VERSION
+    }
+}
+
+/// What the tokens sent with `ft_transfer_call` are for.
+#[derive(Deserialize, Serialize, Clone, Debug, PartialEq)]
+#[serde(crate = "near_sdk::serde")]
+#[serde(tag = "action", rename_all = "snake_case")]
+pub enum TransferAction {
+    Pledge { pool_id: PoolId, tier: Option<u32>, referrer: Option<AccountId>, signature: Option<String> },
+}
+
+/// Why a pledge was sent back to the backer, logged with the `pledge_rejected` event.
+#[derive(Deserialize, Serialize, Clone, Copy, Debug, PartialEq)]
+#[serde(crate = "near_sdk::serde")]
+pub enum RefundReason {
+    Paused,
+    TokenNotSupported,
+    InvalidMessage,
+    UnsupportedVersion,
+    PoolNotFound,
+    PoolNotFunding,
+    OutsidePledgePeriod,
+    TokenNotAccepted,
+    HardCapReached,
+    MaxPledgeReached,
+    BelowMinimumPledge,
+    TierNotFound,
+    BelowTierMinimum,
+    TierSoldOut,
+    NotAllowlisted,
+    SignatureRequired,
+    InvalidSignature,
+}
+
+impl RefundReason {
+    pub fn message(&self) -> &'static str {
+        match self {
+            RefundReason::Paused => "Pledging is paused",
+            RefundReason::TokenNotSupported => "Token ID from message does not match any token ID in the list.",
+            RefundReason::InvalidMessage => "Invalid ft_on_transfer message",
+            RefundReason::UnsupportedVersion => "Unsupported message version",
+            RefundReason::PoolNotFound => "Pool does not exist",
+            RefundReason::PoolNotFunding => "Pool is not in funding status",
+            RefundReason::OutsidePledgePeriod => "Not within pledge period",
+            RefundReason::TokenNotAccepted => "Invalid token for this pool",
+            RefundReason::HardCapReached => "Pool has reached its hard cap",
+            RefundReason::MaxPledgeReached => "Backer has reached the maximum pledge of this pool",
+            RefundReason::BelowMinimumPledge => "Pledge is below the minimum pledge or the pledge multiple of this pool",
+            RefundReason::TierNotFound => "Reward tier does not exist",
+            RefundReason::BelowTierMinimum => "Pledge is below the minimum pledge of the reward tier",
+            RefundReason::TierSoldOut => "Reward tier is sold out",
+            RefundReason::NotAllowlisted => "Sender is not on the allowlist of this pool",
+            RefundReason::SignatureRequired => "Pool requires a platform signature to pledge",
+            RefundReason::InvalidSignature => "Invalid pledge signature",
+        }
+    }
+}
+
 #[derive(BorshSerialize)]
 pub enum LaunchpadStorageKey {
     AllPoolId,
     PoolMetadataById,
     UserRecordsMap,
     UserRecordsById { pool_id: PoolId },
+    ProposalByPoolId,
+    VotesMap,
+    MilestonesByPoolId,
+    PoolsByBacker,
+    RoleMembers,
+    FeeBpsByToken,
+    TokenRates,
+    TokenPledges,
+    RewardTiersByPoolId,
+    BackerTiers,
+    ReferralsByPoolId,
+    ReferrerByBacker,
+    AccessByPoolId,
+    AllowlistByPoolId,
+    StretchGoalsByPoolId,
+    OverfundingByPoolId,
+    ExcessByBacker,
+    FundingTermsByPoolId,
+    ReferralReserves,
 }
 
 impl LaunchpadStorageKey {
@@ -96,11 +603,48 @@ impl LaunchpadStorageKey {
         prefix.extend_from_slice(&pool_id.to_le_bytes());
         prefix
     }
+
+    pub fn backer_pools_prefix(account_id: &AccountId) -> Vec<u8> {
+        let mut prefix = Vec::with_capacity(2 + 32);
+        prefix.extend_from_slice(b"bp");
+        prefix.extend_from_slice(&near_sdk::env::sha256(account_id.as_bytes()));
+        prefix
+    }
+
+    pub fn role_members_prefix(role: &Role) -> Vec<u8> {
+        let mut prefix = Vec::with_capacity(4 + 1);
+        prefix.extend_from_slice(b"role");
+        prefix.extend_from_slice(&role.try_to_vec().unwrap());
+        prefix
+    }
+
+    pub fn votes_prefix(pool_id: PoolId) -> Vec<u8> {
+        let mut prefix = Vec::with_capacity(4 + 8);
+        prefix.extend_from_slice(b"vote");
+        prefix.extend_from_slice(&pool_id.to_le_bytes());
+        prefix
+    }
+
+    pub fn allowlist_prefix(pool_id: PoolId) -> Vec<u8> {
+        let mut prefix = Vec::with_capacity(4 + 8);
+        prefix.extend_from_slice(b"alst");
+        prefix.extend_from_slice(&pool_id.to_le_bytes());
+        prefix
+    }
+
+    pub fn referrals_prefix(pool_id: PoolId) -> Vec<u8> {
+        let mut prefix = Vec::with_capacity(4 + 8);
+        prefix.extend_from_slice(b"refr");
+        prefix.extend_from_slice(&pool_id.to_le_bytes());
+        prefix
+    }
 }
 
 pub trait LaunchpadFeature {
-    fn init_pool(&mut self, campaign_id: String, token_id: AccountId, min_multiple_pledge: u128, target_funding: U128) -> PoolMetadata;
-    fn set_funding_pool_by_creator(&mut self, pool_id: PoolId, time_start_pledge: u64, funding_duration_days: u64);
+    fn init_pool(&mut self, campaign_id: String, token_id: AccountId, min_multiple_pledge: u128, target_funding: U128, milestones: Option<Vec<MilestoneInfo>>, pledge_limits: Option<PledgeLimits>) -> PoolMetadata;
+    fn set_accepted_tokens(&mut self, pool_id: PoolId, accepted_tokens: Vec<AccountId>);
+    fn set_funding_pool_by_creator(&mut self, pool_id: PoolId, time_start_pledge: Timestamp, funding_duration_days: u64, milestones: Option<Vec<MilestoneInfo>>, withdraw_penalty_percent: Option<u8>);
+    fn change_pool_funding_time(&mut self, pool_id: PoolId, time_start_pledge: Timestamp, time_end_pledge: Timestamp);
     fn admin_check_pool_status_after_init_15days(&mut self, pool_id: PoolId);
     fn ft_on_transfer(
         &mut self,
@@ -108,6 +652,7 @@ pub trait LaunchpadFeature {
         amount: U128,
         msg: String,
     ) -> PromiseOrValue<U128>;
+    fn pledge(&mut self, pool_id: PoolId, tier_index: Option<u32>, referrer: Option<AccountId>, signature: Option<String>) -> U128;
     fn add_token(
         &mut self,
         token_id: String,
@@ -121,25 +666,122 @@ pub trait LaunchpadFeature {
         token_id: AccountId
     );
     fn set_min_staking_amount(&mut self, amount: U128);
+    fn set_funding_terms(&mut self, terms: FundingTerms);
     fn set_refund_reject_pool(&mut self, percent: u8);
     fn admin_set_status_pool_pre_funding(&mut self, pool_id: PoolId, approve: bool) -> PoolMetadata;
     fn cancel_pool(&mut self, pool_id: PoolId) -> PoolMetadata;
-    fn withdraw_to_creator(&mut self, pool_id: PoolId, amount: U128);
+    fn withdraw_to_creator(&mut self, pool_id: PoolId);
     fn check_funding_result(&mut self, pool_id: PoolId, is_waiting_funding: bool) -> PoolMetadata;
     fn claim_refund(&mut self, pool_id: PoolId);
+    fn withdraw_pledge(&mut self, pool_id: PoolId, amount: Option<U128>);
     fn update_pool_status(&mut self, pool_id: PoolId, status: String);
     fn creator_accept_voting(&mut self, pool_id: PoolId, approve: bool) -> PoolMetadata;
 }
 
+pub trait LaunchpadVoting {
+    fn create_proposal(&mut self, pool_id: PoolId, description: String) -> Proposal;
+    fn vote(&mut self, pool_id: PoolId, approve: bool);
+    fn tally_votes(&mut self, pool_id: PoolId) -> PoolMetadata;
+    fn fail_expired_milestone(&mut self, pool_id: PoolId) -> PoolMetadata;
+    fn set_voting_config(&mut self, quorum_percent: u8, duration: Duration);
+}
+
+pub trait LaunchpadAccess {
+    fn grant_role(&mut self, role: Role, account_id: AccountId);
+    fn revoke_role(&mut self, role: Role, account_id: AccountId);
+    fn accept_admin(&mut self);
+    fn has_role(&self, role: Role, account_id: AccountId) -> bool;
+    fn get_role_members(&self, role: Role) -> Vec<AccountId>;
+    fn get_roles(&self, account_id: AccountId) -> Vec<Role>;
+    fn get_owner(&self) -> AccountId;
+    fn get_pending_owner(&self) -> Option<AccountId>;
+}
+
+pub trait LaunchpadTreasury {
+    fn withdraw_treasury(&mut self, amount: Option<U128>, receiver_id: Option<AccountId>);
+    fn set_stake_slash_percent(&mut self, percent: u8);
+    fn get_treasury(&self) -> TreasuryLedger;
+    fn get_stake_slash_percent(&self) -> u8;
+    fn set_platform_fee(&mut self, fee_bps: u16);
+    fn set_token_fee(&mut self, token_id: AccountId, fee_bps: Option<u16>);
+    fn set_fee_collector(&mut self, account_id: AccountId);
+    fn withdraw_fees(&mut self, token_id: AccountId, amount: Option<U128>);
+    fn get_platform_fee(&self, token_id: Option<AccountId>) -> u16;
+    fn get_fee_collector(&self) -> AccountId;
+    fn get_assets(&self) -> Vec<Assets>;
+    fn set_token_rate(&mut self, token_id: AccountId, rate: Option<U128>);
+    fn get_token_rate(&self, token_id: AccountId) -> U128;
+}
+
+pub trait LaunchpadRewards {
+    fn set_reward_tiers(&mut self, pool_id: PoolId, tiers: Vec<RewardTierInfo>);
+    fn get_reward_tiers(&self, pool_id: PoolId) -> Vec<RewardTier>;
+    fn get_backer_tier(&self, pool_id: PoolId, account_id: AccountId) -> Option<BackerTier>;
+}
+
+pub trait LaunchpadPoolAccess {
+    fn set_pledge_signer_key(&mut self, public_key: Option<String>);
+    fn set_pool_access(&mut self, pool_id: PoolId, access: PoolAccess);
+    fn add_to_allowlist(&mut self, pool_id: PoolId, account_ids: Vec<AccountId>);
+    fn remove_from_allowlist(&mut self, pool_id: PoolId, account_ids: Vec<AccountId>);
+    fn get_pledge_signer_key(&self) -> Option<String>;
+    fn get_pool_access(&self, pool_id: PoolId) -> PoolAccess;
+    fn is_allowlisted(&self, pool_id: PoolId, account_id: AccountId) -> bool;
+    fn get_allowlist(&self, pool_id: PoolId, from_index: Option<u64>, limit: Option<u64>) -> Vec<AccountId>;
+}
+
+pub trait LaunchpadStretchGoals {
+    fn set_stretch_goals(&mut self, pool_id: PoolId, goals: Vec<StretchGoalInfo>);
+    fn set_overfunding_policy(&mut self, pool_id: PoolId, policy: OverfundingPolicy);
+    fn claim_excess(&mut self, pool_id: PoolId);
+    fn get_stretch_goals(&self, pool_id: PoolId) -> Vec<StretchGoal>;
+    fn get_overfunding_policy(&self, pool_id: PoolId) -> OverfundingPolicy;
+    fn get_excess(&self, pool_id: PoolId, account_id: AccountId) -> Vec<TokenPledge>;
+}
+
+pub trait LaunchpadReferrals {
+    fn set_referral_reward(&mut self, reward_bps: u16);
+    fn claim_referral_reward(&mut self, pool_id: PoolId);
+    fn get_referral_reward(&self) -> u16;
+    fn get_referrer(&self, pool_id: PoolId, account_id: AccountId) -> Option<AccountId>;
+    fn get_referral_stats(&self, pool_id: PoolId, referrer_id: AccountId) -> Option<ReferralStats>;
+    fn get_referral_reserve(&self, pool_id: PoolId, token_id: AccountId) -> ReferralReserve;
+    fn get_pool_referrals(&self, pool_id: PoolId, from_index: Option<u64>, limit: Option<u64>) -> Vec<ReferralStats>;
+}
+
+pub trait LaunchpadKeeper {
+    fn advance_pool(&mut self, pool_id: PoolId) -> PoolMetadata;
+    fn set_keeper_reward(&mut self, amount: U128);
+    fn get_keeper_reward(&self) -> U128;
+}
+
+pub trait LaunchpadPause {
+    fn pause(&mut self, features: Option<Vec<Feature>>);
+    fn unpause(&mut self, features: Option<Vec<Feature>>);
+    fn is_paused(&self, feature: Feature) -> bool;
+    fn get_paused_features(&self) -> Vec<Feature>;
+}
 
 pub trait LaunchpadGet {
     fn is_token_supported(&self, token_id: AccountId) -> bool;
-    fn get_all_pool(&self) -> Option<Vec<PoolMetadata>>;
-    fn get_pools_by_status(&self, status_str: String) -> Option<Vec<PoolMetadata>>;
+    fn get_number_of_pools(&self) -> u64;
+    fn get_all_pool(&self, from_index: Option<u64>, limit: Option<u64>) -> Option<Vec<PoolMetadata>>;
+    fn get_pools_by_status(&self, status_str: String, from_index: Option<u64>, limit: Option<u64>) -> Option<Vec<PoolMetadata>>;
+    fn get_pools(&self, filter: PoolFilter, from_index: Option<u64>, limit: Option<u64>) -> Option<Vec<PoolMetadata>>;
+    fn get_pools_by_backer(&self, account_id: AccountId, from_index: Option<u64>, limit: Option<u64>) -> Option<Vec<BackerPoolDetail>>;
     fn get_detail_pool(&self, pool_id: PoolId) -> Option<PoolMetadata>;
     fn get_balance_creator(&self, pool_id: PoolId) -> Option<u128>;
     fn get_refund_reject_pool(&self) -> u8;
     fn get_min_staking_amount(&self) -> U128;
-    fn get_user_records_by_pool_id(&self, pool_id: PoolId) -> Option<Vec<UserRecordDetail>>;
-    fn get_current_timestamp(&self) -> u64;
+    fn get_funding_terms(&self) -> FundingTerms;
+    fn get_pool_funding_terms(&self, pool_id: PoolId) -> Option<FundingTerms>;
+    fn get_user_records_by_pool_id(&self, pool_id: PoolId, from_index: Option<u64>, limit: Option<u64>) -> Option<Vec<UserRecordDetail>>;
+    fn get_token_pledges(&self, pool_id: PoolId, account_id: AccountId) -> Vec<TokenPledge>;
+    fn get_current_timestamp(&self) -> Timestamp;
+    fn get_proposal(&self, pool_id: PoolId) -> Option<Proposal>;
+    fn get_vote(&self, pool_id: PoolId, account_id: AccountId) -> Option<Vote>;
+    fn get_milestones(&self, pool_id: PoolId) -> Option<Vec<Milestone>>;
+    fn get_voting_quorum_percent(&self) -> u8;
+    fn get_voting_duration(&self) -> Duration;
+    fn get_state_version(&self) -> u8;
 }
\ No newline at end of file
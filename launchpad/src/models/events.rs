@@ -0,0 +1,484 @@
+//! NEP-297 events emitted by the launchpad, logged as `EVENT_JSON:{...}`.
+use near_sdk::serde::Serialize;
+use near_sdk::{env, json_types::U128, serde_json, AccountId};
+
+use super::contract::{Feature, OverfundingPolicy, ProposalStatus, RefundReason, Role, Status};
+use super::time::Timestamp;
+use super::PoolId;
+
+pub const EVENT_STANDARD: &str = "openedu_launchpad";
+pub const EVENT_STANDARD_VERSION: &str = "1.0.0";
+
+#[derive(Serialize)]
+#[serde(crate = "near_sdk::serde")]
+struct EventLog<'a, T: Serialize> {
+    standard: &'static str,
+    version: &'static str,
+    event: &'a str,
+    data: &'a [T],
+}
+
+fn emit_event<T: Serialize>(event: &str, data: &[T]) {
+    let log = EventLog {
+        standard: EVENT_STANDARD,
+        version: EVENT_STANDARD_VERSION,
+        event,
+        data,
+    };
+    env::log_str(&format!("EVENT_JSON:{}", serde_json::to_string(&log).unwrap()));
+}
+
+#[derive(Serialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct PoolCreated<'a> {
+    pub pool_id: PoolId,
+    pub campaign_id: &'a str,
+    pub creator_id: &'a AccountId,
+    pub token_id: &'a AccountId,
+    pub target_funding: U128,
+    pub staking_amount: U128,
+}
+
+impl PoolCreated<'_> {
+    pub fn emit(self) {
+        emit_event("pool_created", &[self])
+    }
+}
+
+#[derive(Serialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct PoolStatusChanged<'a> {
+    pub pool_id: PoolId,
+    pub old_status: &'a Status,
+    pub new_status: &'a Status,
+}
+
+impl PoolStatusChanged<'_> {
+    pub fn emit(self) {
+        emit_event("pool_status_changed", &[self])
+    }
+}
+
+/// NEAR stake returned to the creator when a pool is rejected or canceled.
+#[derive(Serialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct StakeRefunded<'a> {
+    pub pool_id: PoolId,
+    pub creator_id: &'a AccountId,
+    pub amount: U128,
+}
+
+impl StakeRefunded<'_> {
+    pub fn emit(self) {
+        emit_event("stake_refunded", &[self])
+    }
+}
+
+#[derive(Serialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct Pledge<'a> {
+    pub pool_id: PoolId,
+    pub account_id: &'a AccountId,
+    pub token_id: &'a AccountId,
+    pub amount: U128,
+}
+
+impl Pledge<'_> {
+    pub fn emit(self) {
+        emit_event("pledge", &[self])
+    }
+}
+
+#[derive(Serialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct PledgeRejected<'a> {
+    pub account_id: &'a AccountId,
+    pub token_id: &'a AccountId,
+    pub amount: U128,
+    pub reason: RefundReason,
+}
+
+impl PledgeRejected<'_> {
+    pub fn emit(self) {
+        emit_event("pledge_rejected", &[self])
+    }
+}
+
+#[derive(Serialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct ReferralRecorded<'a> {
+    pub pool_id: PoolId,
+    pub account_id: &'a AccountId,
+    pub referrer_id: &'a AccountId,
+}
+
+impl ReferralRecorded<'_> {
+    pub fn emit(self) {
+        emit_event("referral_recorded", &[self])
+    }
+}
+
+#[derive(Serialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct ReferralReward<'a> {
+    pub pool_id: PoolId,
+    pub referrer_id: &'a AccountId,
+    pub token_id: &'a AccountId,
+    pub amount: U128,
+}
+
+impl ReferralReward<'_> {
+    pub fn emit(self) {
+        emit_event("referral_reward", &[self])
+    }
+
+    pub fn emit_failed(self) {
+        emit_event("referral_reward_failed", &[self])
+    }
+}
+
+#[derive(Serialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct Refund<'a> {
+    pub pool_id: PoolId,
+    pub account_id: &'a AccountId,
+    pub token_id: &'a AccountId,
+    pub amount: U128,
+}
+
+impl Refund<'_> {
+    pub fn emit(self) {
+        emit_event("refund", &[self])
+    }
+
+    pub fn emit_failed(self) {
+        emit_event("refund_failed", &[self])
+    }
+}
+
+/// Pledge pulled back by a backer during funding: `amount` is paid back, `penalty` stays in the pool.
+#[derive(Serialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct PledgeWithdrawn<'a> {
+    pub pool_id: PoolId,
+    pub account_id: &'a AccountId,
+    pub token_id: &'a AccountId,
+    pub amount: U128,
+    pub penalty: U128,
+}
+
+impl PledgeWithdrawn<'_> {
+    pub fn emit(self) {
+        emit_event("pledge_withdrawn", &[self])
+    }
+
+    pub fn emit_failed(self) {
+        emit_event("pledge_withdraw_failed", &[self])
+    }
+}
+
+#[derive(Serialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct CreatorWithdraw<'a> {
+    pub pool_id: PoolId,
+    pub creator_id: &'a AccountId,
+    pub milestone_index: u32,
+    pub token_id: &'a AccountId,
+    pub amount: U128,
+    /// Platform fee kept from the tranche, on top of `amount`.
+    pub fee: U128,
+}
+
+impl CreatorWithdraw<'_> {
+    pub fn emit(self) {
+        emit_event("creator_withdraw", &[self])
+    }
+
+    pub fn emit_failed(self) {
+        emit_event("creator_withdraw_failed", &[self])
+    }
+}
+
+#[derive(Serialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct ProposalCreated {
+    pub pool_id: PoolId,
+    pub milestone_index: u32,
+    pub time_end_voting: Timestamp,
+}
+
+impl ProposalCreated {
+    pub fn emit(self) {
+        emit_event("proposal_created", &[self])
+    }
+}
+
+#[derive(Serialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct ProposalFinalized<'a> {
+    pub pool_id: PoolId,
+    pub milestone_index: u32,
+    pub status: &'a ProposalStatus,
+    pub approve_power: U128,
+    pub reject_power: U128,
+}
+
+impl ProposalFinalized<'_> {
+    pub fn emit(self) {
+        emit_event("proposal_finalized", &[self])
+    }
+}
+
+#[derive(Serialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct VoteCast<'a> {
+    pub pool_id: PoolId,
+    pub account_id: &'a AccountId,
+    pub approve: bool,
+    pub voting_power: U128,
+}
+
+impl VoteCast<'_> {
+    pub fn emit(self) {
+        emit_event("vote_cast", &[self])
+    }
+}
+
+#[derive(Serialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct RoleChanged<'a> {
+    pub role: &'a Role,
+    pub account_id: &'a AccountId,
+}
+
+impl RoleChanged<'_> {
+    pub fn emit_granted(self) {
+        emit_event("role_granted", &[self])
+    }
+
+    pub fn emit_revoked(self) {
+        emit_event("role_revoked", &[self])
+    }
+}
+
+#[derive(Serialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct AdminTransfer<'a> {
+    pub old_owner_id: &'a AccountId,
+    pub new_owner_id: &'a AccountId,
+}
+
+impl AdminTransfer<'_> {
+    pub fn emit_proposed(self) {
+        emit_event("admin_transfer_proposed", &[self])
+    }
+
+    pub fn emit_accepted(self) {
+        emit_event("admin_transfer_accepted", &[self])
+    }
+}
+
+#[derive(Serialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct PauseChanged<'a> {
+    pub features: &'a [Feature],
+    pub account_id: &'a AccountId,
+}
+
+impl PauseChanged<'_> {
+    pub fn emit_paused(self) {
+        emit_event("paused", &[self])
+    }
+
+    pub fn emit_unpaused(self) {
+        emit_event("unpaused", &[self])
+    }
+}
+
+/// Part of the creator's stake moved to the treasury after a failed milestone.
+#[derive(Serialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct StakeSlashed<'a> {
+    pub pool_id: PoolId,
+    pub creator_id: &'a AccountId,
+    pub amount: U128,
+}
+
+impl StakeSlashed<'_> {
+    pub fn emit(self) {
+        emit_event("stake_slashed", &[self])
+    }
+}
+
+#[derive(Serialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct TreasuryWithdraw<'a> {
+    pub receiver_id: &'a AccountId,
+    pub amount: U128,
+}
+
+impl TreasuryWithdraw<'_> {
+    pub fn emit(self) {
+        emit_event("treasury_withdraw", &[self])
+    }
+
+    pub fn emit_failed(self) {
+        emit_event("treasury_withdraw_failed", &[self])
+    }
+}
+
+/// Platform fees of a token sent to the fee collector.
+#[derive(Serialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct FeeWithdraw<'a> {
+    pub token_id: &'a AccountId,
+    pub receiver_id: &'a AccountId,
+    pub amount: U128,
+}
+
+impl FeeWithdraw<'_> {
+    pub fn emit(self) {
+        emit_event("fee_withdraw", &[self])
+    }
+
+    pub fn emit_failed(self) {
+        emit_event("fee_withdraw_failed", &[self])
+    }
+}
+
+/// NEAR paid to the account that advanced a pool through `advance_pool`.
+#[derive(Serialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct KeeperRewarded<'a> {
+    pub pool_id: PoolId,
+    pub account_id: &'a AccountId,
+    pub amount: U128,
+}
+
+impl KeeperRewarded<'_> {
+    pub fn emit(self) {
+        emit_event("keeper_rewarded", &[self])
+    }
+}
+
+/// Reward tier a backer selected with a pledge, replacing `previous_tier_index` if any.
+#[derive(Serialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct RewardTierSelected<'a> {
+    pub pool_id: PoolId,
+    pub account_id: &'a AccountId,
+    pub tier_index: u32,
+    pub previous_tier_index: Option<u32>,
+}
+
+impl RewardTierSelected<'_> {
+    pub fn emit(self) {
+        emit_event("reward_tier_selected", &[self])
+    }
+}
+
+/// Reward tier given up by a backer whose pledge fell below its minimum.
+#[derive(Serialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct RewardTierReleased<'a> {
+    pub pool_id: PoolId,
+    pub account_id: &'a AccountId,
+    pub tier_index: u32,
+}
+
+impl RewardTierReleased<'_> {
+    pub fn emit(self) {
+        emit_event("reward_tier_released", &[self])
+    }
+}
+
+/// Stretch goal reached by the raise of a pool when its funding closed.
+#[derive(Serialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct StretchGoalUnlocked {
+    pub pool_id: PoolId,
+    pub goal_index: u32,
+    pub amount: U128,
+}
+
+impl StretchGoalUnlocked {
+    pub fn emit(self) {
+        emit_event("stretch_goal_unlocked", &[self])
+    }
+}
+
+/// Raise above `goal` the overfunding policy set aside for the backers to claim with `claim_excess`.
+#[derive(Serialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct OverfundingSettled<'a> {
+    pub pool_id: PoolId,
+    pub policy: &'a OverfundingPolicy,
+    pub goal: U128,
+    pub excess: U128,
+}
+
+impl OverfundingSettled<'_> {
+    pub fn emit(self) {
+        emit_event("overfunding_settled", &[self])
+    }
+}
+
+/// Pledge window set by the creator of an approved pool.
+#[derive(Serialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct FundingScheduled {
+    pub pool_id: PoolId,
+    pub time_start_pledge: Timestamp,
+    pub time_end_pledge: Timestamp,
+}
+
+impl FundingScheduled {
+    pub fn emit(self) {
+        emit_event("funding_scheduled", &[self])
+    }
+}
+
+/// New pledge window of a FUNDING pool moved by its creator or a pool reviewer.
+#[derive(Serialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct FundingRescheduled {
+    pub pool_id: PoolId,
+    pub time_start_pledge: Timestamp,
+    pub time_end_pledge: Timestamp,
+}
+
+impl FundingRescheduled {
+    pub fn emit(self) {
+        emit_event("funding_rescheduled", &[self])
+    }
+}
+
+/// Milestone failed once its deadline passed without an approved proposal.
+#[derive(Serialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct MilestoneFailed {
+    pub pool_id: PoolId,
+    pub milestone_index: u32,
+}
+
+impl MilestoneFailed {
+    pub fn emit(self) {
+        emit_event("milestone_failed", &[self])
+    }
+}
+
+/// Setting changed by an admin, or by the creator when `pool_id` is set.
+#[derive(Serialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct ConfigChanged<'a> {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pool_id: Option<PoolId>,
+    pub setting: &'a str,
+    pub value: serde_json::Value,
+}
+
+impl ConfigChanged<'_> {
+    pub fn emit(self) {
+        emit_event("config_changed", &[self])
+    }
+}
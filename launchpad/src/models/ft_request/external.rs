@@ -1,9 +1,22 @@
 use near_sdk::{ext_contract, json_types::U128, AccountId};
 
+use crate::models::{contract::TokenPledge, PoolId};
+
 #[ext_contract(cross_edu)]
 pub trait CrossCall {
     fn ft_transfer(&mut self, receiver_id: AccountId, amount: U128);
     fn storage_deposit(&mut self, account_id: AccountId);
     fn ft_transfer_call(&mut self, receiver_id: AccountId, amount: U128, msg: String);
     fn ft_balance_of(&mut self, account_id: AccountId);
+}
+
+#[ext_contract(ext_self)]
+pub trait LaunchpadResolver {
+    fn resolve_claim_refund(&mut self, pool_id: PoolId, user_id: AccountId, token_pledge: TokenPledge, amount: U128, value: U128, tier_index: Option<u32>);
+    fn resolve_withdraw_to_creator(&mut self, pool_id: PoolId, milestone_index: u32, token_id: AccountId, amount: U128, fee: U128, value: U128);
+    fn resolve_withdraw_pledge(&mut self, pool_id: PoolId, user_id: AccountId, token_pledge: TokenPledge, penalty: U128, value: U128, tier_index: Option<u32>);
+    fn resolve_withdraw_treasury(&mut self, receiver_id: AccountId, amount: U128);
+    fn resolve_withdraw_fees(&mut self, token_id: AccountId, receiver_id: AccountId, amount: U128);
+    fn resolve_claim_referral_reward(&mut self, pool_id: PoolId, referrer_id: AccountId, token_id: AccountId, amount: U128);
+    fn resolve_claim_excess(&mut self, pool_id: PoolId, user_id: AccountId, token_pledge: TokenPledge);
 }
\ No newline at end of file
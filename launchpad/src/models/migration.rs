@@ -0,0 +1,544 @@
+use near_sdk::borsh::{self, BorshDeserialize, BorshSerialize};
+use near_sdk::{
+    collections::{LookupMap, UnorderedSet, UnorderedMap},
+    AccountId,
+};
+
+use super::contract::{
+    Assets, Feature, FundingTerms, LaunchpadStorageKey, Milestone, OverfundingPolicy, PoolAccess, PoolFundingTerms, PoolMetadata, PoolToken, Proposal, ReferralStats, RewardTier, Role, Status, StretchGoal, TokenPledge, TreasuryLedger, UserTokenDepositRecord, Vote
+};
+use super::time::{Duration, Timestamp};
+use super::PoolId;
+
+/// State version 0: contract layout deployed before voting power became an integer share.
+#[derive(BorshDeserialize, BorshSerialize)]
+pub struct OldLaunchpad {
+    pub owner_id: AccountId,
+    pub all_pool_id: UnorderedSet<PoolId>,
+    pub list_assets: Vec<Assets>,
+    pub pool_metadata_by_id: LookupMap<PoolId, OldPoolMetadata>,
+    pub min_staking_amount: u128,
+    pub refund_percent: u8,
+    pub user_records: LookupMap<PoolId, UnorderedMap<AccountId, OldUserTokenDepositRecord>>,
+}
+
+#[derive(BorshDeserialize, BorshSerialize)]
+pub struct OldPoolMetadata {
+    pub pool_id: PoolId,
+    pub campaign_id: String,
+    pub creator_id: AccountId,
+    pub staking_amount: u128,
+    pub status: Status,
+    pub token_id: AccountId,
+    pub total_balance: u128,
+    pub target_funding: u128,
+    pub time_init: u64,
+    pub time_start_pledge: u64,
+    pub time_end_pledge: u64,
+    pub funding_duration_days: u64,
+    pub min_multiple_pledge: u128,
+}
+
+#[derive(BorshDeserialize, BorshSerialize)]
+pub struct OldUserTokenDepositRecord {
+    pub amount: u128,
+    pub voting_power: f64, // percentage of the pool
+}
+
+/// State version 1: integer voting power, milestones and the backer index, before roles.
+#[derive(BorshDeserialize, BorshSerialize)]
+pub struct LaunchpadV1 {
+    pub owner_id: AccountId,
+    pub all_pool_id: UnorderedSet<PoolId>,
+    pub list_assets: Vec<Assets>,
+    pub pool_metadata_by_id: LookupMap<PoolId, PoolMetadata>,
+    pub min_staking_amount: u128,
+    pub refund_percent: u8,
+    pub user_records: LookupMap<PoolId, UnorderedMap<AccountId, UserTokenDepositRecord>>,
+    pub voting_quorum_percent: u8,
+    pub voting_duration: u64,
+    pub proposal_by_pool_id: LookupMap<PoolId, Proposal>,
+    pub votes_by_pool_id: LookupMap<PoolId, UnorderedMap<AccountId, Vote>>,
+    pub milestones_by_pool_id: LookupMap<PoolId, Vec<Milestone>>,
+    pub pools_by_backer: LookupMap<AccountId, UnorderedSet<PoolId>>,
+}
+
+/// State version 2: roles and the two-step admin transfer, before the pause flags.
+#[derive(BorshDeserialize, BorshSerialize)]
+pub struct LaunchpadV2 {
+    pub owner_id: AccountId,
+    pub all_pool_id: UnorderedSet<PoolId>,
+    pub list_assets: Vec<Assets>,
+    pub pool_metadata_by_id: LookupMap<PoolId, PoolMetadata>,
+    pub min_staking_amount: u128,
+    pub refund_percent: u8,
+    pub user_records: LookupMap<PoolId, UnorderedMap<AccountId, UserTokenDepositRecord>>,
+    pub voting_quorum_percent: u8,
+    pub voting_duration: u64,
+    pub proposal_by_pool_id: LookupMap<PoolId, Proposal>,
+    pub votes_by_pool_id: LookupMap<PoolId, UnorderedMap<AccountId, Vote>>,
+    pub milestones_by_pool_id: LookupMap<PoolId, Vec<Milestone>>,
+    pub pools_by_backer: LookupMap<AccountId, UnorderedSet<PoolId>>,
+    pub pending_owner_id: Option<AccountId>,
+    pub role_members: LookupMap<Role, UnorderedSet<AccountId>>,
+}
+
+/// State versions 3 to 5: pause flags, before the keeper reward.
+#[derive(BorshDeserialize, BorshSerialize)]
+pub struct LaunchpadV5 {
+    pub owner_id: AccountId,
+    pub all_pool_id: UnorderedSet<PoolId>,
+    pub list_assets: Vec<Assets>,
+    pub pool_metadata_by_id: LookupMap<PoolId, PoolMetadata>,
+    pub min_staking_amount: u128,
+    pub refund_percent: u8,
+    pub user_records: LookupMap<PoolId, UnorderedMap<AccountId, UserTokenDepositRecord>>,
+    pub voting_quorum_percent: u8,
+    pub voting_duration: u64,
+    pub proposal_by_pool_id: LookupMap<PoolId, Proposal>,
+    pub votes_by_pool_id: LookupMap<PoolId, UnorderedMap<AccountId, Vote>>,
+    pub milestones_by_pool_id: LookupMap<PoolId, Vec<Milestone>>,
+    pub pools_by_backer: LookupMap<AccountId, UnorderedSet<PoolId>>,
+    pub pending_owner_id: Option<AccountId>,
+    pub role_members: LookupMap<Role, UnorderedSet<AccountId>>,
+    pub paused_features: Vec<Feature>,
+}
+
+/// State version 6: keeper reward, before the treasury ledger.
+#[derive(BorshDeserialize, BorshSerialize)]
+pub struct LaunchpadV6 {
+    pub owner_id: AccountId,
+    pub all_pool_id: UnorderedSet<PoolId>,
+    pub list_assets: Vec<Assets>,
+    pub pool_metadata_by_id: LookupMap<PoolId, PoolMetadata>,
+    pub min_staking_amount: u128,
+    pub refund_percent: u8,
+    pub user_records: LookupMap<PoolId, UnorderedMap<AccountId, UserTokenDepositRecord>>,
+    pub voting_quorum_percent: u8,
+    pub voting_duration: u64,
+    pub proposal_by_pool_id: LookupMap<PoolId, Proposal>,
+    pub votes_by_pool_id: LookupMap<PoolId, UnorderedMap<AccountId, Vote>>,
+    pub milestones_by_pool_id: LookupMap<PoolId, Vec<Milestone>>,
+    pub pools_by_backer: LookupMap<AccountId, UnorderedSet<PoolId>>,
+    pub pending_owner_id: Option<AccountId>,
+    pub role_members: LookupMap<Role, UnorderedSet<AccountId>>,
+    pub paused_features: Vec<Feature>,
+    pub keeper_reward: u128,
+}
+
+/// State version 7: treasury ledger, before the platform fee.
+#[derive(BorshDeserialize, BorshSerialize)]
+pub struct LaunchpadV7 {
+    pub owner_id: AccountId,
+    pub all_pool_id: UnorderedSet<PoolId>,
+    pub list_assets: Vec<Assets>,
+    pub pool_metadata_by_id: LookupMap<PoolId, PoolMetadata>,
+    pub min_staking_amount: u128,
+    pub refund_percent: u8,
+    pub user_records: LookupMap<PoolId, UnorderedMap<AccountId, UserTokenDepositRecord>>,
+    pub voting_quorum_percent: u8,
+    pub voting_duration: u64,
+    pub proposal_by_pool_id: LookupMap<PoolId, Proposal>,
+    pub votes_by_pool_id: LookupMap<PoolId, UnorderedMap<AccountId, Vote>>,
+    pub milestones_by_pool_id: LookupMap<PoolId, Vec<Milestone>>,
+    pub pools_by_backer: LookupMap<AccountId, UnorderedSet<PoolId>>,
+    pub pending_owner_id: Option<AccountId>,
+    pub role_members: LookupMap<Role, UnorderedSet<AccountId>>,
+    pub paused_features: Vec<Feature>,
+    pub keeper_reward: u128,
+    pub treasury: TreasuryLedger,
+    pub stake_slash_percent: u8,
+}
+
+/// State version 8: platform fee, before multi-token pools.
+#[derive(BorshDeserialize, BorshSerialize)]
+pub struct LaunchpadV8 {
+    pub owner_id: AccountId,
+    pub all_pool_id: UnorderedSet<PoolId>,
+    pub list_assets: Vec<Assets>,
+    pub pool_metadata_by_id: LookupMap<PoolId, PoolMetadata>,
+    pub min_staking_amount: u128,
+    pub refund_percent: u8,
+    pub user_records: LookupMap<PoolId, UnorderedMap<AccountId, UserTokenDepositRecord>>,
+    pub voting_quorum_percent: u8,
+    pub voting_duration: u64,
+    pub proposal_by_pool_id: LookupMap<PoolId, Proposal>,
+    pub votes_by_pool_id: LookupMap<PoolId, UnorderedMap<AccountId, Vote>>,
+    pub milestones_by_pool_id: LookupMap<PoolId, Vec<Milestone>>,
+    pub pools_by_backer: LookupMap<AccountId, UnorderedSet<PoolId>>,
+    pub pending_owner_id: Option<AccountId>,
+    pub role_members: LookupMap<Role, UnorderedSet<AccountId>>,
+    pub paused_features: Vec<Feature>,
+    pub keeper_reward: u128,
+    pub treasury: TreasuryLedger,
+    pub stake_slash_percent: u8,
+    pub platform_fee_bps: u16,
+    pub fee_bps_by_token: LookupMap<AccountId, u16>,
+    pub fee_collector_id: AccountId,
+}
+
+/// State version 9: multi-token pools, before reward tiers.
+#[derive(BorshDeserialize, BorshSerialize)]
+pub struct LaunchpadV9 {
+    pub owner_id: AccountId,
+    pub all_pool_id: UnorderedSet<PoolId>,
+    pub list_assets: Vec<Assets>,
+    pub pool_metadata_by_id: LookupMap<PoolId, PoolMetadata>,
+    pub min_staking_amount: u128,
+    pub refund_percent: u8,
+    pub user_records: LookupMap<PoolId, UnorderedMap<AccountId, UserTokenDepositRecord>>,
+    pub voting_quorum_percent: u8,
+    pub voting_duration: u64,
+    pub proposal_by_pool_id: LookupMap<PoolId, Proposal>,
+    pub votes_by_pool_id: LookupMap<PoolId, UnorderedMap<AccountId, Vote>>,
+    pub milestones_by_pool_id: LookupMap<PoolId, Vec<Milestone>>,
+    pub pools_by_backer: LookupMap<AccountId, UnorderedSet<PoolId>>,
+    pub pending_owner_id: Option<AccountId>,
+    pub role_members: LookupMap<Role, UnorderedSet<AccountId>>,
+    pub paused_features: Vec<Feature>,
+    pub keeper_reward: u128,
+    pub treasury: TreasuryLedger,
+    pub stake_slash_percent: u8,
+    pub platform_fee_bps: u16,
+    pub fee_bps_by_token: LookupMap<AccountId, u16>,
+    pub fee_collector_id: AccountId,
+    pub token_rates: LookupMap<AccountId, u128>,
+    pub token_pledges: LookupMap<(PoolId, AccountId), Vec<TokenPledge>>,
+}
+
+/// State version 10: reward tiers, before referrals.
+#[derive(BorshDeserialize, BorshSerialize)]
+pub struct LaunchpadV10 {
+    pub owner_id: AccountId,
+    pub all_pool_id: UnorderedSet<PoolId>,
+    pub list_assets: Vec<Assets>,
+    pub pool_metadata_by_id: LookupMap<PoolId, PoolMetadata>,
+    pub min_staking_amount: u128,
+    pub refund_percent: u8,
+    pub user_records: LookupMap<PoolId, UnorderedMap<AccountId, UserTokenDepositRecord>>,
+    pub voting_quorum_percent: u8,
+    pub voting_duration: u64,
+    pub proposal_by_pool_id: LookupMap<PoolId, Proposal>,
+    pub votes_by_pool_id: LookupMap<PoolId, UnorderedMap<AccountId, Vote>>,
+    pub milestones_by_pool_id: LookupMap<PoolId, Vec<Milestone>>,
+    pub pools_by_backer: LookupMap<AccountId, UnorderedSet<PoolId>>,
+    pub pending_owner_id: Option<AccountId>,
+    pub role_members: LookupMap<Role, UnorderedSet<AccountId>>,
+    pub paused_features: Vec<Feature>,
+    pub keeper_reward: u128,
+    pub treasury: TreasuryLedger,
+    pub stake_slash_percent: u8,
+    pub platform_fee_bps: u16,
+    pub fee_bps_by_token: LookupMap<AccountId, u16>,
+    pub fee_collector_id: AccountId,
+    pub token_rates: LookupMap<AccountId, u128>,
+    pub token_pledges: LookupMap<(PoolId, AccountId), Vec<TokenPledge>>,
+    pub reward_tiers_by_pool_id: LookupMap<PoolId, Vec<RewardTier>>,
+    pub backer_tiers: LookupMap<(PoolId, AccountId), u32>,
+}
+
+/// State version 11: referrals, before restricted pools.
+#[derive(BorshDeserialize, BorshSerialize)]
+pub struct LaunchpadV11 {
+    pub owner_id: AccountId,
+    pub all_pool_id: UnorderedSet<PoolId>,
+    pub list_assets: Vec<Assets>,
+    pub pool_metadata_by_id: LookupMap<PoolId, PoolMetadata>,
+    pub min_staking_amount: u128,
+    pub refund_percent: u8,
+    pub user_records: LookupMap<PoolId, UnorderedMap<AccountId, UserTokenDepositRecord>>,
+    pub voting_quorum_percent: u8,
+    pub voting_duration: u64,
+    pub proposal_by_pool_id: LookupMap<PoolId, Proposal>,
+    pub votes_by_pool_id: LookupMap<PoolId, UnorderedMap<AccountId, Vote>>,
+    pub milestones_by_pool_id: LookupMap<PoolId, Vec<Milestone>>,
+    pub pools_by_backer: LookupMap<AccountId, UnorderedSet<PoolId>>,
+    pub pending_owner_id: Option<AccountId>,
+    pub role_members: LookupMap<Role, UnorderedSet<AccountId>>,
+    pub paused_features: Vec<Feature>,
+    pub keeper_reward: u128,
+    pub treasury: TreasuryLedger,
+    pub stake_slash_percent: u8,
+    pub platform_fee_bps: u16,
+    pub fee_bps_by_token: LookupMap<AccountId, u16>,
+    pub fee_collector_id: AccountId,
+    pub token_rates: LookupMap<AccountId, u128>,
+    pub token_pledges: LookupMap<(PoolId, AccountId), Vec<TokenPledge>>,
+    pub reward_tiers_by_pool_id: LookupMap<PoolId, Vec<RewardTier>>,
+    pub backer_tiers: LookupMap<(PoolId, AccountId), u32>,
+    pub referral_reward_bps: u16,
+    pub referrals_by_pool_id: LookupMap<PoolId, UnorderedMap<AccountId, ReferralStats>>,
+    pub referrer_by_backer: LookupMap<(PoolId, AccountId), AccountId>,
+}
+
+/// State version 14: configurable funding terms, before referral reserves.
+#[derive(BorshDeserialize, BorshSerialize)]
+pub struct LaunchpadV14 {
+    pub owner_id: AccountId,
+    pub all_pool_id: UnorderedSet<PoolId>,
+    pub list_assets: Vec<Assets>,
+    pub pool_metadata_by_id: LookupMap<PoolId, PoolMetadata>,
+    pub min_staking_amount: u128,
+    pub refund_percent: u8,
+    pub user_records: LookupMap<PoolId, UnorderedMap<AccountId, UserTokenDepositRecord>>,
+    pub voting_quorum_percent: u8,
+    pub voting_duration: Duration,
+    pub proposal_by_pool_id: LookupMap<PoolId, Proposal>,
+    pub votes_by_pool_id: LookupMap<PoolId, UnorderedMap<AccountId, Vote>>,
+    pub milestones_by_pool_id: LookupMap<PoolId, Vec<Milestone>>,
+    pub pools_by_backer: LookupMap<AccountId, UnorderedSet<PoolId>>,
+    pub pending_owner_id: Option<AccountId>,
+    pub role_members: LookupMap<Role, UnorderedSet<AccountId>>,
+    pub paused_features: Vec<Feature>,
+    pub keeper_reward: u128,
+    pub treasury: TreasuryLedger,
+    pub stake_slash_percent: u8,
+    pub platform_fee_bps: u16,
+    pub fee_bps_by_token: LookupMap<AccountId, u16>,
+    pub fee_collector_id: AccountId,
+    pub token_rates: LookupMap<AccountId, u128>,
+    pub token_pledges: LookupMap<(PoolId, AccountId), Vec<TokenPledge>>,
+    pub reward_tiers_by_pool_id: LookupMap<PoolId, Vec<RewardTier>>,
+    pub backer_tiers: LookupMap<(PoolId, AccountId), u32>,
+    pub referral_reward_bps: u16,
+    pub referrals_by_pool_id: LookupMap<PoolId, UnorderedMap<AccountId, ReferralStats>>,
+    pub referrer_by_backer: LookupMap<(PoolId, AccountId), AccountId>,
+    pub pledge_signer_key: Option<String>,
+    pub access_by_pool_id: LookupMap<PoolId, PoolAccess>,
+    pub allowlist_by_pool_id: LookupMap<PoolId, UnorderedSet<AccountId>>,
+    pub stretch_goals_by_pool_id: LookupMap<PoolId, Vec<StretchGoal>>,
+    pub overfunding_by_pool_id: LookupMap<PoolId, OverfundingPolicy>,
+    pub excess_by_backer: LookupMap<(PoolId, AccountId), Vec<TokenPledge>>,
+    pub funding_terms: FundingTerms,
+    pub funding_terms_by_pool_id: LookupMap<PoolId, PoolFundingTerms>,
+}
+
+/// State version 13: stretch goals, before configurable funding terms.
+#[derive(BorshDeserialize, BorshSerialize)]
+pub struct LaunchpadV13 {
+    pub owner_id: AccountId,
+    pub all_pool_id: UnorderedSet<PoolId>,
+    pub list_assets: Vec<Assets>,
+    pub pool_metadata_by_id: LookupMap<PoolId, PoolMetadata>,
+    pub min_staking_amount: u128,
+    pub refund_percent: u8,
+    pub user_records: LookupMap<PoolId, UnorderedMap<AccountId, UserTokenDepositRecord>>,
+    pub voting_quorum_percent: u8,
+    pub voting_duration: u64,
+    pub proposal_by_pool_id: LookupMap<PoolId, Proposal>,
+    pub votes_by_pool_id: LookupMap<PoolId, UnorderedMap<AccountId, Vote>>,
+    pub milestones_by_pool_id: LookupMap<PoolId, Vec<Milestone>>,
+    pub pools_by_backer: LookupMap<AccountId, UnorderedSet<PoolId>>,
+    pub pending_owner_id: Option<AccountId>,
+    pub role_members: LookupMap<Role, UnorderedSet<AccountId>>,
+    pub paused_features: Vec<Feature>,
+    pub keeper_reward: u128,
+    pub treasury: TreasuryLedger,
+    pub stake_slash_percent: u8,
+    pub platform_fee_bps: u16,
+    pub fee_bps_by_token: LookupMap<AccountId, u16>,
+    pub fee_collector_id: AccountId,
+    pub token_rates: LookupMap<AccountId, u128>,
+    pub token_pledges: LookupMap<(PoolId, AccountId), Vec<TokenPledge>>,
+    pub reward_tiers_by_pool_id: LookupMap<PoolId, Vec<RewardTier>>,
+    pub backer_tiers: LookupMap<(PoolId, AccountId), u32>,
+    pub referral_reward_bps: u16,
+    pub referrals_by_pool_id: LookupMap<PoolId, UnorderedMap<AccountId, ReferralStats>>,
+    pub referrer_by_backer: LookupMap<(PoolId, AccountId), AccountId>,
+    pub pledge_signer_key: Option<String>,
+    pub access_by_pool_id: LookupMap<PoolId, PoolAccess>,
+    pub allowlist_by_pool_id: LookupMap<PoolId, UnorderedSet<AccountId>>,
+    pub stretch_goals_by_pool_id: LookupMap<PoolId, Vec<StretchGoal>>,
+    pub overfunding_by_pool_id: LookupMap<PoolId, OverfundingPolicy>,
+    pub excess_by_backer: LookupMap<(PoolId, AccountId), Vec<TokenPledge>>,
+}
+
+/// State version 12: restricted pools, before stretch goals.
+#[derive(BorshDeserialize, BorshSerialize)]
+pub struct LaunchpadV12 {
+    pub owner_id: AccountId,
+    pub all_pool_id: UnorderedSet<PoolId>,
+    pub list_assets: Vec<Assets>,
+    pub pool_metadata_by_id: LookupMap<PoolId, PoolMetadata>,
+    pub min_staking_amount: u128,
+    pub refund_percent: u8,
+    pub user_records: LookupMap<PoolId, UnorderedMap<AccountId, UserTokenDepositRecord>>,
+    pub voting_quorum_percent: u8,
+    pub voting_duration: u64,
+    pub proposal_by_pool_id: LookupMap<PoolId, Proposal>,
+    pub votes_by_pool_id: LookupMap<PoolId, UnorderedMap<AccountId, Vote>>,
+    pub milestones_by_pool_id: LookupMap<PoolId, Vec<Milestone>>,
+    pub pools_by_backer: LookupMap<AccountId, UnorderedSet<PoolId>>,
+    pub pending_owner_id: Option<AccountId>,
+    pub role_members: LookupMap<Role, UnorderedSet<AccountId>>,
+    pub paused_features: Vec<Feature>,
+    pub keeper_reward: u128,
+    pub treasury: TreasuryLedger,
+    pub stake_slash_percent: u8,
+    pub platform_fee_bps: u16,
+    pub fee_bps_by_token: LookupMap<AccountId, u16>,
+    pub fee_collector_id: AccountId,
+    pub token_rates: LookupMap<AccountId, u128>,
+    pub token_pledges: LookupMap<(PoolId, AccountId), Vec<TokenPledge>>,
+    pub reward_tiers_by_pool_id: LookupMap<PoolId, Vec<RewardTier>>,
+    pub backer_tiers: LookupMap<(PoolId, AccountId), u32>,
+    pub referral_reward_bps: u16,
+    pub referrals_by_pool_id: LookupMap<PoolId, UnorderedMap<AccountId, ReferralStats>>,
+    pub referrer_by_backer: LookupMap<(PoolId, AccountId), AccountId>,
+    pub pledge_signer_key: Option<String>,
+    pub access_by_pool_id: LookupMap<PoolId, PoolAccess>,
+    pub allowlist_by_pool_id: LookupMap<PoolId, UnorderedSet<AccountId>>,
+}
+
+/// Pool layout of state versions 1 to 3, before pledge limits.
+#[derive(BorshDeserialize, BorshSerialize)]
+pub struct PoolMetadataV3 {
+    pub pool_id: PoolId,
+    pub campaign_id: String,
+    pub creator_id: AccountId,
+    pub staking_amount: u128,
+    pub status: Status,
+    pub token_id: AccountId,
+    pub total_balance: u128,
+    pub target_funding: u128,
+    pub time_init: u64,
+    pub time_start_pledge: u64,
+    pub time_end_pledge: u64,
+    pub funding_duration_days: u64,
+    pub min_multiple_pledge: u128,
+    pub total_voting_power: u128,
+}
+
+/// Pool layout of state version 4, before pledge withdrawals.
+#[derive(BorshDeserialize, BorshSerialize)]
+pub struct PoolMetadataV4 {
+    pub pool_id: PoolId,
+    pub campaign_id: String,
+    pub creator_id: AccountId,
+    pub staking_amount: u128,
+    pub status: Status,
+    pub token_id: AccountId,
+    pub total_balance: u128,
+    pub target_funding: u128,
+    pub time_init: u64,
+    pub time_start_pledge: u64,
+    pub time_end_pledge: u64,
+    pub funding_duration_days: u64,
+    pub min_multiple_pledge: u128,
+    pub total_voting_power: u128,
+    pub min_pledge: u128,
+    pub max_pledge: u128,
+    pub hard_cap: u128,
+}
+
+/// Pool layout of state versions 5 to 8, before multi-token pools.
+#[derive(BorshDeserialize, BorshSerialize)]
+pub struct PoolMetadataV8 {
+    pub pool_id: PoolId,
+    pub campaign_id: String,
+    pub creator_id: AccountId,
+    pub staking_amount: u128,
+    pub status: Status,
+    pub token_id: AccountId,
+    pub total_balance: u128,
+    pub target_funding: u128,
+    pub time_init: u64,
+    pub time_start_pledge: u64,
+    pub time_end_pledge: u64,
+    pub funding_duration_days: u64,
+    pub min_multiple_pledge: u128,
+    pub total_voting_power: u128,
+    pub min_pledge: u128,
+    pub max_pledge: u128,
+    pub hard_cap: u128,
+    pub withdraw_penalty_percent: u8,
+}
+
+// pools stored before pledge limits existed get no limits, so they keep accepting pledges up to their target
+impl From<PoolMetadataV3> for PoolMetadataV4 {
+    fn from(pool: PoolMetadataV3) -> Self {
+        Self {
+            pool_id: pool.pool_id,
+            campaign_id: pool.campaign_id,
+            creator_id: pool.creator_id,
+            staking_amount: pool.staking_amount,
+            status: pool.status,
+            token_id: pool.token_id,
+            total_balance: pool.total_balance,
+            target_funding: pool.target_funding,
+            time_init: pool.time_init,
+            time_start_pledge: pool.time_start_pledge,
+            time_end_pledge: pool.time_end_pledge,
+            funding_duration_days: pool.funding_duration_days,
+            min_multiple_pledge: pool.min_multiple_pledge,
+            total_voting_power: pool.total_voting_power,
+            min_pledge: 0,
+            max_pledge: 0,
+            hard_cap: 0,
+        }
+    }
+}
+
+// pledges of pools stored before withdrawals existed can be withdrawn without penalty
+impl From<PoolMetadataV4> for PoolMetadataV8 {
+    fn from(pool: PoolMetadataV4) -> Self {
+        Self {
+            pool_id: pool.pool_id,
+            campaign_id: pool.campaign_id,
+            creator_id: pool.creator_id,
+            staking_amount: pool.staking_amount,
+            status: pool.status,
+            token_id: pool.token_id,
+            total_balance: pool.total_balance,
+            target_funding: pool.target_funding,
+            time_init: pool.time_init,
+            time_start_pledge: pool.time_start_pledge,
+            time_end_pledge: pool.time_end_pledge,
+            funding_duration_days: pool.funding_duration_days,
+            min_multiple_pledge: pool.min_multiple_pledge,
+            total_voting_power: pool.total_voting_power,
+            min_pledge: pool.min_pledge,
+            max_pledge: pool.max_pledge,
+            hard_cap: pool.hard_cap,
+            withdraw_penalty_percent: 0,
+        }
+    }
+}
+
+// single-token pools hold all their balance in `token_id`, every record was pledged in it at 1:1;
+// the outstanding pledges are summed from the backer records by migrate_pools
+impl From<PoolMetadataV8> for PoolMetadata {
+    fn from(pool: PoolMetadataV8) -> Self {
+        Self {
+            pool_id: pool.pool_id,
+            campaign_id: pool.campaign_id,
+            creator_id: pool.creator_id,
+            staking_amount: pool.staking_amount,
+            status: pool.status,
+            tokens: vec![PoolToken {
+                token_id: pool.token_id.clone(),
+                balance: pool.total_balance,
+                pledged: 0,
+            }],
+            token_id: pool.token_id,
+            total_balance: pool.total_balance,
+            target_funding: pool.target_funding,
+            time_init: Timestamp::from_nanos(pool.time_init),
+            time_start_pledge: Timestamp::from_nanos(pool.time_start_pledge),
+            time_end_pledge: Timestamp::from_nanos(pool.time_end_pledge),
+            funding_duration_days: pool.funding_duration_days,
+            min_multiple_pledge: pool.min_multiple_pledge,
+            total_voting_power: pool.total_voting_power,
+            min_pledge: pool.min_pledge,
+            max_pledge: pool.max_pledge,
+            hard_cap: pool.hard_cap,
+            withdraw_penalty_percent: pool.withdraw_penalty_percent,
+        }
+    }
+}
+
+// pool as stored in an older layout under the current pool map prefix
+pub fn read_old_pool<T: BorshDeserialize + BorshSerialize>(pool_id: PoolId) -> Option<T> {
+    let old_pools: LookupMap<PoolId, T> = LookupMap::new(LaunchpadStorageKey::PoolMetadataById.try_to_vec().unwrap());
+    old_pools.get(&pool_id)
+}
@@ -1,4 +1,7 @@
 pub mod contract;
+pub mod events;
 pub mod ft_request;
+pub mod migration;
+pub mod time;
 
 pub type PoolId = u64;
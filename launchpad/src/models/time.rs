@@ -0,0 +1,112 @@
+//! Nanosecond timestamps and durations, the unit of `env::block_timestamp`.
+//! Both are stored and serialized as the bare `u64` they wrap.
+use std::fmt;
+use std::ops::{Add, AddAssign, Sub};
+
+use near_sdk::borsh::{self, BorshDeserialize, BorshSerialize};
+use near_sdk::env;
+use near_sdk::serde::{Deserialize, Serialize};
+
+const NANOS_PER_SECOND: u64 = 1_000_000_000;
+const NANOS_PER_HOUR: u64 = 60 * 60 * NANOS_PER_SECOND;
+const NANOS_PER_DAY: u64 = 24 * NANOS_PER_HOUR;
+
+/// Point in time in nanoseconds since the Unix epoch.
+#[derive(BorshDeserialize, BorshSerialize, Deserialize, Serialize, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Debug, Default)]
+#[serde(crate = "near_sdk::serde", transparent)]
+pub struct Timestamp(pub u64);
+
+impl Timestamp {
+    /// Timestamp of the block being executed.
+    pub fn now() -> Self {
+        Self(env::block_timestamp())
+    }
+
+    pub const fn from_nanos(nanos: u64) -> Self {
+        Self(nanos)
+    }
+
+    pub const fn as_nanos(self) -> u64 {
+        self.0
+    }
+
+    /// Unset timestamps are 0, e.g. a milestone without deadline or a pool never scheduled.
+    pub const fn is_set(self) -> bool {
+        self.0 != 0
+    }
+
+    /// Time elapsed from `earlier` to this timestamp, zero if `earlier` is later.
+    pub fn saturating_since(self, earlier: Timestamp) -> Duration {
+        Duration(self.0.saturating_sub(earlier.0))
+    }
+}
+
+impl Add<Duration> for Timestamp {
+    type Output = Timestamp;
+
+    fn add(self, duration: Duration) -> Timestamp {
+        Timestamp(self.0.checked_add(duration.0).unwrap_or_else(|| env::panic_str("Timestamp overflow")))
+    }
+}
+
+impl AddAssign<Duration> for Timestamp {
+    fn add_assign(&mut self, duration: Duration) {
+        *self = *self + duration;
+    }
+}
+
+impl Sub for Timestamp {
+    type Output = Duration;
+
+    fn sub(self, earlier: Timestamp) -> Duration {
+        Duration(self.0.checked_sub(earlier.0).unwrap_or_else(|| env::panic_str("Timestamp underflow")))
+    }
+}
+
+impl fmt::Display for Timestamp {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+/// Span of time in nanoseconds.
+#[derive(BorshDeserialize, BorshSerialize, Deserialize, Serialize, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Debug, Default)]
+#[serde(crate = "near_sdk::serde", transparent)]
+pub struct Duration(pub u64);
+
+impl Duration {
+    pub const fn from_nanos(nanos: u64) -> Self {
+        Self(nanos)
+    }
+
+    pub const fn from_secs(secs: u64) -> Self {
+        Self(secs * NANOS_PER_SECOND)
+    }
+
+    pub const fn from_hours(hours: u64) -> Self {
+        Self(hours * NANOS_PER_HOUR)
+    }
+
+    pub const fn from_days(days: u64) -> Self {
+        Self(days * NANOS_PER_DAY)
+    }
+
+    pub const fn as_nanos(self) -> u64 {
+        self.0
+    }
+
+    /// Whole days in the span, rounded down.
+    pub const fn whole_days(self) -> u64 {
+        self.0 / NANOS_PER_DAY
+    }
+
+    pub const fn is_zero(self) -> bool {
+        self.0 == 0
+    }
+}
+
+impl fmt::Display for Duration {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} ns", self.0)
+    }
+}
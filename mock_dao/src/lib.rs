@@ -0,0 +1,94 @@
+// MINIMAL DAO USED BY THE INTEGRATION TESTS
+// Council members propose a function call, the DAO account executes it once every member approved,
+// like a function call proposal of a Sputnik DAO.
+use near_sdk::borsh::{self, BorshDeserialize, BorshSerialize};
+use near_sdk::collections::LookupMap;
+use near_sdk::json_types::{Base64VecU8, U128, U64};
+use near_sdk::serde::{Deserialize, Serialize};
+use near_sdk::{env, near_bindgen, AccountId, Gas, PanicOnDefault, Promise};
+
+#[near_bindgen]
+#[derive(BorshDeserialize, BorshSerialize, PanicOnDefault)]
+pub struct MockDao {
+    council: Vec<AccountId>,
+    proposals: LookupMap<u64, Proposal>,
+    last_proposal_id: u64,
+}
+
+#[derive(BorshDeserialize, BorshSerialize, Deserialize, Serialize, Clone)]
+#[serde(crate = "near_sdk::serde")]
+pub struct Proposal {
+    pub receiver_id: AccountId,
+    pub method_name: String,
+    pub args: Base64VecU8,
+    pub deposit: U128,
+    pub gas: U64,
+    pub approvals: Vec<AccountId>,
+    pub executed: bool,
+}
+
+#[near_bindgen]
+impl MockDao {
+    #[init]
+    pub fn new(council: Vec<AccountId>) -> Self {
+        assert!(!council.is_empty(), "Council cannot be empty");
+
+        Self {
+            council,
+            proposals: LookupMap::new(b"p".to_vec()),
+            last_proposal_id: 0,
+        }
+    }
+
+    // council member proposes a function call and approves it
+    pub fn add_proposal(&mut self, receiver_id: AccountId, method_name: String, args: Base64VecU8, deposit: U128, gas: U64) -> u64 {
+        self.assert_council();
+
+        self.last_proposal_id += 1;
+        self.proposals.insert(&self.last_proposal_id, &Proposal {
+            receiver_id,
+            method_name,
+            args,
+            deposit,
+            gas,
+            approvals: Vec::new(),
+            executed: false,
+        });
+        self.approve(self.last_proposal_id);
+
+        self.last_proposal_id
+    }
+
+    // council member approves a proposal, the last approval executes it from the DAO account
+    pub fn approve(&mut self, proposal_id: u64) {
+        self.assert_council();
+
+        let mut proposal = self.proposals.get(&proposal_id).expect("Proposal does not exist");
+        assert!(!proposal.executed, "Proposal already executed");
+
+        let member_id = env::predecessor_account_id();
+        if !proposal.approvals.contains(&member_id) {
+            proposal.approvals.push(member_id);
+        }
+
+        if proposal.approvals.len() == self.council.len() {
+            proposal.executed = true;
+            Promise::new(proposal.receiver_id.clone()).function_call(
+                proposal.method_name.clone(),
+                proposal.args.0.clone(),
+                proposal.deposit.0,
+                Gas(proposal.gas.0),
+            );
+        }
+
+        self.proposals.insert(&proposal_id, &proposal);
+    }
+
+    pub fn get_proposal(&self, proposal_id: u64) -> Option<Proposal> {
+        self.proposals.get(&proposal_id)
+    }
+
+    fn assert_council(&self) {
+        assert!(self.council.contains(&env::predecessor_account_id()), "Only council members can act");
+    }
+}
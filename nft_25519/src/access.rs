@@ -0,0 +1,165 @@
+// ROLES, TWO-STEP OWNERSHIP TRANSFER AND PAUSING OF THE CERTIFICATE CONTRACT
+use near_sdk::borsh::{self, BorshDeserialize, BorshSerialize};
+use near_sdk::collections::UnorderedSet;
+use near_sdk::serde::{Deserialize, Serialize};
+use near_sdk::{env, near_bindgen, AccountId};
+
+use crate::events::{AdminTransfer, MintingPause, RoleChanged};
+use crate::{Contract, ContractExt};
+
+/// Roles the NFT owner can grant. The NFT owner implicitly holds every role.
+#[derive(BorshDeserialize, BorshSerialize, Deserialize, Serialize, Clone, PartialEq, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub enum Role {
+    Minter, // mints certificates with `nft_mint` and `nft_mint_for_sponsor`
+    Pauser, // pauses minting in an emergency
+}
+
+impl Role {
+    pub const ALL: [Role; 2] = [Role::Minter, Role::Pauser];
+
+    fn members_prefix(&self) -> Vec<u8> {
+        let mut prefix = Vec::with_capacity(4 + 1);
+        prefix.extend_from_slice(b"role");
+        prefix.extend_from_slice(&self.try_to_vec().unwrap());
+        prefix
+    }
+}
+
+#[near_bindgen]
+impl Contract {
+    /// Grants a role to an account. Only the NFT owner can grant roles.
+    pub fn grant_role(&mut self, role: Role, account_id: AccountId) {
+        self.assert_owner();
+
+        let mut members = self.role_members.get(&role)
+            .unwrap_or_else(|| UnorderedSet::new(role.members_prefix()));
+
+        assert!(members.insert(&account_id), "Account already has this role.");
+        self.role_members.insert(&role, &members);
+
+        RoleChanged {
+            role: &role,
+            account_id: &account_id,
+        }
+        .emit_granted();
+    }
+
+    /// Revokes a role from an account. Only the NFT owner can revoke roles.
+    pub fn revoke_role(&mut self, role: Role, account_id: AccountId) {
+        self.assert_owner();
+
+        let mut members = self.role_members.get(&role)
+            .expect("Account does not have this role.");
+
+        assert!(members.remove(&account_id), "Account does not have this role.");
+        self.role_members.insert(&role, &members);
+
+        RoleChanged {
+            role: &role,
+            account_id: &account_id,
+        }
+        .emit_revoked();
+    }
+
+    /// Proposes a new NFT owner, the ownership moves once it calls `accept_admin`.
+    pub fn change_admin(&mut self, new_admin: AccountId) {
+        self.assert_owner();
+
+        AdminTransfer {
+            old_owner_id: &self.tokens.owner_id,
+            new_owner_id: &new_admin,
+        }
+        .emit_proposed();
+        self.pending_owner_id = Some(new_admin);
+    }
+
+    /// Called by the proposed NFT owner to take over the ownership.
+    pub fn accept_admin(&mut self) {
+        let caller_id = env::predecessor_account_id();
+
+        assert_eq!(self.pending_owner_id.as_ref(), Some(&caller_id), "Only the proposed admin can accept the ownership.");
+
+        let old_owner_id = std::mem::replace(&mut self.tokens.owner_id, caller_id);
+        self.pending_owner_id = None;
+
+        AdminTransfer {
+            old_owner_id: &old_owner_id,
+            new_owner_id: &self.tokens.owner_id,
+        }
+        .emit_accepted();
+    }
+
+    /// Halts every mint method. Only a pauser can pause minting.
+    pub fn pause_minting(&mut self) {
+        self.assert_role(Role::Pauser);
+        self.minting_paused = true;
+
+        MintingPause {
+            account_id: &env::predecessor_account_id(),
+        }
+        .emit_paused();
+    }
+
+    /// Resumes minting. Only a pauser can unpause minting.
+    pub fn unpause_minting(&mut self) {
+        self.assert_role(Role::Pauser);
+        self.minting_paused = false;
+
+        MintingPause {
+            account_id: &env::predecessor_account_id(),
+        }
+        .emit_unpaused();
+    }
+
+    pub fn is_minting_paused(&self) -> bool {
+        self.minting_paused
+    }
+
+    pub fn has_role(&self, role: Role, account_id: AccountId) -> bool {
+        self.internal_has_role(&role, &account_id)
+    }
+
+    pub fn get_role_members(&self, role: Role) -> Vec<AccountId> {
+        self.role_members.get(&role).map_or_else(Vec::new, |members| members.to_vec())
+    }
+
+    /// Roles held by the account, the NFT owner holds all of them implicitly.
+    pub fn get_roles(&self, account_id: AccountId) -> Vec<Role> {
+        Role::ALL
+            .into_iter()
+            .filter(|role| self.internal_has_role(role, &account_id))
+            .collect()
+    }
+
+    pub fn get_owner(&self) -> AccountId {
+        self.tokens.owner_id.clone()
+    }
+
+    pub fn get_pending_owner(&self) -> Option<AccountId> {
+        self.pending_owner_id.clone()
+    }
+}
+
+impl Contract {
+    pub(crate) fn internal_has_role(&self, role: &Role, account_id: &AccountId) -> bool {
+        account_id == &self.tokens.owner_id
+            || self.role_members.get(role).is_some_and(|members| members.contains(account_id))
+    }
+
+    pub(crate) fn assert_owner(&self) {
+        assert_eq!(env::predecessor_account_id(), self.tokens.owner_id, "Only the NFT owner can call this method.");
+    }
+
+    pub(crate) fn assert_role(&self, role: Role) {
+        assert!(
+            self.internal_has_role(&role, &env::predecessor_account_id()),
+            "Caller does not have the {:?} role.",
+            role
+        );
+    }
+
+    pub(crate) fn assert_minting_not_paused(&self) {
+        assert!(!self.minting_paused, "Minting is paused.");
+    }
+}
@@ -0,0 +1,138 @@
+//! NEP-297 events emitted by the certificate contract, logged as `EVENT_JSON:{...}`.
+use near_sdk::serde::Serialize;
+use near_sdk::{env, json_types::U128, serde_json, AccountId};
+
+use crate::access::Role;
+
+pub const EVENT_STANDARD: &str = "openedu_certificate";
+pub const EVENT_STANDARD_VERSION: &str = "1.0.0";
+
+#[derive(Serialize)]
+#[serde(crate = "near_sdk::serde")]
+struct EventLog<'a, T: Serialize> {
+    standard: &'static str,
+    version: &'static str,
+    event: &'a str,
+    data: &'a [T],
+}
+
+fn emit_event<T: Serialize>(event: &str, data: &[T]) {
+    let log = EventLog {
+        standard: EVENT_STANDARD,
+        version: EVENT_STANDARD_VERSION,
+        event,
+        data,
+    };
+    env::log_str(&format!("EVENT_JSON:{}", serde_json::to_string(&log).unwrap()));
+}
+
+/// Emitted next to the NEP-171 `nft_mint` event to link a certificate to its course.
+#[derive(Serialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct CertificateMint<'a> {
+    pub token_id: &'a str,
+    pub receiver_id: &'a AccountId,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub course_id: Option<&'a str>,
+    /// Storage and gas cost charged to the course sponsor, if any.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sponsor_charge: Option<U128>,
+}
+
+impl CertificateMint<'_> {
+    pub fn emit(self) {
+        emit_event("certificate_mint", &[self])
+    }
+}
+
+#[derive(Serialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct SponsorDeposit<'a> {
+    pub sponsor_id: &'a AccountId,
+    pub course_id: &'a str,
+    pub amount: U128,
+}
+
+impl SponsorDeposit<'_> {
+    pub fn emit(self) {
+        emit_event("sponsor_deposit", &[self])
+    }
+}
+
+#[derive(Serialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct SponsorWithdraw<'a> {
+    pub sponsor_id: &'a AccountId,
+    pub course_id: &'a str,
+    pub amount: U128,
+}
+
+impl SponsorWithdraw<'_> {
+    pub fn emit(self) {
+        emit_event("sponsor_withdraw", &[self])
+    }
+}
+
+#[derive(Serialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct RoleChanged<'a> {
+    pub role: &'a Role,
+    pub account_id: &'a AccountId,
+}
+
+impl RoleChanged<'_> {
+    pub fn emit_granted(self) {
+        emit_event("role_granted", &[self])
+    }
+
+    pub fn emit_revoked(self) {
+        emit_event("role_revoked", &[self])
+    }
+}
+
+#[derive(Serialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct AdminTransfer<'a> {
+    pub old_owner_id: &'a AccountId,
+    pub new_owner_id: &'a AccountId,
+}
+
+impl AdminTransfer<'_> {
+    pub fn emit_proposed(self) {
+        emit_event("admin_transfer_proposed", &[self])
+    }
+
+    pub fn emit_accepted(self) {
+        emit_event("admin_transfer_accepted", &[self])
+    }
+}
+
+#[derive(Serialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct MintingPause<'a> {
+    pub account_id: &'a AccountId,
+}
+
+impl MintingPause<'_> {
+    pub fn emit_paused(self) {
+        emit_event("minting_paused", &[self])
+    }
+
+    pub fn emit_unpaused(self) {
+        emit_event("minting_unpaused", &[self])
+    }
+}
+
+/// Public key verifying the signed mint messages replaced by the NFT owner.
+#[derive(Serialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct AdminKeyChanged<'a> {
+    pub public_key: &'a str,
+    pub account_id: &'a AccountId,
+}
+
+impl AdminKeyChanged<'_> {
+    pub fn emit(self) {
+        emit_event("admin_key_changed", &[self])
+    }
+}
@@ -8,14 +8,27 @@ use near_contract_standards::non_fungible_token::NonFungibleToken;
 use near_sdk::borsh::{self, BorshDeserialize, BorshSerialize};
 use near_sdk::collections::{LazyOption, LookupMap, UnorderedSet};
 use near_sdk::{
-    env, near_bindgen, AccountId, BorshStorageKey, PanicOnDefault, Promise, log, serde::{Deserialize, Serialize},
+    env, near_bindgen, AccountId, BorshStorageKey, Gas, PanicOnDefault, Promise, serde::{Deserialize, Serialize},
 };
 
 use ed25519_dalek::{PublicKey, Signature, Verifier};
 use near_sdk::base64::decode;
+use near_sdk::json_types::U128;
+
+mod access;
+mod events;
+mod migration;
+use access::Role;
+use events::{AdminKeyChanged, CertificateMint, SponsorDeposit, SponsorWithdraw};
+use migration::{ContractV1, ContractV2};
 
 pub type CourseId = String;
 
+pub const STATE_VERSION: u8 = 3; // bump together with a migration arm in `migrate`
+const STATE_VERSION_KEY: &[u8] = b"STATE_VERSION";
+// gas kept by `upgrade` itself, the rest of the prepaid gas goes to `migrate`
+const GAS_FOR_UPGRADE: Gas = Gas(10_000_000_000_000);
+
 #[near_bindgen]
 #[derive(BorshDeserialize, BorshSerialize, PanicOnDefault)]
 pub struct Contract {
@@ -25,6 +38,11 @@ pub struct Contract {
     course_metadata_by_id: LookupMap<CourseId, CourseMetadata>,
     total_balances: u128,
     all_msg_25519: UnorderedSet<String>,
+    /// Account proposed by `change_admin`, becomes the NFT owner once it calls `accept_admin`.
+    pending_owner_id: Option<AccountId>,
+    role_members: LookupMap<Role, UnorderedSet<AccountId>>,
+    /// Set by a pauser to halt every mint method.
+    minting_paused: bool,
 }
 
 #[derive(BorshDeserialize, BorshSerialize, Deserialize, Serialize, Clone)]
@@ -40,6 +58,7 @@ pub enum CourseStorageKey {
     CourseById,
     AllCourseId,
     AllMsg25519,
+    RoleMembers,
 }
 
 const DATA_IMAGE_SVG_NEAR_ICON: &str = "data:image/svg+xml,%3Csvg xmlns='http://www.w3.org/2000/svg' viewBox='0 0 288 288'%3E%3Cg id='l' data-name='l'%3E%3Cpath d='M187.58,79.81l-30.1,44.69a3.2,3.2,0,0,0,4.75,4.2L191.86,103a1.2,1.2,0,0,1,2,.91v80.46a1.2,1.2,0,0,1-2.12.77L102.18,77.93A15.35,15.35,0,0,0,90.47,72.5H87.34A15.34,15.34,0,0,0,72,87.84V201.16A15.34,15.34,0,0,0,87.34,216.5h0a15.35,15.35,0,0,0,13.08-7.31l30.1-44.69a3.2,3.2,0,0,0-4.75-4.2L96.14,186a1.2,1.2,0,0,1-2-.91V104.61a1.2,1.2,0,0,1,2.12-.77l89.55,107.23a15.35,15.35,0,0,0,11.71,5.43h3.13A15.34,15.34,0,0,0,216,201.16V87.84A15.34,15.34,0,0,0,200.66,72.5h0A15.35,15.35,0,0,0,187.58,79.81Z'/%3E%3C/g%3E%3C/svg%3E";
@@ -78,6 +97,7 @@ impl Contract {
     pub fn new(owner_id: AccountId, metadata: NFTContractMetadata, admin_pub_key: String) -> Self {
         assert!(!env::state_exists(), "Already initialized");
         metadata.assert_valid();
+        write_state_version();
         Self {
             tokens: NonFungibleToken::new(
                 StorageKey::NonFungibleToken,
@@ -90,7 +110,10 @@ impl Contract {
             admin_pub_key,
             course_metadata_by_id: LookupMap::new(CourseStorageKey::CourseById.try_to_vec().unwrap()),
             total_balances: 0,
-            all_msg_25519: UnorderedSet::new(CourseStorageKey::AllMsg25519.try_to_vec().unwrap())
+            all_msg_25519: UnorderedSet::new(CourseStorageKey::AllMsg25519.try_to_vec().unwrap()),
+            pending_owner_id: None,
+            role_members: LookupMap::new(CourseStorageKey::RoleMembers.try_to_vec().unwrap()),
+            minting_paused: false,
         }
     }
 
@@ -111,6 +134,8 @@ impl Contract {
         signature_base64: String,
         course_id: String,
     ) -> Token {
+        self.assert_minting_not_paused();
+
         let user_address = env::predecessor_account_id();
 
         let signature_bytes = decode(&signature_base64).expect("Invalid signature (Base64 decode error)");
@@ -135,7 +160,17 @@ impl Contract {
             self.all_msg_25519.insert(&expected_message);
         }
 
-        self.tokens.internal_mint(token_id, receiver_id, Some(token_metadata))
+        let token = self.tokens.internal_mint(token_id, receiver_id, Some(token_metadata));
+
+        CertificateMint {
+            token_id: &token.token_id,
+            receiver_id: &token.owner_id,
+            course_id: Some(&course_id),
+            sponsor_charge: None,
+        }
+        .emit();
+
+        token
     }
 
     #[payable]
@@ -147,11 +182,8 @@ impl Contract {
         course_id: CourseId
     ) -> Token {
         
-        assert_eq!(
-            env::predecessor_account_id(),
-            self.tokens.owner_id,
-            "Unauthorized"
-        );
+        self.assert_role(Role::Minter);
+        self.assert_minting_not_paused();
 
         let mut course_metadata = self.course_metadata_by_id.get(&course_id)
             .expect("Course metadata not found");
@@ -186,16 +218,13 @@ impl Contract {
         // Update the course metadata with the new balance
         self.course_metadata_by_id.insert(&course_id, &course_metadata);
 
-        // Log transaction details
-        log!(
-            "NFT Mint Transaction Details:\n\
-            - Storage Cost: {} yoctoNEAR\n\
-            - Gas Cost: {} yoctoNEAR\n\
-            - Total Gas Cost: {} yoctoNEAR",
-            storage_cost,
-            gas_cost,
-            total_gas_cost
-        );
+        CertificateMint {
+            token_id: &token.token_id,
+            receiver_id: &token.owner_id,
+            course_id: Some(&course_id),
+            sponsor_charge: Some(U128(total_gas_cost)),
+        }
+        .emit();
 
         token
     }
@@ -207,13 +236,20 @@ impl Contract {
         receiver_id: AccountId,
         token_metadata: TokenMetadata,
     ) -> Token {
-        assert_eq!(
-            env::predecessor_account_id(),
-            self.tokens.owner_id,
-            "Unauthorized"
-        );
-        self.tokens
-            .internal_mint(token_id, receiver_id, Some(token_metadata))
+        self.assert_role(Role::Minter);
+        self.assert_minting_not_paused();
+        let token = self.tokens
+            .internal_mint(token_id, receiver_id, Some(token_metadata));
+
+        CertificateMint {
+            token_id: &token.token_id,
+            receiver_id: &token.owner_id,
+            course_id: None,
+            sponsor_charge: None,
+        }
+        .emit();
+
+        token
     }
 
     // Ensure the function is payable to allow NEAR deposits
@@ -248,13 +284,12 @@ impl Contract {
         // Update the record in the map
         self.course_metadata_by_id.insert(&course_id, &record);
 
-        // Log the deposit action
-        log!(
-            "Sponsor {} deposited {} yoctoNEAR for course {}",
-            sponsor_id,
-            deposit_amount,
-            course_id
-        );
+        SponsorDeposit {
+            sponsor_id: &sponsor_id,
+            course_id: &course_id,
+            amount: U128(deposit_amount),
+        }
+        .emit();
     }
 
     #[payable]
@@ -278,13 +313,12 @@ impl Contract {
         // Transfer the specified amount of NEAR back to the sponsor
         Promise::new(sponsor_id.clone()).transfer(amount);
 
-        // Log the withdrawal action
-        log!(
-            "Sponsor {} withdrew {} yoctoNEAR from course {}",
-            sponsor_id,
-            amount,
-            course_id
-        );
+        SponsorWithdraw {
+            sponsor_id: &sponsor_id,
+            course_id: &course_id,
+            amount: U128(amount),
+        }
+        .emit();
     }
 
     pub fn change_admin_pubkey(&mut self, new_pubkey: String) {
@@ -293,8 +327,12 @@ impl Contract {
 
         assert_eq!(caller_id, owner_id, "Only the NFT owner can change the admin public key.");
 
-        self.admin_pub_key = new_pubkey.clone();
-        log!("Admin public key changed to {}", new_pubkey);
+        AdminKeyChanged {
+            public_key: &new_pubkey,
+            account_id: &caller_id,
+        }
+        .emit();
+        self.admin_pub_key = new_pubkey;
     }
 
     pub fn get_sponsor_balance(&self, course_id: CourseId, sponsor_id: AccountId) -> Option<u128> {
@@ -305,6 +343,55 @@ impl Contract {
         }
         None
     }
+
+    /// Deploys the wasm passed as the raw call input and migrates the state in the same batch,
+    /// a failing migration reverts the deployment. Only the NFT owner can upgrade.
+    pub fn upgrade(&self) -> Promise {
+        self.assert_owner();
+
+        let code = env::input().expect("Missing contract code");
+        let migrate_gas = env::prepaid_gas() - env::used_gas() - GAS_FOR_UPGRADE;
+
+        Promise::new(env::current_account_id())
+            .deploy_contract(code)
+            .function_call("migrate".to_string(), Vec::new(), 0, migrate_gas)
+    }
+
+    /// Brings the stored state up to STATE_VERSION, a no-op rewrite when only the code changed.
+    #[private]
+    #[init(ignore_state)]
+    pub fn migrate() -> Self {
+        let contract = match read_state_version() {
+            // version 0 only lacked the version tag, its layout is the one of version 1
+            0 | 1 => {
+                let old: ContractV1 = env::state_read().expect("Failed to read old state");
+                old.into_current()
+            }
+            2 => {
+                let old: ContractV2 = env::state_read().expect("Failed to read old state");
+                old.into_current()
+            }
+            STATE_VERSION => env::state_read().expect("Failed to read state"),
+            version => env::panic_str(&format!("Unknown state version {}", version)),
+        };
+
+        write_state_version();
+        contract
+    }
+
+    /// Layout version of the stored state.
+    pub fn get_state_version(&self) -> u8 {
+        read_state_version()
+    }
+}
+
+// state written before versioning was introduced is version 0
+fn read_state_version() -> u8 {
+    env::storage_read(STATE_VERSION_KEY).map_or(0, |version| version[0])
+}
+
+fn write_state_version() {
+    env::storage_write(STATE_VERSION_KEY, &[STATE_VERSION]);
 }
 
 near_contract_standards::impl_non_fungible_token_approval!(Contract, tokens);
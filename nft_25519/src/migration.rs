@@ -0,0 +1,67 @@
+// STORED LAYOUTS OF PREVIOUS STATE VERSIONS
+use near_contract_standards::non_fungible_token::metadata::NFTContractMetadata;
+use near_contract_standards::non_fungible_token::NonFungibleToken;
+use near_sdk::borsh::{self, BorshDeserialize, BorshSerialize};
+use near_sdk::collections::{LazyOption, LookupMap, UnorderedSet};
+use near_sdk::AccountId;
+
+use crate::access::Role;
+use crate::{Contract, CourseId, CourseMetadata, CourseStorageKey};
+
+/// State versions 0 and 1: single NFT owner, before roles.
+#[derive(BorshDeserialize, BorshSerialize)]
+pub struct ContractV1 {
+    tokens: NonFungibleToken,
+    metadata: LazyOption<NFTContractMetadata>,
+    admin_pub_key: String,
+    course_metadata_by_id: LookupMap<CourseId, CourseMetadata>,
+    total_balances: u128,
+    all_msg_25519: UnorderedSet<String>,
+}
+
+impl ContractV1 {
+    /// Roles and the pending owner start empty, the NFT owner keeps full control.
+    pub fn into_current(self) -> Contract {
+        Contract {
+            tokens: self.tokens,
+            metadata: self.metadata,
+            admin_pub_key: self.admin_pub_key,
+            course_metadata_by_id: self.course_metadata_by_id,
+            total_balances: self.total_balances,
+            all_msg_25519: self.all_msg_25519,
+            pending_owner_id: None,
+            role_members: LookupMap::new(CourseStorageKey::RoleMembers.try_to_vec().unwrap()),
+            minting_paused: false,
+        }
+    }
+}
+
+/// State version 2: roles and the two-step ownership transfer, before minting could be paused.
+#[derive(BorshDeserialize, BorshSerialize)]
+pub struct ContractV2 {
+    tokens: NonFungibleToken,
+    metadata: LazyOption<NFTContractMetadata>,
+    admin_pub_key: String,
+    course_metadata_by_id: LookupMap<CourseId, CourseMetadata>,
+    total_balances: u128,
+    all_msg_25519: UnorderedSet<String>,
+    pending_owner_id: Option<AccountId>,
+    role_members: LookupMap<Role, UnorderedSet<AccountId>>,
+}
+
+impl ContractV2 {
+    /// Minting is not paused after the upgrade.
+    pub fn into_current(self) -> Contract {
+        Contract {
+            tokens: self.tokens,
+            metadata: self.metadata,
+            admin_pub_key: self.admin_pub_key,
+            course_metadata_by_id: self.course_metadata_by_id,
+            total_balances: self.total_balances,
+            all_msg_25519: self.all_msg_25519,
+            pending_owner_id: self.pending_owner_id,
+            role_members: self.role_members,
+            minting_paused: false,
+        }
+    }
+}
@@ -1,8 +1,9 @@
-use near_sdk::{env, Gas};
+use near_sdk::{env, AccountId, Gas};
+
+use crate::models::contract::{Feature, Payment, Role, STATE_VERSION, STATE_VERSION_KEY};
 
 pub fn yocto_near_to_near(yocto: u128) -> f64 {
-    let near = yocto as f64 / 1e24;
-    return near;
+    yocto as f64 / 1e24
 }
 
 pub fn mul_div_down(x: u128, y: u128, denominator: u128) -> u128 {
@@ -22,14 +23,12 @@ pub fn mul_div_down(x: u128, y: u128, denominator: u128) -> u128 {
 pub fn random_in_range(start: i64, end: i64) -> u64 {
     let block_timestamp = env::block_timestamp();
     //todo
-    let random_seed = block_timestamp % ((end - start).abs() as u64 + 1) + start as u64;
-
-    random_seed
+    block_timestamp % ((end - start).unsigned_abs() + 1) + start as u64
 }
 
 pub fn sqrt(x: u128) -> u128 {
     // Kiểm tra giá trị x
-    if x <= 0 {
+    if x == 0 {
         return 0;
     }
 
@@ -46,7 +45,48 @@ pub fn sqrt(x: u128) -> u128 {
     }
 
     // Trả về giá trị căn bậc 2
-    return lo - 1;
+    lo - 1
+}
+
+// layout version of the stored state, state written before versioning was introduced is version 0
+pub fn read_state_version() -> u8 {
+    env::storage_read(STATE_VERSION_KEY).map_or(0, |version| version[0])
+}
+
+pub fn write_state_version() {
+    env::storage_write(STATE_VERSION_KEY, &[STATE_VERSION]);
+}
+
+impl Payment {
+    pub(crate) fn internal_has_role(&self, role: &Role, account_id: &AccountId) -> bool {
+        account_id == &self.owner_id
+            || self.role_members.get(role).is_some_and(|members| members.contains(account_id))
+    }
+
+    // panic unless the caller is the admin
+    pub(crate) fn assert_owner(&self) {
+        if env::predecessor_account_id() != self.owner_id {
+            env::panic_str("Only the admin can call this method.");
+        }
+    }
+
+    // panic unless the caller is the admin or holds the role
+    pub(crate) fn assert_role(&self, role: Role) {
+        if !self.internal_has_role(&role, &env::predecessor_account_id()) {
+            env::panic_str(&format!("Caller does not have the {:?} role.", role));
+        }
+    }
+
+    pub(crate) fn internal_is_paused(&self, feature: &Feature) -> bool {
+        self.paused_features.contains(feature)
+    }
+
+    // panic while a pauser has halted the feature
+    pub(crate) fn assert_not_paused(&self, feature: Feature) {
+        if self.internal_is_paused(&feature) {
+            env::panic_str(&format!("{:?} is paused.", feature));
+        }
+    }
 }
 
 pub const GAS_FOR_CROSS_CALL: Gas = Gas(3_000_000_000_000);
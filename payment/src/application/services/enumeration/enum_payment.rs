@@ -1,5 +1,6 @@
-use near_sdk::{env, near_bindgen, AccountId};
+use near_sdk::{near_bindgen, AccountId};
 
+use crate::application::repository::read_state_version;
 use crate::models::{
     contract::{Payment, PaymentExt, PaymentEnum, UserTokenDepositRecord},
 };
@@ -17,4 +18,9 @@ impl PaymentEnum for Payment {
             Some(self.list_assets.iter().map(|asset| asset.token_id.clone()).collect())
         }
     }
+
+    // layout version of the stored state
+    fn get_state_version(&self) -> u8 {
+        read_state_version()
+    }
 }
@@ -1,10 +1,8 @@
-use core::num;
-use std::hash::RandomState;
-
-use near_sdk::{env, json_types::U128, near_bindgen, AccountId, Balance, Gas, PromiseOrValue};
+use near_sdk::{env, json_types::U128, near_bindgen, AccountId, Gas, PromiseOrValue};
 
+use crate::models::events::{AdminTransfer, PaymentDeposit, TokenRemoved};
 use crate::models::{
-    contract::{Payment, PaymentExt, PaymentFeature, Assets, UserTokenDepositRecord, TokenDeposit, PaymentInfo}, ft_request::external::cross_edu
+    contract::{Payment, PaymentExt, PaymentFeature, Assets, UserTokenDepositRecord, TokenDeposit, PaymentInfo, Role, Feature}, ft_request::external::{cross_edu, ext_self}
 };
 
 
@@ -12,6 +10,7 @@ pub const GAS_FOR_CROSS_CALL: Gas = Gas(3_000_000_000_000);
 pub const GAS_FOR_FT_TRANSFER_CALL: Gas = Gas(300_000_000_000_000);
 pub const ATTACHED_TRANSFER_FT: u128 = 1;
 pub const ATTACHED_STORAGE_DEPOSIT: u128 = 1_250_000_000_000_000_000_000;
+pub const GAS_FOR_CLAIM_CALLBACK: Gas = Gas(5_000_000_000_000);
 
 #[near_bindgen]
 impl PaymentFeature for Payment {
@@ -23,10 +22,13 @@ impl PaymentFeature for Payment {
         msg: String,
     ) -> PromiseOrValue<U128> {
 
-        env::log_str(&format!("Received {} tokens from {}", amount.0, sender_id));
-        
         let token_id_from_msg = env::predecessor_account_id();
 
+        // returning the amount makes the token contract give it back to the sender
+        if self.internal_is_paused(&Feature::Deposit) {
+            return PromiseOrValue::Value(amount);
+        }
+
         if !self.list_assets.iter().any(|asset| asset.token_id == token_id_from_msg.clone()) {
             env::panic_str("Token ID from message does not match any token ID in the list.");
         }
@@ -34,10 +36,18 @@ impl PaymentFeature for Payment {
         let payment_info: Vec<PaymentInfo> = near_sdk::serde_json::from_str(&msg)
             .expect("Invalid message format");
 
-        for user_info in payment_info {
-            let user = self.records_user_by_id.get(&user_info.user_id);
-
-            if user.is_none() {
+        for user_info in payment_info.iter() {
+            if let Some(mut user) = self.records_user_by_id.get(&user_info.user_id) {
+                if let Some(deposit) = user.deposits.iter_mut().find(|d| d.token_id == token_id_from_msg) {
+                    deposit.amount += user_info.amount;
+                } else {
+                    user.deposits.push(TokenDeposit {
+                        token_id: token_id_from_msg.clone(),
+                        amount: user_info.amount,
+                    });
+                }
+                self.records_user_by_id.insert(&user_info.user_id, &user);
+            } else {
                 let user_record = UserTokenDepositRecord {
                     user_id: user_info.user_id.clone(),
                     deposits: vec![TokenDeposit {
@@ -48,35 +58,31 @@ impl PaymentFeature for Payment {
 
 
                 self.records_user_by_id.insert(&user_info.user_id, &user_record);
-            } else {
-                let mut user = user.unwrap();
-                
-                if let Some(deposit) = user.deposits.iter_mut().find(|d| d.token_id == token_id_from_msg) {
-                    deposit.amount += user_info.amount;
-                } else {
-                    user.deposits.push(TokenDeposit {
-                        token_id: token_id_from_msg.clone(),
-                        amount: user_info.amount,
-                    });
-                }
-                self.records_user_by_id.insert(&user_info.user_id, &user);
             }
         }
+
+        let deposits: Vec<PaymentDeposit> = payment_info.iter()
+            .map(|user_info| PaymentDeposit {
+                sender_id: &sender_id,
+                user_id: &user_info.user_id,
+                token_id: &token_id_from_msg,
+                amount: U128(user_info.amount),
+            })
+            .collect();
+        PaymentDeposit::emit_many(&deposits);
+
         PromiseOrValue::Value(U128(0))
 
     }
 
+    // treasury manager can add a supported token
     fn add_token(
         &mut self,
         token_id: String,
     ) {
-
-        if env::signer_account_id() != self.owner_id {
-            env::panic_str("Only the admin can add a new token.");
-        }
+        self.assert_role(Role::TreasuryManager);
 
         if self.list_assets.iter().any(|asset| asset.token_id == AccountId::new_unchecked(token_id.clone())) {
-            env::log_str("Token already exists in the list.");
             return;
         }
 
@@ -99,29 +105,34 @@ impl PaymentFeature for Payment {
         &mut self,
         token_id: String,
     ) {
-        let signer_id = env::signer_account_id();
+        self.assert_not_paused(Feature::Claim);
+
+        let caller_id = env::predecessor_account_id();
         let token_id_account = AccountId::new_unchecked(token_id.clone());
 
-        if let Some(mut user_record) = self.records_user_by_id.get(&signer_id.clone()) {
+        if let Some(mut user_record) = self.records_user_by_id.get(&caller_id.clone()) {
             if let Some(deposit) = user_record.deposits.iter_mut().find(|d| d.token_id == token_id_account) {
                 if deposit.amount > 0 {
                     // User has assets with the specified token_id, allow withdrawal
+                    // the deposit is zeroed now and restored by the callback if the transfer fails
                     cross_edu::ext(token_id_account.to_owned())
                     .with_static_gas(GAS_FOR_CROSS_CALL)
                     .with_attached_deposit(ATTACHED_STORAGE_DEPOSIT)
-                    .storage_deposit(signer_id.clone())
+                    .storage_deposit(caller_id.clone())
                     .then(
                         cross_edu::ext(token_id_account.to_owned())
                         .with_static_gas(GAS_FOR_CROSS_CALL)
                         .with_attached_deposit(ATTACHED_TRANSFER_FT)
-                        .ft_transfer(signer_id.clone(), U128(deposit.amount)));
+                        .ft_transfer(caller_id.clone(), U128(deposit.amount)))
+                    .then(
+                        ext_self::ext(env::current_account_id())
+                        .with_static_gas(GAS_FOR_CLAIM_CALLBACK)
+                        .resolve_claim(caller_id.clone(), token_id_account.clone(), U128(deposit.amount)));
 
-                    let withdrawn_amount = deposit.amount;
                     deposit.amount = 0; // Reset the amount to zero after withdrawal
-                    env::log_str(&format!("{}", withdrawn_amount));
 
                     // Update the user record in the storage
-                    self.records_user_by_id.insert(&signer_id, &user_record);
+                    self.records_user_by_id.insert(&caller_id, &user_record);
                 } else {
                     env::panic_str("User does not have any assets with the specified token_id.");
                 }
@@ -133,23 +144,24 @@ impl PaymentFeature for Payment {
         }
     }
 
+    // admin proposes a new admin, the ownership moves once the new admin calls accept_admin
     fn change_admin(&mut self, new_admin: AccountId) {
-       
-        if env::signer_account_id() != self.owner_id {
-            env::panic_str("Only the current admin can change the admin.");
-        }
+        self.assert_owner();
 
-        self.owner_id = new_admin.clone();
-        env::log_str(&format!("Admin changed to: {}", new_admin));
+        AdminTransfer {
+            old_owner_id: &self.owner_id,
+            new_owner_id: &new_admin,
+        }
+        .emit_proposed();
+        self.pending_owner_id = Some(new_admin);
     }
 
+    // treasury manager can delete a supported token
     fn delete_token_by_token_id(
         &mut self,
         token_id: AccountId
     ) {
-        if env::signer_account_id() != self.owner_id {
-            env::panic_str("Only the admin can delete a token.");
-        }
+        self.assert_role(Role::TreasuryManager);
 
         // Remove the token from all user records
         for user_id in self.all_user_id.iter() {
@@ -159,7 +171,10 @@ impl PaymentFeature for Payment {
             }
         }
 
-        // Log the deletion of the token
-        env::log_str(&format!("Token with ID {} has been deleted.", token_id));
+        TokenRemoved {
+            token_id: &token_id,
+            account_id: &env::predecessor_account_id(),
+        }
+        .emit();
     }
 }
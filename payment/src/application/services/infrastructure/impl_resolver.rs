@@ -0,0 +1,41 @@
+use near_sdk::{is_promise_success, json_types::U128, near_bindgen, AccountId};
+
+use crate::models::events::PaymentClaim;
+use crate::models::{
+    contract::{Payment, PaymentExt, TokenDeposit},
+    ft_request::external::PaymentResolver
+};
+
+#[near_bindgen]
+impl PaymentResolver for Payment {
+
+    // give the claimed amount back to the user's deposit if the transfer failed
+    #[private]
+    fn resolve_claim(&mut self, user_id: AccountId, token_id: AccountId, amount: U128) {
+        let event = PaymentClaim {
+            user_id: &user_id,
+            token_id: &token_id,
+            amount,
+        };
+
+        if is_promise_success() {
+            event.emit();
+            return;
+        }
+
+        let mut user_record = self.records_user_by_id.get(&user_id)
+            .expect("User record not found.");
+
+        if let Some(deposit) = user_record.deposits.iter_mut().find(|d| d.token_id == token_id) {
+            deposit.amount += amount.0;
+        } else {
+            user_record.deposits.push(TokenDeposit {
+                token_id: token_id.clone(),
+                amount: amount.0,
+            });
+        }
+        self.records_user_by_id.insert(&user_id, &user_record);
+
+        event.emit_failed();
+    }
+}
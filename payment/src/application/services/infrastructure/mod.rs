@@ -1,3 +1,4 @@
-use near_sdk::env;
-
 pub mod impl_payment;
+pub mod impl_resolver;
+pub mod impl_access;
+pub mod impl_pause;
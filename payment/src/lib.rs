@@ -1,29 +1,92 @@
-use models::contract::{Payment, PaymentStorageKey, PaymentExt};
+use application::repository::{read_state_version, write_state_version};
+use models::contract::{Payment, PaymentStorageKey, PaymentExt, STATE_VERSION};
+use models::migration::{PaymentV1, PaymentV2};
 use near_sdk::borsh::BorshSerialize;
 use near_sdk::{
     collections::{LookupMap, UnorderedSet},
-    env, near_bindgen, AccountId,
+    env, near_bindgen, AccountId, Gas, Promise,
 };
 
 pub mod application;
 pub mod models;
 
+// gas kept by `upgrade` itself, the rest of the prepaid gas goes to `migrate`
+const GAS_FOR_UPGRADE: Gas = Gas(10_000_000_000_000);
+
 #[near_bindgen]
 impl Payment {
     #[init]
     pub fn init() -> Self {
-        let owner_id = env::signer_account_id();
+        let owner_id = env::predecessor_account_id();
 
         Self::new(owner_id)
     }
 
     #[init]
     pub fn new(owner_id: AccountId) -> Self {
+        write_state_version();
+
         Self {
             owner_id,
             list_assets: Vec::new(),
             records_user_by_id: LookupMap::new(PaymentStorageKey::RecordUserById.try_to_vec().unwrap()),
-            all_user_id: UnorderedSet::new(PaymentStorageKey::AllUserId.try_to_vec().unwrap())
+            all_user_id: UnorderedSet::new(PaymentStorageKey::AllUserId.try_to_vec().unwrap()),
+            pending_owner_id: None,
+            role_members: LookupMap::new(PaymentStorageKey::RoleMembers.try_to_vec().unwrap()),
+            paused_features: Vec::new(),
         }
     }
+
+    // admin deploys the wasm passed as the raw call input and migrates the state in the same batch,
+    // a failing migration reverts the deployment
+    pub fn upgrade(&self) -> Promise {
+        self.assert_owner();
+
+        let code = env::input().expect("Missing contract code");
+        let migrate_gas = env::prepaid_gas() - env::used_gas() - GAS_FOR_UPGRADE;
+
+        Promise::new(env::current_account_id())
+            .deploy_contract(code)
+            .function_call("migrate".to_string(), Vec::new(), 0, migrate_gas)
+    }
+
+    // bring the stored state up to STATE_VERSION, a no-op rewrite when only the code changed
+    #[private]
+    #[init(ignore_state)]
+    pub fn migrate() -> Self {
+        let contract = match read_state_version() {
+            // version 0 only lacked the version tag, its layout is the one of version 1
+            0 | 1 => Self::migrate_from_v1(),
+            2 => Self::migrate_from_v2(),
+            STATE_VERSION => env::state_read().expect("Failed to read state"),
+            version => env::panic_str(&format!("Unknown state version {}", version)),
+        };
+
+        write_state_version();
+        contract
+    }
+
+    // nothing is paused after the upgrade
+    fn migrate_from_v2() -> Self {
+        let old: PaymentV2 = env::state_read().expect("Failed to read old state");
+
+        let mut contract = Self::new(old.owner_id);
+        contract.list_assets = old.list_assets;
+        contract.records_user_by_id = old.records_user_by_id;
+        contract.all_user_id = old.all_user_id;
+        contract.pending_owner_id = old.pending_owner_id;
+        contract.role_members = old.role_members;
+        contract
+    }
+
+    // roles and the pending admin start empty, the admin keeps full control
+    fn migrate_from_v1() -> Self {
+        let old: PaymentV1 = env::state_read().expect("Failed to read old state");
+
+        let mut contract = Self::new(old.owner_id);
+        contract.list_assets = old.list_assets;
+        contract.records_user_by_id = old.records_user_by_id;
+        contract.all_user_id = old.all_user_id;
+        contract
+    }
 }
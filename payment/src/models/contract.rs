@@ -1,7 +1,6 @@
 use near_sdk::borsh::{self, BorshDeserialize, BorshSerialize};
 use near_sdk::{
     collections::{LookupMap, UnorderedSet},
-    json_types::Base64VecU8,
     near_bindgen,
     serde::{Deserialize, Serialize},
     AccountId, PanicOnDefault,
@@ -9,6 +8,9 @@ use near_sdk::{
     json_types::U128
 };
 
+pub const STATE_VERSION: u8 = 3; // bump together with a migration arm in `migrate`
+pub const STATE_VERSION_KEY: &[u8] = b"STATE_VERSION";
+
 #[near_bindgen]
 #[derive(PanicOnDefault, BorshDeserialize, BorshSerialize)]
 pub struct Payment {
@@ -17,6 +19,35 @@ pub struct Payment {
     pub list_assets: Vec<Assets>,
     pub records_user_by_id: LookupMap<AccountId, UserTokenDepositRecord>,
     pub all_user_id: UnorderedSet<AccountId>,
+    /// Account proposed by `change_admin`, becomes the owner once it calls `accept_admin`.
+    pub pending_owner_id: Option<AccountId>,
+    pub role_members: LookupMap<Role, UnorderedSet<AccountId>>,
+    /// Features halted by a pauser, see `Feature`.
+    pub paused_features: Vec<Feature>,
+}
+
+/// Roles the owner can grant. The owner implicitly holds every role.
+#[derive(BorshDeserialize, BorshSerialize, Deserialize, Serialize, Clone, PartialEq, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub enum Role {
+    TreasuryManager, // manages supported tokens
+    Pauser,          // pauses the payment contract in an emergency
+}
+
+impl Role {
+    pub const ALL: [Role; 2] = [Role::TreasuryManager, Role::Pauser];
+}
+
+/// Parts of the payment contract a pauser can halt independently during an incident.
+#[derive(BorshDeserialize, BorshSerialize, Deserialize, Serialize, Clone, PartialEq, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub enum Feature {
+    Deposit, // deposits through ft_on_transfer, refunded while paused
+    Claim,   // claim
+}
+
+impl Feature {
+    pub const ALL: [Feature; 2] = [Feature::Deposit, Feature::Claim];
 }
 
 #[derive(BorshDeserialize, BorshSerialize, Deserialize, Serialize, Clone)]
@@ -51,6 +82,16 @@ pub struct TokenDeposit {
 pub enum PaymentStorageKey {
     RecordUserById,
     AllUserId,
+    RoleMembers,
+}
+
+impl PaymentStorageKey {
+    pub fn role_members_prefix(role: &Role) -> Vec<u8> {
+        let mut prefix = Vec::with_capacity(4 + 1);
+        prefix.extend_from_slice(b"role");
+        prefix.extend_from_slice(&role.try_to_vec().unwrap());
+        prefix
+    }
 }
 
 pub trait PaymentFeature {
@@ -82,7 +123,26 @@ pub trait PaymentFeature {
     );
 }
 
+pub trait PaymentAccess {
+    fn grant_role(&mut self, role: Role, account_id: AccountId);
+    fn revoke_role(&mut self, role: Role, account_id: AccountId);
+    fn accept_admin(&mut self);
+    fn has_role(&self, role: Role, account_id: AccountId) -> bool;
+    fn get_role_members(&self, role: Role) -> Vec<AccountId>;
+    fn get_roles(&self, account_id: AccountId) -> Vec<Role>;
+    fn get_owner(&self) -> AccountId;
+    fn get_pending_owner(&self) -> Option<AccountId>;
+}
+
+pub trait PaymentPause {
+    fn pause(&mut self, features: Option<Vec<Feature>>);
+    fn unpause(&mut self, features: Option<Vec<Feature>>);
+    fn is_paused(&self, feature: Feature) -> bool;
+    fn get_paused_features(&self) -> Vec<Feature>;
+}
+
 pub trait PaymentEnum {
     fn get_user_info_by_id(&self, user_id: AccountId) -> Option<UserTokenDepositRecord>;
     fn get_all_token_id(&self) -> Option<Vec<AccountId>>;
+    fn get_state_version(&self) -> u8;
 }
\ No newline at end of file
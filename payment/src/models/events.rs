@@ -0,0 +1,126 @@
+//! NEP-297 events emitted by the payment contract, logged as `EVENT_JSON:{...}`.
+use near_sdk::serde::Serialize;
+use near_sdk::{env, json_types::U128, serde_json, AccountId};
+
+use super::contract::{Feature, Role};
+
+pub const EVENT_STANDARD: &str = "openedu_payment";
+pub const EVENT_STANDARD_VERSION: &str = "1.0.0";
+
+#[derive(Serialize)]
+#[serde(crate = "near_sdk::serde")]
+struct EventLog<'a, T: Serialize> {
+    standard: &'static str,
+    version: &'static str,
+    event: &'a str,
+    data: &'a [T],
+}
+
+fn emit_event<T: Serialize>(event: &str, data: &[T]) {
+    let log = EventLog {
+        standard: EVENT_STANDARD,
+        version: EVENT_STANDARD_VERSION,
+        event,
+        data,
+    };
+    env::log_str(&format!("EVENT_JSON:{}", serde_json::to_string(&log).unwrap()));
+}
+
+/// Amount credited to a user by an `ft_transfer_call` into the payment contract.
+#[derive(Serialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct PaymentDeposit<'a> {
+    pub sender_id: &'a AccountId,
+    pub user_id: &'a AccountId,
+    pub token_id: &'a AccountId,
+    pub amount: U128,
+}
+
+impl PaymentDeposit<'_> {
+    pub fn emit_many(data: &[PaymentDeposit<'_>]) {
+        emit_event("payment_deposit", data)
+    }
+}
+
+#[derive(Serialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct PaymentClaim<'a> {
+    pub user_id: &'a AccountId,
+    pub token_id: &'a AccountId,
+    pub amount: U128,
+}
+
+impl PaymentClaim<'_> {
+    pub fn emit(self) {
+        emit_event("payment_claim", &[self])
+    }
+
+    pub fn emit_failed(self) {
+        emit_event("payment_claim_failed", &[self])
+    }
+}
+
+#[derive(Serialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct RoleChanged<'a> {
+    pub role: &'a Role,
+    pub account_id: &'a AccountId,
+}
+
+impl RoleChanged<'_> {
+    pub fn emit_granted(self) {
+        emit_event("role_granted", &[self])
+    }
+
+    pub fn emit_revoked(self) {
+        emit_event("role_revoked", &[self])
+    }
+}
+
+#[derive(Serialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct AdminTransfer<'a> {
+    pub old_owner_id: &'a AccountId,
+    pub new_owner_id: &'a AccountId,
+}
+
+impl AdminTransfer<'_> {
+    pub fn emit_proposed(self) {
+        emit_event("admin_transfer_proposed", &[self])
+    }
+
+    pub fn emit_accepted(self) {
+        emit_event("admin_transfer_accepted", &[self])
+    }
+}
+
+#[derive(Serialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct PauseChanged<'a> {
+    pub features: &'a [Feature],
+    pub account_id: &'a AccountId,
+}
+
+impl PauseChanged<'_> {
+    pub fn emit_paused(self) {
+        emit_event("paused", &[self])
+    }
+
+    pub fn emit_unpaused(self) {
+        emit_event("unpaused", &[self])
+    }
+}
+
+/// Token taken off the supported list, the deposits users held in it are dropped.
+#[derive(Serialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct TokenRemoved<'a> {
+    pub token_id: &'a AccountId,
+    pub account_id: &'a AccountId,
+}
+
+impl TokenRemoved<'_> {
+    pub fn emit(self) {
+        emit_event("token_removed", &[self])
+    }
+}
@@ -6,4 +6,9 @@ pub trait CrossCall {
     fn storage_deposit(&mut self, account_id: AccountId);
     fn ft_transfer_call(&mut self, receiver_id: AccountId, amount: U128, msg: String);
     fn ft_balance_of(&mut self, account_id: AccountId);
+}
+
+#[ext_contract(ext_self)]
+pub trait PaymentResolver {
+    fn resolve_claim(&mut self, user_id: AccountId, token_id: AccountId, amount: U128);
 }
\ No newline at end of file
@@ -0,0 +1,27 @@
+use near_sdk::borsh::{self, BorshDeserialize, BorshSerialize};
+use near_sdk::{
+    collections::{LookupMap, UnorderedSet},
+    AccountId,
+};
+
+use super::contract::{Assets, Role, UserTokenDepositRecord};
+
+/// State versions 0 and 1: single owner, before roles.
+#[derive(BorshDeserialize, BorshSerialize)]
+pub struct PaymentV1 {
+    pub owner_id: AccountId,
+    pub list_assets: Vec<Assets>,
+    pub records_user_by_id: LookupMap<AccountId, UserTokenDepositRecord>,
+    pub all_user_id: UnorderedSet<AccountId>,
+}
+
+/// State version 2: roles and the two-step admin transfer, before the pause flags.
+#[derive(BorshDeserialize, BorshSerialize)]
+pub struct PaymentV2 {
+    pub owner_id: AccountId,
+    pub list_assets: Vec<Assets>,
+    pub records_user_by_id: LookupMap<AccountId, UserTokenDepositRecord>,
+    pub all_user_id: UnorderedSet<AccountId>,
+    pub pending_owner_id: Option<AccountId>,
+    pub role_members: LookupMap<Role, UnorderedSet<AccountId>>,
+}
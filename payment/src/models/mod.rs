@@ -1,4 +1,6 @@
 pub mod contract;
+pub mod events;
 pub mod ft_request;
+pub mod migration;
 
 pub type PoolId = u64;
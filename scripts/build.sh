@@ -9,6 +9,12 @@ cd ..
 cd "`dirname $0`"/../launchpad
 cargo build --all --target wasm32-unknown-unknown --release
 cd ..
+cd "`dirname $0`"/../nft_25519
+cargo build --all --target wasm32-unknown-unknown --release
+cd ..
+cd "`dirname $0`"/../mock_dao
+cargo build --all --target wasm32-unknown-unknown --release
+cd ..
 
 # Create the directory if it doesn't exist
 mkdir -p ./res/